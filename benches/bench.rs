@@ -0,0 +1,105 @@
+#[macro_use]
+extern crate criterion;
+extern crate curv;
+
+use criterion::Criterion;
+use curv::arithmetic::traits::Modulo;
+use curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use curv::BigInt;
+
+mod scalar_arithmetic {
+    use super::*;
+
+    pub fn bench_secp256k1_scalar(c: &mut Criterion) {
+        use curv::elliptic::curves::secp256_k1::FE;
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        c.bench_function("secp256k1 scalar add", move |bencher| {
+            bencher.iter(|| a.add(&b.get_element()))
+        });
+        c.bench_function("secp256k1 scalar mul", move |bencher| {
+            bencher.iter(|| a.mul(&b.get_element()))
+        });
+        c.bench_function("secp256k1 scalar invert", move |bencher| {
+            bencher.iter(|| a.invert())
+        });
+    }
+
+    // the previous implementation of the secp256k1 scalar operations, kept for comparison
+    pub fn bench_big_int_scalar(c: &mut Criterion) {
+        use curv::elliptic::curves::secp256_k1::FE;
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        c.bench_function("BigInt mod q add", move |bencher| {
+            bencher.iter(|| {
+                let res: FE =
+                    ECScalar::from(&BigInt::mod_add(&a.to_big_int(), &b.to_big_int(), &FE::q()));
+                res
+            })
+        });
+        c.bench_function("BigInt mod q mul", move |bencher| {
+            bencher.iter(|| {
+                let res: FE =
+                    ECScalar::from(&BigInt::mod_mul(&a.to_big_int(), &b.to_big_int(), &FE::q()));
+                res
+            })
+        });
+        c.bench_function("BigInt mod q invert", move |bencher| {
+            bencher.iter(|| {
+                let res: FE = ECScalar::from(&BigInt::mod_inv(&a.to_big_int(), &FE::q()));
+                res
+            })
+        });
+    }
+
+    pub fn bench_p256_scalar(c: &mut Criterion) {
+        use curv::elliptic::curves::p256::FE;
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        c.bench_function("p256 scalar mul", move |bencher| {
+            bencher.iter(|| a.mul(&b.get_element()))
+        });
+        c.bench_function("p256 scalar invert", move |bencher| {
+            bencher.iter(|| a.invert())
+        });
+    }
+
+    criterion_group!(
+        scalar_arithmetic,
+        bench_secp256k1_scalar,
+        bench_big_int_scalar,
+        bench_p256_scalar
+    );
+}
+
+mod fixed_base {
+    use super::*;
+
+    fn bench_curve<P>(c: &mut Criterion, name: &str)
+    where
+        P: ECPoint + Clone + 'static,
+        P::Scalar: Clone + 'static,
+    {
+        let g: P = ECPoint::generator();
+        let k: P::Scalar = ECScalar::new_random();
+        let k2 = k.clone();
+        c.bench_function(&format!("{} generator * k", name), move |bencher| {
+            bencher.iter(|| g.clone() * k.clone())
+        });
+        c.bench_function(&format!("{} mul_base", name), move |bencher| {
+            bencher.iter(|| P::mul_base(&k2))
+        });
+    }
+
+    pub fn bench_mul_base(c: &mut Criterion) {
+        bench_curve::<curv::elliptic::curves::secp256_k1::GE>(c, "secp256k1");
+        bench_curve::<curv::elliptic::curves::p256::GE>(c, "p256");
+        bench_curve::<curv::elliptic::curves::ed25519::GE>(c, "ed25519");
+        bench_curve::<curv::elliptic::curves::curve_ristretto::GE>(c, "ristretto");
+        bench_curve::<curv::elliptic::curves::bls12_381::GE>(c, "bls12_381");
+    }
+
+    criterion_group!(fixed_base, bench_mul_base);
+}
+
+criterion_main!(scalar_arithmetic::scalar_arithmetic, fixed_base::fixed_base);
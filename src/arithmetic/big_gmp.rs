@@ -14,8 +14,8 @@
     @license GPL-3.0+ <https://github.com/KZen-networks/curv/blob/master/LICENSE>
 */
 
-use super::rand::rngs::OsRng;
-use super::rand::RngCore;
+use super::montgomery::ModulusN;
+use super::rand::{CryptoRng, RngCore};
 use super::traits::{
     BitManipulation, ConvertFrom, Converter, Modulo, NumberTests, Samplable, ZeroizeBN, EGCD,
 };
@@ -75,15 +75,42 @@ impl Modulo for Mpz {
     fn mod_inv(a: &Self, modulus: &Self) -> Self {
         a.invert(modulus).unwrap()
     }
+
+    fn mod_pow_sec(base: &Self, exponent: &Self, modulus: &Self) -> Self {
+        assert!(modulus.tstbit(0), "modulus must be odd");
+        assert!(*exponent >= Mpz::zero(), "negative exponent");
+        // mpz_powm_sec requires a positive exponent
+        if exponent.is_zero() {
+            return Mpz::one().mod_floor(modulus);
+        }
+        base.mod_floor(modulus).powm_sec(exponent, modulus)
+    }
+
+    fn mod_mul_sec(a: &Self, b: &Self, modulus: &Self) -> Self {
+        ModulusN::new(modulus).mul(a, b)
+    }
+
+    // a^-1 = r * (a * r)^-1 for a random unit r, the variable time inversion only sees a * r
+    // which is a uniformly random unit
+    fn mod_inv_sec(a: &Self, modulus: &Self) -> Self {
+        let r = loop {
+            let r = Self::sample_below(modulus);
+            if r.gcd(modulus) == Mpz::one() {
+                break r;
+            }
+        };
+        let a_r_inv = Self::mod_inv(&Self::mod_mul(a, &r, modulus), modulus);
+        Self::mod_mul(&a_r_inv, &r, modulus)
+    }
 }
 
 impl Samplable for Mpz {
-    fn sample_below(upper: &Self) -> Self {
+    fn sample_below_with_rng<R: CryptoRng + RngCore>(upper: &Self, rng: &mut R) -> Self {
         assert!(*upper > Mpz::zero());
 
         let bits = upper.bit_length();
         loop {
-            let n = Self::sample(bits);
+            let n = Self::sample_with_rng(bits, rng);
             if n < *upper {
                 return n;
             }
@@ -105,8 +132,7 @@ impl Samplable for Mpz {
         }
     }
 
-    fn sample(bit_size: usize) -> Self {
-        let mut rng = OsRng::new().unwrap();
+    fn sample_with_rng<R: CryptoRng + RngCore>(bit_size: usize, rng: &mut R) -> Self {
         let bytes = (bit_size - 1) / 8 + 1;
         let mut buf: Vec<u8> = vec![0; bytes];
         rng.fill_bytes(&mut buf);
@@ -161,121 +187,3 @@ impl ConvertFrom<Mpz> for u64 {
         opt_x.unwrap()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::Converter;
-    use super::Modulo;
-    use super::Mpz;
-    use super::Samplable;
-
-    use std::cmp;
-
-    #[test]
-    #[should_panic]
-    fn sample_below_zero_test() {
-        Mpz::sample_below(&Mpz::from(-1));
-    }
-
-    #[test]
-    fn sample_below_test() {
-        let upper_bound = Mpz::from(10);
-
-        for _ in 1..100 {
-            let r = Mpz::sample_below(&upper_bound);
-            assert!(r < upper_bound);
-        }
-    }
-
-    #[test]
-    #[should_panic]
-    fn invalid_range_test() {
-        Mpz::sample_range(&Mpz::from(10), &Mpz::from(9));
-    }
-
-    #[test]
-    fn sample_range_test() {
-        let upper_bound = Mpz::from(10);
-        let lower_bound = Mpz::from(5);
-
-        for _ in 1..100 {
-            let r = Mpz::sample_range(&lower_bound, &upper_bound);
-            assert!(r < upper_bound && r >= lower_bound);
-        }
-    }
-
-    #[test]
-    fn strict_sample_range_test() {
-        let len = 249;
-
-        for _ in 1..100 {
-            let a = Mpz::sample(len);
-            let b = Mpz::sample(len);
-            let lower_bound = cmp::min(a.clone(), b.clone());
-            let upper_bound = cmp::max(a.clone(), b.clone());
-
-            let r = Mpz::strict_sample_range(&lower_bound, &upper_bound);
-            assert!(r < upper_bound && r >= lower_bound);
-        }
-    }
-
-    #[test]
-    fn strict_sample_test() {
-        let len = 249;
-
-        for _ in 1..100 {
-            let a = Mpz::strict_sample(len);
-            assert_eq!(a.bit_length(), len);
-        }
-    }
-
-    //test mod_sub: a-b mod n where a-b >0
-    #[test]
-    fn test_mod_sub_modulo() {
-        let a = Mpz::from(10);
-        let b = Mpz::from(5);
-        let modulo = Mpz::from(3);
-        let res = Mpz::from(2);
-        assert_eq!(res, Mpz::mod_sub(&a, &b, &modulo));
-    }
-
-    //test mod_sub: a-b mod n where a-b <0
-    #[test]
-    fn test_mod_sub_negative_modulo() {
-        let a = Mpz::from(5);
-        let b = Mpz::from(10);
-        let modulo = Mpz::from(3);
-        let res = Mpz::from(1);
-        assert_eq!(res, Mpz::mod_sub(&a, &b, &modulo));
-    }
-
-    #[test]
-    fn test_mod_mul() {
-        let a = Mpz::from(4);
-        let b = Mpz::from(5);
-        let modulo = Mpz::from(3);
-        let res = Mpz::from(2);
-        assert_eq!(res, Mpz::mod_mul(&a, &b, &modulo));
-    }
-
-    #[test]
-    fn test_mod_pow() {
-        let a = Mpz::from(2);
-        let b = Mpz::from(3);
-        let modulo = Mpz::from(3);
-        let res = Mpz::from(2);
-        assert_eq!(res, Mpz::mod_pow(&a, &b, &modulo));
-    }
-
-    #[test]
-    fn test_to_hex() {
-        let b = Mpz::from(11);
-        assert_eq!("b", b.to_hex());
-    }
-
-    #[test]
-    fn test_from_hex() {
-        let a = Mpz::from(11);
-        assert_eq!(Mpz::from_hex(&a.to_hex()), a);
-    }
-}
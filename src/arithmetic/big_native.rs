@@ -0,0 +1,601 @@
+/*
+    Curv
+
+    Copyright 2018 by Kzen Networks
+
+    This file is part of Cryptography utilities library
+    (https://github.com/KZen-networks/cryptography-utils)
+
+    Cryptography utilities is free software: you can redistribute
+    it and/or modify it under the terms of the GNU General Public
+    License as published by the Free Software Foundation, either
+    version 3 of the License, or (at your option) any later version.
+
+    @license GPL-3.0+ <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+//! Pure Rust BigInt backed by num-bigint, enabled with the `num-bigint` feature (and
+//! `--no-default-features` to drop GMP). It mirrors the part of the `Mpz` API of rust-gmp-kzen
+//! that curv uses, with the same semantics: `/` and `%` truncate, `>>` floors, `mod_floor`
+//! takes the sign of the divisor, `modulus` and `invert` return values in [0, |m|),
+//! `bit_length(0) == 1` and serde uses a hex string.
+
+use super::montgomery::ModulusN;
+use super::rand::{CryptoRng, RngCore};
+use super::traits::{
+    BitManipulation, ConvertFrom, Converter, Modulo, NumberTests, Primes, Samplable, ZeroizeBN,
+    EGCD,
+};
+
+use num_bigint::{BigInt as BN, ParseBigIntError, Sign};
+use num_integer::Integer;
+use num_traits::{Num, One, Signed, ToPrimitive, Zero};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use std::fmt;
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Neg, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+use std::sync::atomic;
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BigInt {
+    num: BN,
+}
+
+impl BigInt {
+    fn inner(num: BN) -> BigInt {
+        BigInt { num }
+    }
+
+    pub fn zero() -> BigInt {
+        BigInt::inner(BN::zero())
+    }
+
+    pub fn one() -> BigInt {
+        BigInt::inner(BN::one())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    pub fn to_str_radix(&self, base: u8) -> String {
+        self.num.to_str_radix(u32::from(base))
+    }
+
+    pub fn from_str_radix(s: &str, base: u8) -> Result<BigInt, ParseBigIntError> {
+        BN::from_str_radix(s, u32::from(base)).map(BigInt::inner)
+    }
+
+    // number of bits of |self|, like mpz_sizeinbase(self, 2) it is 1 for zero
+    pub fn bit_length(&self) -> usize {
+        std::cmp::max(self.num.bits() as usize, 1)
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::inner(self.num.abs())
+    }
+
+    pub fn div_floor(&self, other: &BigInt) -> BigInt {
+        BigInt::inner(self.num.div_floor(&other.num))
+    }
+
+    pub fn mod_floor(&self, other: &BigInt) -> BigInt {
+        BigInt::inner(self.num.mod_floor(&other.num))
+    }
+
+    pub fn nextprime(&self) -> BigInt {
+        let two = BN::from(2);
+        if self.num < two {
+            return BigInt::inner(two);
+        }
+        let mut candidate: BN = &self.num + 1u32;
+        if candidate.is_even() {
+            candidate += 1;
+        }
+        while !BigInt::inner(candidate.clone()).is_probable_prime(20) {
+            candidate += 2;
+        }
+        BigInt::inner(candidate)
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        BigInt::inner(self.num.gcd(&other.num))
+    }
+
+    /// Given (a, b), return (g, s, t) such that g = gcd(a, b) = s*a + t*b.
+    pub fn gcdext(&self, other: &BigInt) -> (BigInt, BigInt, BigInt) {
+        let egcd = self.num.extended_gcd(&other.num);
+        if egcd.gcd.is_negative() {
+            (
+                BigInt::inner(-egcd.gcd),
+                BigInt::inner(-egcd.x),
+                BigInt::inner(-egcd.y),
+            )
+        } else {
+            (
+                BigInt::inner(egcd.gcd),
+                BigInt::inner(egcd.x),
+                BigInt::inner(egcd.y),
+            )
+        }
+    }
+
+    pub fn lcm(&self, other: &BigInt) -> BigInt {
+        BigInt::inner(self.num.lcm(&other.num))
+    }
+
+    pub fn is_multiple_of(&self, other: &BigInt) -> bool {
+        self.num.is_multiple_of(&other.num)
+    }
+
+    pub fn divides(&self, other: &BigInt) -> bool {
+        other.is_multiple_of(self)
+    }
+
+    pub fn modulus(&self, modulo: &BigInt) -> BigInt {
+        let m = modulo.num.abs();
+        BigInt::inner(self.num.mod_floor(&m))
+    }
+
+    pub fn invert(&self, modulo: &BigInt) -> Option<BigInt> {
+        self.num.modinv(&modulo.num.abs()).map(BigInt::inner)
+    }
+
+    pub fn pow(&self, exp: u32) -> BigInt {
+        BigInt::inner(self.num.pow(exp))
+    }
+
+    // a negative exponent uses the inverse of the base, like mpz_powm
+    pub fn powm(&self, exp: &BigInt, modulus: &BigInt) -> BigInt {
+        let m = modulus.num.abs();
+        if exp.num.is_negative() {
+            let base_inv = self.num.modinv(&m).expect("base is not invertible");
+            BigInt::inner(base_inv.modpow(&-&exp.num, &m))
+        } else {
+            BigInt::inner(self.num.modpow(&exp.num, &m))
+        }
+    }
+
+    pub fn setbit(&mut self, bit_index: usize) {
+        self.num.set_bit(bit_index as u64, true)
+    }
+
+    pub fn clrbit(&mut self, bit_index: usize) {
+        self.num.set_bit(bit_index as u64, false)
+    }
+
+    pub fn tstbit(&self, bit_index: usize) -> bool {
+        self.num.bit(bit_index as u64)
+    }
+
+    pub fn root(&self, n: u32) -> BigInt {
+        BigInt::inner(self.num.nth_root(n))
+    }
+
+    pub fn sqrt(&self) -> BigInt {
+        BigInt::inner(self.num.sqrt())
+    }
+}
+
+impl ZeroizeBN for BigInt {
+    // overwrites the limbs in place before setting the value to zero
+    fn zeroize_bn(&mut self) {
+        let digits = (self.num.bits() as usize).div_ceil(32);
+        self.num.assign_from_slice(Sign::Plus, &vec![0u32; digits]);
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl Converter for BigInt {
+    fn to_vec(value: &BigInt) -> Vec<u8> {
+        let bytes: Vec<u8> = value.into();
+        bytes
+    }
+
+    fn to_hex(&self) -> String {
+        self.to_str_radix(super::HEX_RADIX)
+    }
+
+    fn from_hex(value: &str) -> BigInt {
+        BigInt::from_str_radix(value, super::HEX_RADIX).expect("Error in serialization")
+    }
+}
+
+impl Modulo for BigInt {
+    fn mod_pow(base: &Self, exponent: &Self, modulus: &Self) -> Self {
+        base.powm(exponent, modulus)
+    }
+
+    fn mod_mul(a: &Self, b: &Self, modulus: &Self) -> Self {
+        (a.mod_floor(modulus) * b.mod_floor(modulus)).mod_floor(modulus)
+    }
+
+    fn mod_sub(a: &Self, b: &Self, modulus: &Self) -> Self {
+        let a_m = a.mod_floor(modulus);
+        let b_m = b.mod_floor(modulus);
+
+        let sub_op = a_m - b_m + modulus;
+        sub_op.mod_floor(modulus)
+    }
+
+    fn mod_add(a: &Self, b: &Self, modulus: &Self) -> Self {
+        (a.mod_floor(modulus) + b.mod_floor(modulus)).mod_floor(modulus)
+    }
+
+    fn mod_inv(a: &Self, modulus: &Self) -> Self {
+        a.invert(modulus).unwrap()
+    }
+
+    fn mod_pow_sec(base: &Self, exponent: &Self, modulus: &Self) -> Self {
+        ModulusN::new(modulus).pow(base, exponent)
+    }
+
+    fn mod_mul_sec(a: &Self, b: &Self, modulus: &Self) -> Self {
+        ModulusN::new(modulus).mul(a, b)
+    }
+
+    // a^-1 = r * (a * r)^-1 for a random unit r, the variable time inversion only sees a * r
+    // which is a uniformly random unit
+    fn mod_inv_sec(a: &Self, modulus: &Self) -> Self {
+        let r = loop {
+            let r = Self::sample_below(modulus);
+            if r.gcd(modulus) == BigInt::one() {
+                break r;
+            }
+        };
+        let a_r_inv = Self::mod_inv(&Self::mod_mul(a, &r, modulus), modulus);
+        Self::mod_mul(&a_r_inv, &r, modulus)
+    }
+}
+
+impl Samplable for BigInt {
+    fn sample_below_with_rng<R: CryptoRng + RngCore>(upper: &Self, rng: &mut R) -> Self {
+        assert!(*upper > BigInt::zero());
+
+        let bits = upper.bit_length();
+        loop {
+            let n = Self::sample_with_rng(bits, rng);
+            if n < *upper {
+                return n;
+            }
+        }
+    }
+
+    fn sample_range(lower: &Self, upper: &Self) -> Self {
+        assert!(upper > lower);
+        lower + Self::sample_below(&(upper - lower))
+    }
+
+    fn strict_sample_range(lower: &Self, upper: &Self) -> Self {
+        assert!(upper > lower);
+        loop {
+            let n = lower + Self::sample_below(&(upper - lower));
+            if n > *lower && n < *upper {
+                return n;
+            }
+        }
+    }
+
+    fn sample_with_rng<R: CryptoRng + RngCore>(bit_size: usize, rng: &mut R) -> Self {
+        let bytes = (bit_size - 1) / 8 + 1;
+        let mut buf: Vec<u8> = vec![0; bytes];
+        rng.fill_bytes(&mut buf);
+        Self::from(&*buf) >> (bytes * 8 - bit_size)
+    }
+
+    fn strict_sample(bit_size: usize) -> Self {
+        loop {
+            let n = Self::sample(bit_size);
+            if n.bit_length() == bit_size {
+                return n;
+            }
+        }
+    }
+}
+
+impl NumberTests for BigInt {
+    fn is_zero(me: &Self) -> bool {
+        me.is_zero()
+    }
+    fn is_even(me: &Self) -> bool {
+        me.num.is_even()
+    }
+    fn is_negative(me: &Self) -> bool {
+        me.num.is_negative()
+    }
+}
+
+impl EGCD for BigInt {
+    fn egcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        a.gcdext(b)
+    }
+}
+
+impl BitManipulation for BigInt {
+    fn set_bit(&mut self, bit: usize, bit_val: bool) {
+        if bit_val {
+            self.setbit(bit);
+        } else {
+            self.clrbit(bit);
+        }
+    }
+
+    fn test_bit(&self, bit: usize) -> bool {
+        self.tstbit(bit)
+    }
+}
+
+impl ConvertFrom<BigInt> for u64 {
+    fn _from(x: &BigInt) -> u64 {
+        let opt_x: Option<u64> = x.into();
+        opt_x.unwrap()
+    }
+}
+
+macro_rules! impl_oper {
+    ($tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
+        impl $tr<BigInt> for BigInt {
+            type Output = BigInt;
+            fn $meth(self, other: BigInt) -> BigInt {
+                BigInt::inner(self.num.$meth(other.num))
+            }
+        }
+
+        impl<'a> $tr<&'a BigInt> for BigInt {
+            type Output = BigInt;
+            fn $meth(self, other: &BigInt) -> BigInt {
+                BigInt::inner(self.num.$meth(&other.num))
+            }
+        }
+
+        impl<'a> $tr<BigInt> for &'a BigInt {
+            type Output = BigInt;
+            fn $meth(self, other: BigInt) -> BigInt {
+                BigInt::inner((&self.num).$meth(other.num))
+            }
+        }
+
+        impl<'a, 'b> $tr<&'b BigInt> for &'a BigInt {
+            type Output = BigInt;
+            fn $meth(self, other: &BigInt) -> BigInt {
+                BigInt::inner((&self.num).$meth(&other.num))
+            }
+        }
+
+        impl $tr_assign<BigInt> for BigInt {
+            fn $meth_assign(&mut self, other: BigInt) {
+                self.num.$meth_assign(other.num)
+            }
+        }
+
+        impl<'a> $tr_assign<&'a BigInt> for BigInt {
+            fn $meth_assign(&mut self, other: &BigInt) {
+                self.num.$meth_assign(&other.num)
+            }
+        }
+    };
+
+    (both $num: ident, $tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
+        impl_oper!(normal $num, $tr, $meth, $tr_assign, $meth_assign);
+        impl_oper!(reverse $num, $tr, $meth);
+    };
+
+    (normal $num: ident, $tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
+        impl $tr<$num> for BigInt {
+            type Output = BigInt;
+            fn $meth(self, other: $num) -> BigInt {
+                BigInt::inner(self.num.$meth(other))
+            }
+        }
+
+        impl<'a> $tr<$num> for &'a BigInt {
+            type Output = BigInt;
+            fn $meth(self, other: $num) -> BigInt {
+                BigInt::inner((&self.num).$meth(other))
+            }
+        }
+
+        impl $tr_assign<$num> for BigInt {
+            fn $meth_assign(&mut self, other: $num) {
+                self.num.$meth_assign(other)
+            }
+        }
+    };
+
+    (reverse $num: ident, $tr: ident, $meth: ident) => {
+        impl $tr<BigInt> for $num {
+            type Output = BigInt;
+            fn $meth(self, other: BigInt) -> BigInt {
+                BigInt::inner(self.$meth(other.num))
+            }
+        }
+
+        impl<'a> $tr<&'a BigInt> for $num {
+            type Output = BigInt;
+            fn $meth(self, other: &BigInt) -> BigInt {
+                BigInt::inner(self.$meth(&other.num))
+            }
+        }
+    };
+}
+
+impl_oper!(Add, add, AddAssign, add_assign);
+impl_oper!(both u64, Add, add, AddAssign, add_assign);
+
+impl_oper!(Sub, sub, SubAssign, sub_assign);
+impl_oper!(normal u64, Sub, sub, SubAssign, sub_assign);
+impl_oper!(reverse u64, Sub, sub);
+
+impl_oper!(Mul, mul, MulAssign, mul_assign);
+impl_oper!(both i64, Mul, mul, MulAssign, mul_assign);
+impl_oper!(both u64, Mul, mul, MulAssign, mul_assign);
+
+impl_oper!(Div, div, DivAssign, div_assign);
+impl_oper!(normal u64, Div, div, DivAssign, div_assign);
+
+impl_oper!(Rem, rem, RemAssign, rem_assign);
+impl_oper!(normal u64, Rem, rem, RemAssign, rem_assign);
+
+impl_oper!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_oper!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_oper!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::inner(-&self.num)
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::inner(-self.num)
+    }
+}
+
+impl Shl<usize> for &BigInt {
+    type Output = BigInt;
+    fn shl(self, other: usize) -> BigInt {
+        BigInt::inner(&self.num << other)
+    }
+}
+
+impl Shr<usize> for &BigInt {
+    type Output = BigInt;
+    fn shr(self, other: usize) -> BigInt {
+        BigInt::inner(&self.num >> other)
+    }
+}
+
+impl Shl<usize> for BigInt {
+    type Output = BigInt;
+    fn shl(self, other: usize) -> BigInt {
+        BigInt::inner(self.num << other)
+    }
+}
+
+impl Shr<usize> for BigInt {
+    type Output = BigInt;
+    fn shr(self, other: usize) -> BigInt {
+        BigInt::inner(self.num >> other)
+    }
+}
+
+impl ShlAssign<usize> for BigInt {
+    fn shl_assign(&mut self, other: usize) {
+        self.num <<= other
+    }
+}
+
+impl ShrAssign<usize> for BigInt {
+    fn shr_assign(&mut self, other: usize) {
+        self.num >>= other
+    }
+}
+
+// like mpz_export the sign is dropped
+impl From<&BigInt> for Vec<u8> {
+    fn from(other: &BigInt) -> Vec<u8> {
+        other.num.to_bytes_be().1
+    }
+}
+
+impl From<&BigInt> for Option<i64> {
+    fn from(other: &BigInt) -> Option<i64> {
+        other.num.to_i64()
+    }
+}
+
+impl From<&BigInt> for Option<u64> {
+    fn from(other: &BigInt) -> Option<u64> {
+        other.num.to_u64()
+    }
+}
+
+impl<'a> From<&'a [u8]> for BigInt {
+    fn from(other: &'a [u8]) -> BigInt {
+        BigInt::inner(BN::from_bytes_be(Sign::Plus, other))
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(other: u64) -> BigInt {
+        BigInt::inner(BN::from(other))
+    }
+}
+
+impl From<u32> for BigInt {
+    fn from(other: u32) -> BigInt {
+        BigInt::inner(BN::from(other))
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(other: i64) -> BigInt {
+        BigInt::inner(BN::from(other))
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(other: i32) -> BigInt {
+        BigInt::inner(BN::from(other))
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigInt::from_str_radix(s, 10)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
+
+impl Serialize for BigInt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for BigInt {
+    fn deserialize<D>(deserializer: D) -> Result<BigInt, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BigIntVisitor)
+    }
+}
+
+struct BigIntVisitor;
+
+impl<'de> Visitor<'de> for BigIntVisitor {
+    type Value = BigInt;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("BigInt")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<BigInt, E> {
+        BigInt::from_str_radix(s, super::HEX_RADIX).map_err(de::Error::custom)
+    }
+}
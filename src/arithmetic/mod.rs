@@ -18,5 +18,280 @@ use rand;
 
 const HEX_RADIX: u8 = 16;
 
+#[cfg(feature = "rust-gmp-kzen")]
 pub mod big_gmp;
+#[cfg(all(feature = "num-bigint", not(feature = "rust-gmp-kzen")))]
+pub mod big_native;
+pub mod montgomery;
+pub mod number_theory;
+pub mod primes;
 pub mod traits;
+
+#[cfg(test)]
+mod tests {
+    use super::traits::{BitManipulation, Converter, Modulo, NumberTests, Samplable, EGCD};
+    use crate::BigInt;
+
+    use std::cmp;
+
+    #[test]
+    #[should_panic]
+    fn sample_below_zero_test() {
+        BigInt::sample_below(&BigInt::from(-1));
+    }
+
+    #[test]
+    fn sample_below_test() {
+        let upper_bound = BigInt::from(10);
+
+        for _ in 1..100 {
+            let r = BigInt::sample_below(&upper_bound);
+            assert!(r < upper_bound);
+        }
+    }
+
+    #[test]
+    fn sample_with_rng_test() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let upper_bound = BigInt::from(2).pow(200) - 1u64;
+        let a = BigInt::sample_below_with_rng(&upper_bound, &mut StdRng::from_seed([3; 32]));
+        let b = BigInt::sample_below_with_rng(&upper_bound, &mut StdRng::from_seed([3; 32]));
+        assert_eq!(a, b);
+        assert!(a < upper_bound);
+        let c = BigInt::sample_below_with_rng(&upper_bound, &mut StdRng::from_seed([4; 32]));
+        assert_ne!(a, c);
+        let mut rng = StdRng::from_seed([3; 32]);
+        assert_ne!(
+            BigInt::sample_with_rng(256, &mut rng),
+            BigInt::sample_with_rng(256, &mut rng)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_range_test() {
+        BigInt::sample_range(&BigInt::from(10), &BigInt::from(9));
+    }
+
+    #[test]
+    fn sample_range_test() {
+        let upper_bound = BigInt::from(10);
+        let lower_bound = BigInt::from(5);
+
+        for _ in 1..100 {
+            let r = BigInt::sample_range(&lower_bound, &upper_bound);
+            assert!(r < upper_bound && r >= lower_bound);
+        }
+    }
+
+    #[test]
+    fn strict_sample_range_test() {
+        let len = 249;
+
+        for _ in 1..100 {
+            let a = BigInt::sample(len);
+            let b = BigInt::sample(len);
+            let lower_bound = cmp::min(a.clone(), b.clone());
+            let upper_bound = cmp::max(a.clone(), b.clone());
+
+            let r = BigInt::strict_sample_range(&lower_bound, &upper_bound);
+            assert!(r < upper_bound && r >= lower_bound);
+        }
+    }
+
+    #[test]
+    fn strict_sample_test() {
+        let len = 249;
+
+        for _ in 1..100 {
+            let a = BigInt::strict_sample(len);
+            assert_eq!(a.bit_length(), len);
+        }
+    }
+
+    //test mod_sub: a-b mod n where a-b >0
+    #[test]
+    fn test_mod_sub_modulo() {
+        let a = BigInt::from(10);
+        let b = BigInt::from(5);
+        let modulo = BigInt::from(3);
+        let res = BigInt::from(2);
+        assert_eq!(res, BigInt::mod_sub(&a, &b, &modulo));
+    }
+
+    //test mod_sub: a-b mod n where a-b <0
+    #[test]
+    fn test_mod_sub_negative_modulo() {
+        let a = BigInt::from(5);
+        let b = BigInt::from(10);
+        let modulo = BigInt::from(3);
+        let res = BigInt::from(1);
+        assert_eq!(res, BigInt::mod_sub(&a, &b, &modulo));
+    }
+
+    #[test]
+    fn test_mod_mul() {
+        let a = BigInt::from(4);
+        let b = BigInt::from(5);
+        let modulo = BigInt::from(3);
+        let res = BigInt::from(2);
+        assert_eq!(res, BigInt::mod_mul(&a, &b, &modulo));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        let a = BigInt::from(2);
+        let b = BigInt::from(3);
+        let modulo = BigInt::from(3);
+        let res = BigInt::from(2);
+        assert_eq!(res, BigInt::mod_pow(&a, &b, &modulo));
+    }
+
+    #[test]
+    fn test_to_hex() {
+        let b = BigInt::from(11);
+        assert_eq!("b", b.to_hex());
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let a = BigInt::from(11);
+        assert_eq!(BigInt::from_hex(&a.to_hex()), a);
+    }
+
+    // the backends agree on the semantics of the BigInt API used by curv
+
+    #[test]
+    fn test_division_and_reduction_of_negatives() {
+        let a = BigInt::from(-7);
+        let m = BigInt::from(3);
+        assert_eq!(&a / &m, BigInt::from(-2));
+        assert_eq!(&a % &m, BigInt::from(-1));
+        assert_eq!(a.mod_floor(&m), BigInt::from(2));
+        assert_eq!(a.mod_floor(&BigInt::from(-3)), BigInt::from(-1));
+        assert_eq!(a.modulus(&BigInt::from(-3)), BigInt::from(2));
+        assert_eq!(&a >> 1, BigInt::from(-4));
+        assert_eq!(BigInt::from(-8).div_floor(&m), BigInt::from(-3));
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        let modulo = BigInt::from(7);
+        assert_eq!(BigInt::mod_inv(&BigInt::from(3), &modulo), BigInt::from(5));
+        assert_eq!(BigInt::from(-3).invert(&modulo), Some(BigInt::from(2)));
+        assert_eq!(BigInt::from(14).invert(&modulo), None);
+        // a negative exponent inverts the base
+        assert_eq!(
+            BigInt::mod_pow(&BigInt::from(3), &BigInt::from(-1), &modulo),
+            BigInt::from(5)
+        );
+    }
+
+    #[test]
+    fn test_constant_time_modulo() {
+        let modulo = BigInt::from(2).pow(127) - 1u64;
+        for _ in 0..10 {
+            let a = BigInt::sample_below(&modulo);
+            let b = BigInt::sample_below(&modulo);
+            let e = BigInt::sample(256);
+            assert_eq!(
+                BigInt::mod_pow_sec(&a, &e, &modulo),
+                BigInt::mod_pow(&a, &e, &modulo)
+            );
+            assert_eq!(
+                BigInt::mod_mul_sec(&a, &b, &modulo),
+                BigInt::mod_mul(&a, &b, &modulo)
+            );
+            assert_eq!(
+                BigInt::mod_inv_sec(&a, &modulo),
+                BigInt::mod_inv(&a, &modulo)
+            );
+        }
+        assert_eq!(
+            BigInt::mod_pow_sec(&BigInt::from(-2), &BigInt::zero(), &modulo),
+            BigInt::one()
+        );
+        // mod_inv_sec also works for an even modulus
+        assert_eq!(
+            BigInt::mod_inv_sec(&BigInt::from(3), &BigInt::from(10)),
+            BigInt::from(7)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mod_pow_sec_even_modulus() {
+        BigInt::mod_pow_sec(&BigInt::from(3), &BigInt::from(5), &BigInt::from(10));
+    }
+
+    #[test]
+    fn test_egcd() {
+        let a = BigInt::from(240);
+        let b = BigInt::from(46);
+        let (g, s, t) = BigInt::egcd(&a, &b);
+        assert_eq!(g, BigInt::from(2));
+        assert_eq!(s * &a + t * &b, g);
+    }
+
+    #[test]
+    fn test_number_tests() {
+        assert!(NumberTests::is_zero(&BigInt::zero()));
+        assert!(BigInt::is_even(&BigInt::from(-4)));
+        assert!(!BigInt::is_even(&BigInt::from(5)));
+        assert!(BigInt::is_negative(&BigInt::from(-1)));
+        assert!(!BigInt::is_negative(&BigInt::zero()));
+    }
+
+    #[test]
+    fn test_bit_manipulation() {
+        let mut a = BigInt::from(5);
+        a.set_bit(1, true);
+        assert_eq!(a, BigInt::from(7));
+        a.set_bit(0, false);
+        assert_eq!(a, BigInt::from(6));
+        assert!(a.test_bit(2));
+        assert!(!a.test_bit(64));
+        assert_eq!(BigInt::zero().bit_length(), 1);
+        assert_eq!(BigInt::from(256).bit_length(), 9);
+    }
+
+    #[test]
+    fn test_bytes_and_strings() {
+        let bytes = vec![1, 0, 255];
+        let a = BigInt::from(&bytes[..]);
+        assert_eq!(a, BigInt::from(65791));
+        assert_eq!(BigInt::to_vec(&a), bytes);
+        assert_eq!(BigInt::to_vec(&BigInt::zero()), vec![0]);
+        assert_eq!(BigInt::to_vec(&-a.clone()), bytes);
+        assert_eq!(a.to_string(), "65791");
+        assert_eq!(format!("{:?}", -a.clone()), "-65791");
+        assert_eq!(BigInt::from_hex("-1010f"), -BigInt::from(65807));
+        let opt: Option<u64> = (&a).into();
+        assert_eq!(opt, Some(65791));
+        let opt: Option<u64> = (&-a).into();
+        assert_eq!(opt, None);
+    }
+
+    #[test]
+    fn test_serde_is_hex() {
+        let a = BigInt::from(-255);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, "\"-ff\"");
+        assert_eq!(serde_json::from_str::<BigInt>(&json).unwrap(), a);
+    }
+
+    #[test]
+    fn test_primes_and_roots() {
+        assert_eq!(BigInt::from(89).nextprime(), BigInt::from(97));
+        assert_eq!(BigInt::from(-5).nextprime(), BigInt::from(2));
+        // 2^127 - 1 is a Mersenne prime
+        let m127 = BigInt::from(2).pow(127) - 1u64;
+        assert_eq!((&m127 - 1u64).nextprime(), m127);
+        assert_eq!(BigInt::from(99).sqrt(), BigInt::from(9));
+        assert_eq!(BigInt::from(1000).root(3), BigInt::from(10));
+        assert_eq!(BigInt::from(12).gcd(&BigInt::from(18)), BigInt::from(6));
+        assert!(BigInt::from(12).is_multiple_of(&BigInt::from(4)));
+    }
+}
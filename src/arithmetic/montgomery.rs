@@ -0,0 +1,460 @@
+/*
+    Curv
+
+    Copyright 2018 by Kzen Networks
+
+    This file is part of Cryptography utilities library
+    (https://github.com/KZen-networks/cryptography-utils)
+
+    Cryptography utilities is free software: you can redistribute
+    it and/or modify it under the terms of the GNU General Public
+    License as published by the Free Software Foundation, either
+    version 3 of the License, or (at your option) any later version.
+
+    @license GPL-3.0+ <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// Modular arithmetic using Montgomery multiplication.
+// Modulus256 is fixed-width (256 bit): elements are represented as 4 little-endian 64-bit limbs,
+// fully reduced modulo `m`, and none of its operations allocate. ModulusN works with moduli of
+// any size and keeps its limbs in vectors, so it allocates.
+// Apart from `invert` (which leaks only whether the input is zero) the operations do not
+// branch on the values of their inputs.
+
+use super::traits::Converter;
+use crate::BigInt;
+
+pub type Limbs = [u64; 4];
+
+/// Parameters of an odd 256 bit modulus `m`:
+/// `m_inv` = -m^{-1} mod 2^64 and `r2` = 2^512 mod m.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Modulus256 {
+    pub m: Limbs,
+    pub m_inv: u64,
+    pub r2: Limbs,
+}
+
+/// a + b + carry, returning (result, carry)
+#[inline(always)]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + (b as u128) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// a - (b + borrow), returning (result, borrow) where borrow is 0 or u64::MAX
+#[inline(always)]
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let ret = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// a + (b * c) + carry, returning (result, carry)
+#[inline(always)]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + ((b as u128) * (c as u128)) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+impl Modulus256 {
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Limbs {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[32 - 8 * (i + 1)..32 - 8 * i]);
+            *limb = u64::from_be_bytes(word);
+        }
+        limbs
+    }
+
+    pub fn to_bytes_be(limbs: &Limbs) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in limbs.iter().enumerate() {
+            bytes[32 - 8 * (i + 1)..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    pub fn is_zero(a: &Limbs) -> bool {
+        (a[0] | a[1] | a[2] | a[3]) == 0
+    }
+
+    /// subtracts m from (carry:a) if the result is not negative. Requires (carry:a) < 2m.
+    #[inline(always)]
+    fn sub_m_if_ge(&self, a: &Limbs, carry: u64) -> Limbs {
+        let (r0, borrow) = sbb(a[0], self.m[0], 0);
+        let (r1, borrow) = sbb(a[1], self.m[1], borrow);
+        let (r2, borrow) = sbb(a[2], self.m[2], borrow);
+        let (r3, borrow) = sbb(a[3], self.m[3], borrow);
+        let (_, borrow) = sbb(carry, 0, borrow);
+        // borrow is all ones if a < m, in which case we keep a
+        [
+            (a[0] & borrow) | (r0 & !borrow),
+            (a[1] & borrow) | (r1 & !borrow),
+            (a[2] & borrow) | (r2 & !borrow),
+            (a[3] & borrow) | (r3 & !borrow),
+        ]
+    }
+
+    /// reduces any 256 bit value modulo m. Requires m > 2^255 (true for all curve orders we use).
+    pub fn reduce(&self, a: &Limbs) -> Limbs {
+        self.sub_m_if_ge(a, 0)
+    }
+
+    pub fn add(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (s0, carry) = adc(a[0], b[0], 0);
+        let (s1, carry) = adc(a[1], b[1], carry);
+        let (s2, carry) = adc(a[2], b[2], carry);
+        let (s3, carry) = adc(a[3], b[3], carry);
+        self.sub_m_if_ge(&[s0, s1, s2, s3], carry)
+    }
+
+    pub fn sub(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (d0, borrow) = sbb(a[0], b[0], 0);
+        let (d1, borrow) = sbb(a[1], b[1], borrow);
+        let (d2, borrow) = sbb(a[2], b[2], borrow);
+        let (d3, borrow) = sbb(a[3], b[3], borrow);
+        // borrow is all ones on underflow, in which case we add m back
+        let (r0, carry) = adc(d0, self.m[0] & borrow, 0);
+        let (r1, carry) = adc(d1, self.m[1] & borrow, carry);
+        let (r2, carry) = adc(d2, self.m[2] & borrow, carry);
+        let (r3, _) = adc(d3, self.m[3] & borrow, carry);
+        [r0, r1, r2, r3]
+    }
+
+    pub fn neg(&self, a: &Limbs) -> Limbs {
+        self.sub(&[0u64; 4], a)
+    }
+
+    /// Montgomery multiplication (CIOS): returns a * b * 2^-256 mod m
+    pub fn mont_mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut t = [0u64; 6];
+        for bi in b.iter() {
+            let mut carry = 0;
+            for j in 0..4 {
+                let (v, c) = mac(t[j], a[j], *bi, carry);
+                t[j] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[4], carry, 0);
+            t[4] = v;
+            t[5] = c;
+
+            let k = t[0].wrapping_mul(self.m_inv);
+            let (_, mut carry) = mac(t[0], k, self.m[0], 0);
+            for j in 1..4 {
+                let (v, c) = mac(t[j], k, self.m[j], carry);
+                t[j - 1] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[4], carry, 0);
+            t[3] = v;
+            t[4] = t[5] + c;
+            t[5] = 0;
+        }
+        self.sub_m_if_ge(&[t[0], t[1], t[2], t[3]], t[4])
+    }
+
+    pub fn to_montgomery(&self, a: &Limbs) -> Limbs {
+        self.mont_mul(a, &self.r2)
+    }
+
+    pub fn from_montgomery(&self, a: &Limbs) -> Limbs {
+        self.mont_mul(a, &[1, 0, 0, 0])
+    }
+
+    pub fn mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        // (a * b * R^-1) * R^2 * R^-1 = a * b
+        self.mont_mul(&self.mont_mul(a, b), &self.r2)
+    }
+
+    /// a^e mod m for a public exponent e
+    pub fn pow_vartime(&self, a: &Limbs, e: &Limbs) -> Limbs {
+        let a_mont = self.to_montgomery(a);
+        let mut acc = self.to_montgomery(&[1, 0, 0, 0]);
+        for limb in e.iter().rev() {
+            for bit in (0..64).rev() {
+                acc = self.mont_mul(&acc, &acc);
+                if (limb >> bit) & 1 == 1 {
+                    acc = self.mont_mul(&acc, &a_mont);
+                }
+            }
+        }
+        self.from_montgomery(&acc)
+    }
+
+    /// modular inverse using Fermat's little theorem, m must be prime.
+    /// Panics on zero, like `BigInt::invert(..).unwrap()`.
+    pub fn invert(&self, a: &Limbs) -> Limbs {
+        assert!(!Self::is_zero(a), "zero has no inverse");
+        let m_minus_2 = self.sub(&self.m, &[2, 0, 0, 0]);
+        self.pow_vartime(a, &m_minus_2)
+    }
+}
+
+/// Montgomery arithmetic modulo an odd modulus `m` of any size, for the constant-time
+/// operations of `Modulo` (mod_pow_sec, mod_mul_sec). Elements are `n` little-endian 64-bit
+/// limbs where `n` is the number of limbs of `m`, so the running time depends only on the
+/// width of `m` (and of the exponent in `pow`), not on the values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModulusN {
+    pub m: Vec<u64>,
+    pub m_inv: u64,
+    pub r2: Vec<u64>,
+}
+
+impl ModulusN {
+    pub fn new(modulus: &BigInt) -> ModulusN {
+        assert!(
+            *modulus > BigInt::one() && modulus.tstbit(0),
+            "modulus must be odd and greater than one"
+        );
+        let n = modulus.bit_length().div_ceil(64);
+        let m = Self::to_limbs(modulus, n);
+        // Newton iteration for m^-1 mod 2^64, every step doubles the number of correct bits
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m[0].wrapping_mul(inv)));
+        }
+        // the modulus is public, so r2 can be computed with BigInt
+        let r2 = (BigInt::one() << (128 * n)).mod_floor(modulus);
+        ModulusN {
+            m,
+            m_inv: inv.wrapping_neg(),
+            r2: Self::to_limbs(&r2, n),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.m.len()
+    }
+
+    /// little-endian limbs of |a|, padded to at least n limbs
+    fn to_limbs(a: &BigInt, n: usize) -> Vec<u64> {
+        let bytes = BigInt::to_vec(a);
+        let mut limbs = vec![0u64; std::cmp::max(n, bytes.len().div_ceil(8))];
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            limbs[i / 8] |= u64::from(*byte) << (8 * (i % 8));
+        }
+        limbs
+    }
+
+    fn to_big_int(a: &[u64]) -> BigInt {
+        let bytes = a
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes().to_vec())
+            .collect::<Vec<u8>>();
+        BigInt::from(&bytes[..])
+    }
+
+    /// the limbs of a mod m. Reducing an input which is already smaller than m does not depend
+    /// on its value.
+    fn reduce(&self, a: &BigInt) -> Vec<u64> {
+        Self::to_limbs(&a.mod_floor(&Self::to_big_int(&self.m)), self.width())
+    }
+
+    /// subtracts m from (carry:a) if the result is not negative. Requires (carry:a) < 2m.
+    fn sub_m_if_ge(&self, a: &[u64], carry: u64) -> Vec<u64> {
+        let mut borrow = 0;
+        let mut r = vec![0u64; self.width()];
+        for j in 0..self.width() {
+            let (v, b) = sbb(a[j], self.m[j], borrow);
+            r[j] = v;
+            borrow = b;
+        }
+        let (_, borrow) = sbb(carry, 0, borrow);
+        // borrow is all ones if a < m, in which case we keep a
+        a.iter()
+            .zip(r.iter())
+            .map(|(a, r)| (a & borrow) | (r & !borrow))
+            .collect()
+    }
+
+    /// Montgomery multiplication (CIOS): returns a * b * 2^(-64 n) mod m
+    pub fn mont_mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let n = self.width();
+        let mut t = vec![0u64; n + 2];
+        for bi in b.iter() {
+            let mut carry = 0;
+            for j in 0..n {
+                let (v, c) = mac(t[j], a[j], *bi, carry);
+                t[j] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[n], carry, 0);
+            t[n] = v;
+            t[n + 1] = c;
+
+            let k = t[0].wrapping_mul(self.m_inv);
+            let (_, mut carry) = mac(t[0], k, self.m[0], 0);
+            for j in 1..n {
+                let (v, c) = mac(t[j], k, self.m[j], carry);
+                t[j - 1] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[n], carry, 0);
+            t[n - 1] = v;
+            t[n] = t[n + 1] + c;
+            t[n + 1] = 0;
+        }
+        self.sub_m_if_ge(&t[..n], t[n])
+    }
+
+    /// a * b mod m
+    pub fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let (a, b) = (self.reduce(a), self.reduce(b));
+        // (a * b * R^-1) * R^2 * R^-1 = a * b
+        Self::to_big_int(&self.mont_mul(&self.mont_mul(&a, &b), &self.r2))
+    }
+
+    /// a^e mod m with a Montgomery ladder: every bit of e costs one multiplication and one
+    /// squaring, and the two registers are swapped without branching. Only the width of e
+    /// (at least the width of m) leaks. e must not be negative.
+    pub fn pow(&self, a: &BigInt, e: &BigInt) -> BigInt {
+        assert!(*e >= BigInt::zero(), "negative exponent");
+        let mut one = vec![0u64; self.width()];
+        one[0] = 1;
+        let mut r0 = self.mont_mul(&one, &self.r2);
+        let mut r1 = self.mont_mul(&self.reduce(a), &self.r2);
+        for limb in Self::to_limbs(e, self.width()).iter().rev() {
+            for bit in (0..64).rev() {
+                let swap = ((limb >> bit) & 1).wrapping_neg();
+                cswap(&mut r0, &mut r1, swap);
+                r1 = self.mont_mul(&r0, &r1);
+                r0 = self.mont_mul(&r0, &r0);
+                cswap(&mut r0, &mut r1, swap);
+            }
+        }
+        Self::to_big_int(&self.mont_mul(&r0, &one))
+    }
+}
+
+/// swaps a and b if mask is all ones, keeps them if it is zero
+#[inline(always)]
+fn cswap(a: &mut [u64], b: &mut [u64], mask: u64) {
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let t = (*x ^ *y) & mask;
+        *x ^= t;
+        *y ^= t;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Limbs, Modulus256, ModulusN};
+    use crate::arithmetic::traits::{Converter, Modulo, Samplable};
+    use crate::BigInt;
+
+    // the order of the secp256k1 group
+    const N: Modulus256 = Modulus256 {
+        m: [
+            0xbfd2_5e8c_d036_4141,
+            0xbaae_dce6_af48_a03b,
+            0xffff_ffff_ffff_fffe,
+            0xffff_ffff_ffff_ffff,
+        ],
+        m_inv: 0x4b0d_ff66_5588_b13f,
+        r2: [
+            0x896c_f214_67d7_d140,
+            0x7414_96c2_0e7c_f878,
+            0xe697_f5e4_5bcd_07c6,
+            0x9d67_1cd5_81c6_9bc5,
+        ],
+    };
+
+    fn to_limbs(n: &BigInt) -> Limbs {
+        let v = BigInt::to_vec(n);
+        let mut bytes = [0u8; 32];
+        bytes[32 - v.len()..].copy_from_slice(&v);
+        Modulus256::from_bytes_be(&bytes)
+    }
+
+    fn to_big_int(a: &Limbs) -> BigInt {
+        BigInt::from(&Modulus256::to_bytes_be(a)[..])
+    }
+
+    fn modulus() -> BigInt {
+        to_big_int(&N.m)
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let a = BigInt::sample_below(&modulus());
+        assert_eq!(to_big_int(&to_limbs(&a)), a);
+    }
+
+    #[test]
+    fn test_add_sub_mul_against_big_int() {
+        let q = modulus();
+        for _ in 0..100 {
+            let a = BigInt::sample_below(&q);
+            let b = BigInt::sample_below(&q);
+            let (al, bl) = (to_limbs(&a), to_limbs(&b));
+            assert_eq!(to_big_int(&N.add(&al, &bl)), BigInt::mod_add(&a, &b, &q));
+            assert_eq!(to_big_int(&N.sub(&al, &bl)), BigInt::mod_sub(&a, &b, &q));
+            assert_eq!(to_big_int(&N.mul(&al, &bl)), BigInt::mod_mul(&a, &b, &q));
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let q = modulus();
+        let q_minus_one = to_limbs(&(q.clone() - BigInt::one()));
+        let one = [1, 0, 0, 0];
+        assert!(Modulus256::is_zero(&N.add(&q_minus_one, &one)));
+        assert_eq!(N.sub(&[0; 4], &one), q_minus_one);
+        assert_eq!(N.mul(&q_minus_one, &q_minus_one), one);
+        assert!(Modulus256::is_zero(&N.reduce(&N.m)));
+        assert_eq!(N.neg(&one), q_minus_one);
+    }
+
+    #[test]
+    fn test_invert() {
+        let q = modulus();
+        for _ in 0..20 {
+            let a = BigInt::sample_below(&q);
+            let a_inv = N.invert(&to_limbs(&a));
+            assert_eq!(to_big_int(&a_inv), BigInt::mod_inv(&a, &q));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invert_zero() {
+        N.invert(&[0; 4]);
+    }
+
+    #[test]
+    fn test_modulus_n_against_big_int() {
+        // odd moduli of a single limb, of a few limbs and of 2048 bits
+        let moduli = [
+            BigInt::from(1_000_003),
+            modulus(),
+            BigInt::sample(300) * 2u64 + 1u64,
+            BigInt::strict_sample(2048) * 2u64 + 1u64,
+        ];
+        for m in moduli.iter() {
+            let ctx = ModulusN::new(m);
+            for _ in 0..5 {
+                let a = BigInt::sample_below(m);
+                let b = BigInt::sample_below(m);
+                let e = BigInt::sample(m.bit_length() + 3);
+                assert_eq!(ctx.mul(&a, &b), BigInt::mod_mul(&a, &b, m));
+                assert_eq!(ctx.pow(&a, &e), BigInt::mod_pow(&a, &e, m));
+            }
+            let m_minus_one = m - BigInt::one();
+            assert_eq!(ctx.pow(&m_minus_one, &BigInt::zero()), BigInt::one());
+            assert_eq!(ctx.pow(&m_minus_one, &BigInt::from(2)), BigInt::one());
+            assert_eq!(ctx.pow(&BigInt::zero(), &BigInt::from(5)), BigInt::zero());
+            // inputs are reduced first
+            assert_eq!(ctx.mul(&(m + BigInt::from(2)), &BigInt::from(-3)), m - 6u64);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_modulus_n_even() {
+        ModulusN::new(&BigInt::from(1_000_002));
+    }
+}
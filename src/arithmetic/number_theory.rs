@@ -0,0 +1,388 @@
+/*
+    Curv
+
+    Copyright 2018 by Kzen Networks
+
+    This file is part of Cryptography utilities library
+    (https://github.com/KZen-networks/cryptography-utils)
+
+    Cryptography utilities is free software: you can redistribute
+    it and/or modify it under the terms of the GNU General Public
+    License as published by the Free Software Foundation, either
+    version 3 of the License, or (at your option) any later version.
+
+    @license GPL-3.0+ <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// Jacobi symbols, modular square roots and the Chinese remainder theorem. Square roots modulo a
+// prime use Tonelli-Shanks, or Cipolla when p - 1 is divisible by a large power of two, and are
+// not constant-time. Square roots modulo pq are combined with CRT from the roots modulo p and q.
+
+use super::traits::Modulo;
+use crate::BigInt;
+
+// the Jacobi symbol (a/n) for an odd n > 0
+pub fn jacobi(a: &BigInt, n: &BigInt) -> i8 {
+    assert!(n.tstbit(0) && *n > BigInt::zero());
+    let mut a = a.modulus(n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        let s = trailing_zeros(&a);
+        a >>= s;
+        let n_mod_8 = small_mod(&n, 8);
+        if s % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            result = -result;
+        }
+        std::mem::swap(&mut a, &mut n);
+        if small_mod(&a, 4) == 3 && small_mod(&n, 4) == 3 {
+            result = -result;
+        }
+        a = a.modulus(&n);
+    }
+    if n == BigInt::one() {
+        result
+    } else {
+        0
+    }
+}
+
+// the smaller of the two square roots of a modulo an odd prime p, or None if a is not a
+// quadratic residue
+pub fn sqrt_mod_p(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.modulus(p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if jacobi(&a, p) != 1 {
+        return None;
+    }
+    let root = if small_mod(p, 4) == 3 {
+        BigInt::mod_pow(&a, &((p + 1u64) >> 2), p)
+    } else {
+        // Tonelli-Shanks takes O(s^2) multiplications where p - 1 = k 2^s, Cipolla O(log p)
+        let s = trailing_zeros(&(p - 1u64));
+        if s * s > p.bit_length() {
+            cipolla(&a, p)
+        } else {
+            tonelli_shanks(&a, p)
+        }
+    };
+    let other = p - &root;
+    Some(if root < other { root } else { other })
+}
+
+// a square root of a modulo pq for distinct odd primes p and q, or None if a is not a quadratic
+// residue
+pub fn sqrt_mod_pq(a: &BigInt, p: &BigInt, q: &BigInt) -> Option<BigInt> {
+    CrtContext::new(p, q).sqrt(a)
+}
+
+// x with x = residues[i] mod moduli[i] for pairwise coprime moduli, in [0, prod moduli)
+pub fn crt(residues: &[BigInt], moduli: &[BigInt]) -> BigInt {
+    assert_eq!(residues.len(), moduli.len());
+    assert!(!moduli.is_empty());
+    let mut x = residues[0].modulus(&moduli[0]);
+    let mut n = moduli[0].clone();
+    for (r, m) in residues.iter().zip(moduli.iter()).skip(1) {
+        // x + n ((r - x) n^-1 mod m)
+        let h = BigInt::mod_mul(&BigInt::mod_sub(r, &x, m), &BigInt::mod_inv(&n, m), m);
+        x += &n * h;
+        n *= m;
+    }
+    x
+}
+
+/// Arithmetic modulo n = pq when the factorization is known, e.g. for Paillier or Rabin
+/// decryption. An exponentiation is split into two of half the size modulo p and q, with the
+/// exponent reduced modulo p - 1 and q - 1, and recombined with Garner's formula, which is about
+/// four times faster than exponentiating modulo n.
+///
+/// p and q are secret so pow uses mod_pow_sec, but sqrt is not constant-time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrtContext {
+    pub p: BigInt,
+    pub q: BigInt,
+    pub n: BigInt,
+    p_minus_one: BigInt,
+    q_minus_one: BigInt,
+    // q^-1 mod p
+    q_inv: BigInt,
+}
+
+impl CrtContext {
+    // p and q are distinct odd primes
+    pub fn new(p: &BigInt, q: &BigInt) -> CrtContext {
+        assert!(p.tstbit(0) && q.tstbit(0) && p != q);
+        CrtContext {
+            p: p.clone(),
+            q: q.clone(),
+            n: p * q,
+            p_minus_one: p - 1u64,
+            q_minus_one: q - 1u64,
+            q_inv: BigInt::mod_inv_sec(q, p),
+        }
+    }
+
+    // (x mod p, x mod q)
+    pub fn split(&self, x: &BigInt) -> (BigInt, BigInt) {
+        (x.modulus(&self.p), x.modulus(&self.q))
+    }
+
+    // x mod n with x = x_p mod p and x = x_q mod q
+    pub fn combine(&self, x_p: &BigInt, x_q: &BigInt) -> BigInt {
+        let h = BigInt::mod_mul_sec(&BigInt::mod_sub(x_p, x_q, &self.p), &self.q_inv, &self.p);
+        x_q.modulus(&self.q) + h * &self.q
+    }
+
+    // base^exponent mod n for a non-negative exponent
+    pub fn pow(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        assert!(*exponent >= BigInt::zero());
+        if exponent.is_zero() {
+            return BigInt::one();
+        }
+        // (e - 1 mod (p - 1)) + 1 is also right for a base divisible by p
+        let reduce = |m: &BigInt| (exponent - 1u64).modulus(m) + 1u64;
+        let (b_p, b_q) = self.split(base);
+        let x_p = BigInt::mod_pow_sec(&b_p, &reduce(&self.p_minus_one), &self.p);
+        let x_q = BigInt::mod_pow_sec(&b_q, &reduce(&self.q_minus_one), &self.q);
+        self.combine(&x_p, &x_q)
+    }
+
+    // a is a square modulo both p and q, as opposed to jacobi(a, n) = 1
+    pub fn is_quadratic_residue(&self, a: &BigInt) -> bool {
+        jacobi(a, &self.p) >= 0 && jacobi(a, &self.q) >= 0
+    }
+
+    // one of the square roots of a modulo n
+    pub fn sqrt(&self, a: &BigInt) -> Option<BigInt> {
+        let r_p = sqrt_mod_p(a, &self.p)?;
+        let r_q = sqrt_mod_p(a, &self.q)?;
+        Some(self.combine(&r_p, &r_q))
+    }
+
+    // all the distinct square roots of a modulo n, four of them if a is coprime to n, in
+    // increasing order
+    pub fn sqrts(&self, a: &BigInt) -> Option<Vec<BigInt>> {
+        let r_p = sqrt_mod_p(a, &self.p)?;
+        let r_q = sqrt_mod_p(a, &self.q)?;
+        let mut roots = Vec::new();
+        for x_p in [r_p.clone(), (&self.p - &r_p).modulus(&self.p)].iter() {
+            for x_q in [r_q.clone(), (&self.q - &r_q).modulus(&self.q)].iter() {
+                let root = self.combine(x_p, x_q);
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+        }
+        roots.sort();
+        Some(roots)
+    }
+}
+
+pub(crate) fn trailing_zeros(n: &BigInt) -> usize {
+    let mut s = 0;
+    while !n.tstbit(s) {
+        s += 1;
+    }
+    s
+}
+
+pub(crate) fn small_mod(n: &BigInt, m: u64) -> u64 {
+    let r: Option<u64> = (&n.modulus(&BigInt::from(m))).into();
+    r.unwrap()
+}
+
+// a is a non-zero quadratic residue modulo p = 1 mod 4
+fn tonelli_shanks(a: &BigInt, p: &BigInt) -> BigInt {
+    let one = BigInt::one();
+    // p - 1 = k 2^s with k odd
+    let p_minus_one = p - 1u64;
+    let s = trailing_zeros(&p_minus_one);
+    let k = &p_minus_one >> s;
+    let mut z = BigInt::from(2);
+    while jacobi(&z, p) != -1 {
+        z += 1u64;
+    }
+
+    let mut m = s;
+    let mut c = BigInt::mod_pow(&z, &k, p);
+    let mut t = BigInt::mod_pow(a, &k, p);
+    let mut r = BigInt::mod_pow(a, &((&k + 1u64) >> 1), p);
+    // r^2 = a t, where t has order 2^i < 2^m and c has order 2^m
+    while t != one {
+        let mut i = 0;
+        let mut t_pow = t.clone();
+        while t_pow != one {
+            t_pow = BigInt::mod_mul(&t_pow, &t_pow, p);
+            i += 1;
+        }
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = BigInt::mod_mul(&b, &b, p);
+        }
+        m = i;
+        c = BigInt::mod_mul(&b, &b, p);
+        t = BigInt::mod_mul(&t, &c, p);
+        r = BigInt::mod_mul(&r, &b, p);
+    }
+    r
+}
+
+// a is a non-zero quadratic residue modulo p. With w^2 - a a non-residue, (w + sqrt(w^2 - a))^
+// ((p + 1) / 2) in F_p^2 is a square root of a in F_p.
+fn cipolla(a: &BigInt, p: &BigInt) -> BigInt {
+    let mut w = BigInt::one();
+    let omega = loop {
+        let omega = BigInt::mod_sub(&BigInt::mod_mul(&w, &w, p), a, p);
+        if jacobi(&omega, p) == -1 {
+            break omega;
+        }
+        w += 1u64;
+    };
+    // (x1 + y1 u)(x2 + y2 u) with u^2 = omega
+    let mul = |(x1, y1): &(BigInt, BigInt), (x2, y2): &(BigInt, BigInt)| {
+        let y1_y2 = BigInt::mod_mul(y1, y2, p);
+        (
+            BigInt::mod_add(
+                &BigInt::mod_mul(x1, x2, p),
+                &BigInt::mod_mul(&y1_y2, &omega, p),
+                p,
+            ),
+            BigInt::mod_add(&BigInt::mod_mul(x1, y2, p), &BigInt::mod_mul(x2, y1, p), p),
+        )
+    };
+    let exponent = (p + 1u64) >> 1;
+    let base = (w, BigInt::one());
+    let mut result = (BigInt::one(), BigInt::zero());
+    for i in (0..exponent.bit_length()).rev() {
+        result = mul(&result, &result);
+        if exponent.tstbit(i) {
+            result = mul(&result, &base);
+        }
+    }
+    result.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::traits::{Primes, Samplable};
+
+    #[test]
+    fn test_jacobi() {
+        let p = BigInt::from(1_000_003);
+        let exp = (&p - 1u64) >> 1;
+        for a in 0..50u64 {
+            let a = BigInt::from(a);
+            let euler = BigInt::mod_pow(&a, &exp, &p);
+            let expected = if euler.is_zero() {
+                0
+            } else if euler == BigInt::one() {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(jacobi(&a, &p), expected);
+        }
+        // (2/15) = (2/3)(2/5) = 1 even though 2 is not a square mod 15
+        assert_eq!(jacobi(&BigInt::from(2), &BigInt::from(15)), 1);
+        assert_eq!(jacobi(&BigInt::from(-1), &BigInt::from(7)), -1);
+        assert_eq!(jacobi(&BigInt::from(6), &BigInt::from(15)), 0);
+        assert_eq!(jacobi(&BigInt::from(8), &BigInt::from(1)), 1);
+    }
+
+    #[test]
+    fn test_sqrt_mod_p() {
+        // 3 mod 4, 5 mod 8, 2^32 k + 1 and a 256-bit prime
+        let p = BigInt::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        let fermat = BigInt::from(65537);
+        let primes = [
+            BigInt::from(1_000_003),
+            BigInt::from(1_000_037),
+            BigInt::from(2_748_779_069_441u64),
+            p,
+            fermat,
+        ];
+        for p in primes.iter() {
+            assert!(p.is_probable_prime(10));
+            for _ in 0..10 {
+                let x = BigInt::sample_below(p);
+                let a = BigInt::mod_mul(&x, &x, p);
+                let root = sqrt_mod_p(&a, p).unwrap();
+                assert_eq!(BigInt::mod_mul(&root, &root, p), a);
+                assert!(root <= p - &root);
+                if !a.is_zero() && small_mod(p, 4) == 1 {
+                    for root in [tonelli_shanks(&a, p), cipolla(&a, p)].iter() {
+                        assert_eq!(BigInt::mod_mul(root, root, p), a);
+                    }
+                }
+            }
+            let mut z = BigInt::from(2);
+            while jacobi(&z, p) != -1 {
+                z += 1u64;
+            }
+            assert_eq!(sqrt_mod_p(&z, p), None);
+            assert_eq!(sqrt_mod_p(p, p), Some(BigInt::zero()));
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        let moduli = [BigInt::from(3), BigInt::from(5), BigInt::from(7)];
+        let residues = [BigInt::from(2), BigInt::from(3), BigInt::from(-5)];
+        assert_eq!(crt(&residues, &moduli), BigInt::from(23));
+
+        let p = BigInt::sample_prime(128);
+        let q = BigInt::sample_prime(128);
+        let ctx = CrtContext::new(&p, &q);
+        let x = BigInt::sample_below(&ctx.n);
+        let (x_p, x_q) = ctx.split(&x);
+        assert_eq!(ctx.combine(&x_p, &x_q), x);
+        assert_eq!(crt(&[x_p, x_q], &[p.clone(), q.clone()]), x);
+
+        for e in [
+            BigInt::zero(),
+            BigInt::one(),
+            &ctx.p_minus_one * &ctx.q_minus_one,
+            BigInt::sample(300),
+        ]
+        .iter()
+        {
+            assert_eq!(ctx.pow(&x, e), BigInt::mod_pow(&x, e, &ctx.n));
+            // a base which is not coprime to n
+            assert_eq!(ctx.pow(&p, e), BigInt::mod_pow(&p, e, &ctx.n));
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod_pq() {
+        let p = BigInt::sample_blum_prime(128);
+        let q = BigInt::sample_prime(128);
+        let ctx = CrtContext::new(&p, &q);
+        let x = BigInt::sample_below(&ctx.n);
+        let a = BigInt::mod_mul(&x, &x, &ctx.n);
+        assert!(ctx.is_quadratic_residue(&a));
+        let root = sqrt_mod_pq(&a, &p, &q).unwrap();
+        assert_eq!(BigInt::mod_mul(&root, &root, &ctx.n), a);
+        let roots = ctx.sqrts(&a).unwrap();
+        assert_eq!(roots.len(), 4);
+        assert!(roots.contains(&x));
+        for root in roots.iter() {
+            assert_eq!(BigInt::mod_mul(root, root, &ctx.n), a);
+        }
+
+        // -1 is not a square mod a Blum prime, but (-1/n) may be 1
+        let minus_one = &ctx.n - 1u64;
+        assert!(!ctx.is_quadratic_residue(&minus_one));
+        assert_eq!(ctx.sqrt(&minus_one), None);
+        assert_eq!(
+            ctx.sqrts(&BigInt::mod_mul(&p, &p, &ctx.n)).unwrap().len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,440 @@
+/*
+    Curv
+
+    Copyright 2018 by Kzen Networks
+
+    This file is part of Cryptography utilities library
+    (https://github.com/KZen-networks/cryptography-utils)
+
+    Cryptography utilities is free software: you can redistribute
+    it and/or modify it under the terms of the GNU General Public
+    License as published by the Free Software Foundation, either
+    version 3 of the License, or (at your option) any later version.
+
+    @license GPL-3.0+ <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// Primality testing and prime generation, written against the BigInt API only so that it is
+// shared by all the BigInt backends.
+//
+// The test is Baillie-PSW: R. Baillie and S. S. Wagstaff Jr. Lucas Pseudoprimes. Mathematics of
+// Computation 35(152), pages 1391–1417, 1980, i.e. trial division, a strong Fermat (Miller-Rabin)
+// test to base 2 and a strong Lucas test with Selfridge's parameters, to which no counterexample
+// is known. Strong primes are generated with J. Gordon. Strong RSA Keys. Electronics Letters
+// 20(12), pages 514–516, 1984. Seeded generation hashes the seed and a counter with SHA-256 into
+// candidates, like the random prime generation of FIPS 186-4.
+
+use super::number_theory::{jacobi, small_mod, trailing_zeros};
+use super::traits::{Modulo, Primes, Samplable};
+use crate::BigInt;
+
+use digest::Digest;
+use sha2::Sha256;
+
+// random Miller-Rabin rounds on top of Baillie-PSW for the primes we generate
+const MILLER_RABIN_ROUNDS: u32 = 20;
+// the candidates are sieved with the odd primes below this bound
+const SIEVE_BOUND: u64 = 2048;
+
+lazy_static::lazy_static! {
+    static ref SMALL_PRIMES: Vec<u64> = {
+        let mut is_composite = vec![false; SIEVE_BOUND as usize];
+        let mut primes = Vec::new();
+        for i in 3..SIEVE_BOUND as usize {
+            if !is_composite[i] && i % 2 == 1 {
+                primes.push(i as u64);
+                for j in (i * i..SIEVE_BOUND as usize).step_by(i) {
+                    is_composite[j] = true;
+                }
+            }
+        }
+        primes
+    };
+}
+
+impl Primes for BigInt {
+    fn is_probable_prime(&self, rounds: u32) -> bool {
+        let two = BigInt::from(2);
+        if *self < two {
+            return false;
+        }
+        if !self.tstbit(0) {
+            return *self == two;
+        }
+        for r in SMALL_PRIMES.iter() {
+            if *self == BigInt::from(*r) {
+                return true;
+            }
+            if small_mod(self, *r) == 0 {
+                return false;
+            }
+        }
+        // no factor below the square root
+        if *self < BigInt::from(SIEVE_BOUND * SIEVE_BOUND) {
+            return true;
+        }
+        if !miller_rabin(self, &two) || !strong_lucas(self) {
+            return false;
+        }
+        // a in [2, n - 2]
+        let upper = self - 1u64;
+        (0..rounds).all(|_| miller_rabin(self, &BigInt::sample_range(&two, &upper)))
+    }
+
+    fn sample_prime(bit_size: usize) -> Self {
+        assert!(bit_size >= 2);
+        loop {
+            let mut start = BigInt::strict_sample(bit_size);
+            start.setbit(0);
+            let candidate = sieve_search(start, &BigInt::from(2), bit_size, &[0], |p| {
+                p.is_probable_prime(MILLER_RABIN_ROUNDS)
+            });
+            if let Some(p) = candidate {
+                return p;
+            }
+        }
+    }
+
+    fn sample_safe_prime(bit_size: usize) -> Self {
+        assert!(bit_size >= 3);
+        loop {
+            // q is odd (except for p = 5), so p = 3 mod 4
+            let mut start = BigInt::strict_sample(bit_size);
+            start.setbit(0);
+            start.setbit(1);
+            // p = 1 mod r means that r divides q
+            let candidate = sieve_search(start, &BigInt::from(4), bit_size, &[0, 1], |p| {
+                p.is_probable_prime(MILLER_RABIN_ROUNDS)
+                    && (p >> 1).is_probable_prime(MILLER_RABIN_ROUNDS)
+            });
+            if let Some(p) = candidate {
+                return p;
+            }
+        }
+    }
+
+    fn sample_blum_prime(bit_size: usize) -> Self {
+        assert!(bit_size >= 2);
+        loop {
+            let mut start = BigInt::strict_sample(bit_size);
+            start.setbit(0);
+            start.setbit(1);
+            let candidate = sieve_search(start, &BigInt::from(4), bit_size, &[0], |p| {
+                p.is_probable_prime(MILLER_RABIN_ROUNDS)
+            });
+            if let Some(p) = candidate {
+                return p;
+            }
+        }
+    }
+
+    fn sample_strong_prime(bit_size: usize) -> Self {
+        assert!(bit_size >= 64);
+        let is_prime = |p: &BigInt| p.is_probable_prime(MILLER_RABIN_ROUNDS);
+        loop {
+            // s and t of about half the size, so that 2 r s is 2^30 times smaller than p
+            let s = BigInt::sample_prime(bit_size / 2 - 8);
+            let t = BigInt::sample_prime(bit_size / 2 - 24);
+            // r = 2 i t + 1
+            let two_t = &t * 2u64;
+            let r = match sieve_search(&two_t + 1u64, &two_t, bit_size / 2, &[0], is_prime) {
+                Some(r) => r,
+                None => continue,
+            };
+            // p0 = 2 (s^(r - 2) mod r) s - 1 is 1 mod r and -1 mod s, and so is p0 + 2 j r s
+            let p0 = BigInt::mod_pow(&s, &(&r - 2u64), &r) * &s * 2u64 - 1u64;
+            let step = &r * &s * 2u64;
+            let lower = BigInt::one() << (bit_size - 1);
+            let start = if p0 < lower {
+                let j = (&lower - &p0 + &step - 1u64) / &step;
+                p0 + j * &step
+            } else {
+                p0
+            };
+            if let Some(p) = sieve_search(start, &step, bit_size, &[0], is_prime) {
+                return p;
+            }
+        }
+    }
+
+    fn prime_from_seed(seed: &[u8], bit_size: usize) -> (Self, u32) {
+        assert!(bit_size >= 2);
+        (0..u32::MAX)
+            .map(|counter| (seeded_candidate(seed, bit_size, counter), counter))
+            .find(|(candidate, _)| candidate.is_probable_prime(MILLER_RABIN_ROUNDS))
+            .expect("no prime for this seed")
+    }
+
+    // the candidate at counter is self and is prime, and all the candidates before it are not
+    fn verify_prime_from_seed(&self, seed: &[u8], bit_size: usize, counter: u32) -> bool {
+        bit_size >= 2
+            && seeded_candidate(seed, bit_size, counter) == *self
+            && self.is_probable_prime(MILLER_RABIN_ROUNDS)
+            && (0..counter).all(|c| {
+                !seeded_candidate(seed, bit_size, c).is_probable_prime(MILLER_RABIN_ROUNDS)
+            })
+    }
+}
+
+// the first of start, start + step, start + 2 step, ... of at most bit_size bits which passes
+// is_prime and whose residue modulo every small odd prime is not in `excluded`. The residues
+// are updated with machine words along the way.
+fn sieve_search<F>(
+    start: BigInt,
+    step: &BigInt,
+    bit_size: usize,
+    excluded: &[u64],
+    is_prime: F,
+) -> Option<BigInt>
+where
+    F: Fn(&BigInt) -> bool,
+{
+    // small candidates could be one of the small primes
+    let sieve: &[u64] = if bit_size > 32 { &SMALL_PRIMES } else { &[] };
+    let mut residues = sieve
+        .iter()
+        .map(|r| small_mod(&start, *r))
+        .collect::<Vec<u64>>();
+    let step_residues = sieve
+        .iter()
+        .map(|r| small_mod(step, *r))
+        .collect::<Vec<u64>>();
+    let mut candidate = start;
+    while candidate.bit_length() <= bit_size {
+        if residues.iter().all(|x| !excluded.contains(x)) && is_prime(&candidate) {
+            return Some(candidate);
+        }
+        candidate += step;
+        for ((x, r), s) in residues.iter_mut().zip(sieve).zip(step_residues.iter()) {
+            *x = (*x + s) % r;
+        }
+    }
+    None
+}
+
+// SHA-256(seed || counter || i) for i = 0, 1, ... truncated to bit_size bits, with the top and
+// the lowest bit set
+fn seeded_candidate(seed: &[u8], bit_size: usize, counter: u32) -> BigInt {
+    let bytes = bit_size.div_ceil(8);
+    let mut buf = Vec::with_capacity(bytes + 32);
+    let mut block = 0u32;
+    while buf.len() < bytes {
+        let mut hasher = Sha256::new();
+        hasher.input(seed);
+        hasher.input(counter.to_be_bytes());
+        hasher.input(block.to_be_bytes());
+        buf.extend_from_slice(&hasher.result());
+        block += 1;
+    }
+    buf.truncate(bytes);
+    let mut candidate = BigInt::from(&buf[..]) >> (bytes * 8 - bit_size);
+    candidate.setbit(bit_size - 1);
+    candidate.setbit(0);
+    candidate
+}
+
+// strong Fermat test of an odd n > 3 to base a
+fn miller_rabin(n: &BigInt, a: &BigInt) -> bool {
+    let one = BigInt::one();
+    let n_minus_one = n - 1u64;
+    let s = trailing_zeros(&n_minus_one);
+    let d = &n_minus_one >> s;
+    let mut x = BigInt::mod_pow(a, &d, n);
+    if x == one || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = BigInt::mod_mul(&x, &x, n);
+        if x == n_minus_one {
+            return true;
+        }
+        if x == one {
+            return false;
+        }
+    }
+    false
+}
+
+// x / 2 mod n for an odd n
+fn half(x: BigInt, n: &BigInt) -> BigInt {
+    if x.tstbit(0) {
+        (x + n) >> 1
+    } else {
+        x >> 1
+    }
+}
+
+// strong Lucas test of an odd n without small factors, with P = 1 and Q = (1 - D) / 4 where D
+// is the first of 5, -7, 9, -11, ... with (D/n) = -1
+fn strong_lucas(n: &BigInt) -> bool {
+    let root = n.sqrt();
+    if &root * &root == *n {
+        // there is no such D
+        return false;
+    }
+    let mut d_abs = 5u64;
+    let mut negative = false;
+    let d = loop {
+        let d = if negative {
+            -BigInt::from(d_abs)
+        } else {
+            BigInt::from(d_abs)
+        };
+        match jacobi(&d, n) {
+            -1 => break d,
+            0 if BigInt::from(d_abs) != *n => return false,
+            _ => {}
+        }
+        d_abs += 2;
+        negative = !negative;
+    };
+    let q = (BigInt::one() - &d) / 4u64;
+    let d = d.modulus(n);
+    let q = q.modulus(n);
+
+    // n + 1 = k 2^s with k odd
+    let n_plus_one = n + 1u64;
+    let s = trailing_zeros(&n_plus_one);
+    let k = &n_plus_one >> s;
+
+    // U_1 = 1 and V_1 = P = 1, then double and add over the bits of k
+    let mut u = BigInt::one();
+    let mut v = BigInt::one();
+    for i in (0..k.bit_length() - 1).rev() {
+        // U_2j = U_j V_j, V_2j = (V_j^2 + D U_j^2) / 2
+        let u_2j = BigInt::mod_mul(&u, &v, n);
+        let u_sq = BigInt::mod_mul(&u, &u, n);
+        let v_2j = half(
+            BigInt::mod_add(
+                &BigInt::mod_mul(&v, &v, n),
+                &BigInt::mod_mul(&d, &u_sq, n),
+                n,
+            ),
+            n,
+        );
+        u = u_2j;
+        v = v_2j;
+        if k.tstbit(i) {
+            // U_j+1 = (P U_j + V_j) / 2, V_j+1 = (D U_j + P V_j) / 2
+            let u_next = half(BigInt::mod_add(&u, &v, n), n);
+            let v_next = half(BigInt::mod_add(&BigInt::mod_mul(&d, &u, n), &v, n), n);
+            u = u_next;
+            v = v_next;
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    // V_2j = V_j^2 - 2 Q^j
+    let mut q_k = BigInt::mod_pow(&q, &k, n);
+    for _ in 1..s {
+        v = BigInt::mod_sub(
+            &BigInt::mod_mul(&v, &v, n),
+            &BigInt::mod_add(&q_k, &q_k, n),
+            n,
+        );
+        if v.is_zero() {
+            return true;
+        }
+        q_k = BigInt::mod_mul(&q_k, &q_k, n);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_numbers() {
+        let primes = (0..200u64)
+            .filter(|n| BigInt::from(*n).is_probable_prime(10))
+            .collect::<Vec<_>>();
+        let expected = (0..200u64)
+            .filter(|n| *n > 1 && (2..*n).all(|d| n % d != 0))
+            .collect::<Vec<_>>();
+        assert_eq!(primes, expected);
+        assert!(!BigInt::from(-7).is_probable_prime(10));
+    }
+
+    #[test]
+    fn test_pseudoprimes() {
+        // strong pseudoprimes to base 2, Carmichael numbers and strong Lucas pseudoprimes
+        for n in [2047u64, 3_215_031_751, 561, 41041, 825_265].iter() {
+            assert!(!BigInt::from(*n).is_probable_prime(10));
+        }
+        assert!(miller_rabin(&BigInt::from(2047), &BigInt::from(2)));
+        assert!(miller_rabin(
+            &BigInt::from(3_215_031_751u64),
+            &BigInt::from(2)
+        ));
+        for n in [5459u64, 5777, 10877, 16109, 18971].iter() {
+            assert!(strong_lucas(&BigInt::from(*n)));
+            assert!(!BigInt::from(*n).is_probable_prime(10));
+        }
+        // strong pseudoprime to all the bases up to 37
+        let n = BigInt::from_str_radix("3317044064679887385961981", 10).unwrap();
+        for base in [2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37].iter() {
+            assert!(miller_rabin(&n, &BigInt::from(*base)));
+        }
+        assert!(!strong_lucas(&n));
+        assert!(!n.is_probable_prime(0));
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let m127 = (BigInt::one() << 127) - 1u64;
+        let m521 = (BigInt::one() << 521) - 1u64;
+        let m61 = (BigInt::one() << 61) - 1u64;
+        assert!(m127.is_probable_prime(10));
+        assert!(m521.is_probable_prime(10));
+        assert!(!(&m127 * &m61).is_probable_prime(10));
+        assert!(!(&m127 * &m127).is_probable_prime(10));
+        assert!(!((BigInt::one() << 128) + 1u64).is_probable_prime(10));
+    }
+
+    #[test]
+    fn test_sample_primes() {
+        for bit_size in [2usize, 16, 33, 256].iter() {
+            let p = BigInt::sample_prime(*bit_size);
+            assert_eq!(p.bit_length(), *bit_size);
+            assert!(p.is_probable_prime(10));
+
+            let p = BigInt::sample_blum_prime(*bit_size);
+            assert_eq!(p.bit_length(), *bit_size);
+            assert!(p.is_probable_prime(10));
+            assert_eq!(small_mod(&p, 4), 3);
+        }
+    }
+
+    #[test]
+    fn test_sample_safe_prime() {
+        for bit_size in [3usize, 20, 128].iter() {
+            let p = BigInt::sample_safe_prime(*bit_size);
+            assert_eq!(p.bit_length(), *bit_size);
+            assert!(p.is_probable_prime(10));
+            assert!(((p - 1u64) >> 1).is_probable_prime(10));
+        }
+    }
+
+    #[test]
+    fn test_sample_strong_prime() {
+        let p = BigInt::sample_strong_prime(256);
+        assert_eq!(p.bit_length(), 256);
+        assert!(p.is_probable_prime(10));
+    }
+
+    #[test]
+    fn test_prime_from_seed() {
+        let seed = b"curv test parameters";
+        let (p, counter) = BigInt::prime_from_seed(seed, 256);
+        assert_eq!(p.bit_length(), 256);
+        assert!(p.is_probable_prime(10));
+        assert_eq!(BigInt::prime_from_seed(seed, 256), (p.clone(), counter));
+        assert!(p.verify_prime_from_seed(seed, 256, counter));
+        assert!(!p.verify_prime_from_seed(seed, 256, counter + 1));
+        assert!(!p.verify_prime_from_seed(b"other seed", 256, counter));
+        assert!(!p.verify_prime_from_seed(seed, 255, counter));
+        assert_ne!(BigInt::prime_from_seed(b"other seed", 256).0, p);
+    }
+}
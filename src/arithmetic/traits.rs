@@ -14,6 +14,8 @@
     @license GPL-3.0+ <https://github.com/KZen-networks/cryptography-utils/blob/master/LICENSE>
 */
 
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use std::marker::Sized;
 
 pub trait ZeroizeBN {
@@ -31,13 +33,32 @@ pub trait Modulo {
     fn mod_sub(a: &Self, b: &Self, modulus: &Self) -> Self;
     fn mod_add(a: &Self, b: &Self, modulus: &Self) -> Self;
     fn mod_inv(a: &Self, modulus: &Self) -> Self;
+
+    // constant-time versions for secret operands: their running time depends only on the
+    // widths of the operands. mod_pow_sec and mod_mul_sec require an odd modulus and
+    // mod_pow_sec a non-negative exponent.
+    fn mod_pow_sec(base: &Self, exponent: &Self, modulus: &Self) -> Self;
+    fn mod_mul_sec(a: &Self, b: &Self, modulus: &Self) -> Self;
+    fn mod_inv_sec(a: &Self, modulus: &Self) -> Self;
 }
 
-pub trait Samplable {
-    fn sample_below(upper: &Self) -> Self;
+pub trait Samplable
+where
+    Self: Sized,
+{
+    // the randomness is taken from rng, e.g. for deterministic tests or an external entropy
+    // source, the functions without rng use the OS RNG
+    fn sample_below_with_rng<R: CryptoRng + RngCore>(upper: &Self, rng: &mut R) -> Self;
+    fn sample_with_rng<R: CryptoRng + RngCore>(bit_size: usize, rng: &mut R) -> Self;
+
+    fn sample_below(upper: &Self) -> Self {
+        Self::sample_below_with_rng(upper, &mut OsRng::new().unwrap())
+    }
     fn sample_range(lower: &Self, upper: &Self) -> Self;
     fn strict_sample_range(lower: &Self, upper: &Self) -> Self;
-    fn sample(bitsize: usize) -> Self;
+    fn sample(bit_size: usize) -> Self {
+        Self::sample_with_rng(bit_size, &mut OsRng::new().unwrap())
+    }
     fn strict_sample(bit_size: usize) -> Self;
 }
 
@@ -59,6 +80,25 @@ pub trait BitManipulation {
     fn test_bit(self: &Self, bit: usize) -> bool;
 }
 
+pub trait Primes
+where
+    Self: Sized,
+{
+    // Baillie-PSW (Miller-Rabin to base 2 and a strong Lucas test) followed by `rounds` more
+    // Miller-Rabin tests to random bases
+    fn is_probable_prime(&self, rounds: u32) -> bool;
+    fn sample_prime(bit_size: usize) -> Self;
+    // p = 2q + 1 with q prime
+    fn sample_safe_prime(bit_size: usize) -> Self;
+    // p = 3 mod 4
+    fn sample_blum_prime(bit_size: usize) -> Self;
+    // p - 1 and p + 1 have a large prime factor r and s, and r - 1 has a large prime factor t
+    fn sample_strong_prime(bit_size: usize) -> Self;
+    // deterministic generation from a seed, returns the prime and the counter of the candidate
+    fn prime_from_seed(seed: &[u8], bit_size: usize) -> (Self, u32);
+    fn verify_prime_from_seed(&self, seed: &[u8], bit_size: usize, counter: u32) -> bool;
+}
+
 pub trait ConvertFrom<T> {
     fn _from(_: &T) -> Self;
 }
@@ -15,6 +15,7 @@ use crate::BigInt;
 use super::traits::Commitment;
 use super::SECURITY_BITS;
 use crate::arithmetic::traits::Samplable;
+use rand::{CryptoRng, RngCore};
 use sha3::{Digest, Sha3_256};
 //TODO:  using the function with BigInt's as input instead of string's makes it impossible to commit to empty message or use empty randomness
 impl Commitment<BigInt> for HashCommitment {
@@ -30,8 +31,11 @@ impl Commitment<BigInt> for HashCommitment {
         BigInt::from(digest.result().as_ref())
     }
 
-    fn create_commitment(message: &BigInt) -> (BigInt, BigInt) {
-        let blinding_factor = BigInt::sample(SECURITY_BITS);
+    fn create_commitment_with_rng<R: CryptoRng + RngCore>(
+        message: &BigInt,
+        rng: &mut R,
+    ) -> (BigInt, BigInt) {
+        let blinding_factor = BigInt::sample_with_rng(SECURITY_BITS, rng);
         let com = HashCommitment::create_commitment_with_user_defined_randomness(
             message,
             &blinding_factor,
@@ -107,4 +111,22 @@ mod tests {
         let hash_result = BigInt::from(digest.result().as_ref());
         assert_eq!(&commitment, &hash_result);
     }
+
+    #[test]
+    fn test_create_commitment_with_rng() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let message = BigInt::sample(SECURITY_BITS);
+        let (commitment, blind_factor) =
+            HashCommitment::create_commitment_with_rng(&message, &mut StdRng::from_seed([5; 32]));
+        assert_eq!(
+            HashCommitment::create_commitment_with_rng(&message, &mut StdRng::from_seed([5; 32])),
+            (commitment.clone(), blind_factor.clone())
+        );
+        assert_eq!(
+            HashCommitment::create_commitment_with_user_defined_randomness(&message, &blind_factor),
+            commitment
+        );
+    }
 }
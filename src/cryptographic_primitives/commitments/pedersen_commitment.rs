@@ -10,6 +10,7 @@ use std::marker::PhantomData;
 use super::traits::Commitment;
 use super::SECURITY_BITS;
 use crate::arithmetic::traits::Samplable;
+use rand::{CryptoRng, RngCore};
 
 use crate::elliptic::curves::traits::*;
 use crate::BigInt;
@@ -28,17 +29,18 @@ where
         message: &BigInt,
         blinding_factor: &BigInt,
     ) -> P {
-        let g: P = ECPoint::generator();
-        let h = P::base_point2();
         let message_scalar: P::Scalar = ECScalar::from(message);
         let blinding_scalar: P::Scalar = ECScalar::from(blinding_factor);
-        let mg = g * message_scalar;
-        let rh = h * blinding_scalar;
+        let mg = P::mul_base(&message_scalar);
+        let rh = P::mul_base_point2(&blinding_scalar);
         mg + rh
     }
 
-    fn create_commitment(message: &BigInt) -> (P, BigInt) {
-        let blinding_factor = BigInt::sample(SECURITY_BITS);
+    fn create_commitment_with_rng<R: CryptoRng + RngCore>(
+        message: &BigInt,
+        rng: &mut R,
+    ) -> (P, BigInt) {
+        let blinding_factor = BigInt::sample_with_rng(SECURITY_BITS, rng);
         let com = PedersenCommitment::create_commitment_with_user_defined_randomness(
             message,
             &blinding_factor,
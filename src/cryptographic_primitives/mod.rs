@@ -7,6 +7,8 @@
 
 pub mod commitments;
 pub mod hashing;
+pub mod multiparty;
 pub mod proofs;
 pub mod secret_sharing;
+pub mod signatures;
 pub mod twoparty;
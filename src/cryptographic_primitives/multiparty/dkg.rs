@@ -0,0 +1,982 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use std::error::Error;
+use std::fmt::{self, Debug};
+
+use crate::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::{
+    ShamirSecretSharing, VerifiableSS,
+};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// Distributed key generation for t-of-n threshold keys, in two variants:
+///
+/// Pedersen: every party deals a VerifiableSS of a random secret together with a DLogProof
+/// of its constant term. T. P. Pedersen. A threshold cryptosystem without a trusted party.
+/// In EUROCRYPT 1991, Springer (LNCS 547), pages 522–526, 1991.
+///
+/// GJKR: the dealings are first committed with Pedersen commitments G * a_k + H * b_k
+/// (H = base_point2) and the Feldman commitments G * a_k are only revealed once the set of
+/// qualified dealers is fixed, so a rushing adversary cannot bias the public key.
+/// R. Gennaro, S. Jarecki, H. Krawczyk and T. Rabin. Secure Distributed Key Generation for
+/// Discrete-Log Based Cryptosystems. Journal of Cryptology 20(1), pages 51–83, 2007.
+///
+/// protocol (synchronous rounds, every message of a round is delivered before proceed):
+/// 1) every party broadcasts the commitments to its polynomial and sends a share to every party
+/// 2) every party broadcasts complaints against the dealers whose share is missing or invalid
+/// 3) every dealer answers the complaints against it by broadcasting the disputed shares;
+///    dealers with more than t complaints, an invalid dealing or a wrong answer are
+///    disqualified. Pedersen ends here.
+/// 4) GJKR: every qualified dealer broadcasts its Feldman commitments and a DLogProof
+/// 5) GJKR: every party broadcasts the shares (with their blinding) which do not match the
+///    Feldman commitments of their dealer
+/// 6) GJKR: every party broadcasts its shares of the dealers exposed in 4) or 5), whose
+///    polynomials are reconstructed in the clear
+///
+/// Parties are indexed from 0 like in VerifiableSS::reconstruct, party i ends with the share
+/// f(i + 1) of the sum f of the polynomials of the qualified dealers.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DkgVariant {
+    Pedersen,
+    Gjkr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DkgError {
+    InvalidParameters,
+    UnexpectedMessage,
+    NotEnoughQualifiedParties,
+    NotEnoughShares,
+    Finished,
+}
+
+impl fmt::Display for DkgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DkgError::{:?}", self)
+    }
+}
+
+impl Error for DkgError {
+    fn description(&self) -> &str {
+        "Error during distributed key generation"
+    }
+}
+
+/// broadcast when receiver is None
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct Msg<P: ECPoint> {
+    pub sender: usize,
+    pub receiver: Option<usize>,
+    pub body: MsgBody<P>,
+}
+
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub enum MsgBody<P: ECPoint> {
+    /// round 1 of Pedersen, broadcast
+    Commitments(FeldmanCommitments<P>),
+    /// round 1 of GJKR, broadcast
+    PedersenCommitments(Vec<P>),
+    /// round 1, p2p
+    Share(DealtShare<P>),
+    /// round 2, broadcast: the dealers the sender complains against
+    Complaints(Vec<usize>),
+    /// round 3, broadcast: the shares of the parties which complained against the sender
+    Justification(Vec<RevealedShare<P>>),
+    /// round 4 of GJKR, broadcast
+    Extraction(FeldmanCommitments<P>),
+    /// round 5 of GJKR, broadcast: the shares of the sender which don't match the extraction
+    ExtractionComplaints(Vec<RevealedShare<P>>),
+    /// round 6 of GJKR, broadcast: the shares of the sender from the exposed dealers
+    Reconstruction(Vec<RevealedShare<P>>),
+}
+
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct FeldmanCommitments<P: ECPoint> {
+    pub vss_scheme: VerifiableSS<P>,
+    pub proof: DLogProof<P>,
+}
+
+/// blinding is only used by GJKR
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DealtShare<P: ECPoint> {
+    pub share: P::Scalar,
+    pub blinding: Option<P::Scalar>,
+}
+
+/// the share f_dealer(receiver + 1) made public
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct RevealedShare<P: ECPoint> {
+    pub dealer: usize,
+    pub receiver: usize,
+    pub share: DealtShare<P>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LocalKey<P: ECPoint> {
+    pub index: usize,
+    pub vss_scheme: VerifiableSS<P>,
+    pub secret_share: P::Scalar,
+    pub qualified: Vec<usize>,
+}
+
+pub struct Keygen<P: ECPoint> {
+    index: usize,
+    parameters: ShamirSecretSharing,
+    variant: DkgVariant,
+    round: usize,
+    inbox: Vec<Msg<P>>,
+    coefficients: Vec<P::Scalar>,
+    dealt_shares: Vec<DealtShare<P>>,
+    // commitments of the dealers in round 1 (Feldman or Pedersen)
+    commitments: Vec<Option<VerifiableSS<P>>>,
+    // Feldman commitments of the dealers
+    feldman: Vec<Option<VerifiableSS<P>>>,
+    shares: Vec<Option<DealtShare<P>>>,
+    // (dealer, complaining party)
+    complaints: Vec<(usize, usize)>,
+    disqualified: Vec<bool>,
+    exposed: Vec<bool>,
+    output: Option<LocalKey<P>>,
+}
+
+impl<P> MsgBody<P>
+where
+    P: ECPoint,
+{
+    pub fn round(&self) -> usize {
+        match self {
+            MsgBody::Commitments(_) | MsgBody::PedersenCommitments(_) | MsgBody::Share(_) => 1,
+            MsgBody::Complaints(_) => 2,
+            MsgBody::Justification(_) => 3,
+            MsgBody::Extraction(_) => 4,
+            MsgBody::ExtractionComplaints(_) => 5,
+            MsgBody::Reconstruction(_) => 6,
+        }
+    }
+}
+
+impl<P> LocalKey<P>
+where
+    P: ECPoint + Clone,
+{
+    pub fn public_key(&self) -> P {
+        self.vss_scheme.commitments[0].clone()
+    }
+}
+
+impl<P> Keygen<P>
+where
+    P: ECPoint + Clone + PartialEq + Debug,
+    P::Scalar: Clone + PartialEq + Debug + Zeroize,
+{
+    pub fn new(
+        index: usize,
+        t: usize,
+        n: usize,
+        variant: DkgVariant,
+    ) -> Result<Keygen<P>, DkgError> {
+        if t >= n || index >= n {
+            return Err(DkgError::InvalidParameters);
+        }
+        Ok(Keygen {
+            index,
+            parameters: ShamirSecretSharing {
+                threshold: t,
+                share_count: n,
+            },
+            variant,
+            round: 0,
+            inbox: Vec::new(),
+            coefficients: Vec::new(),
+            dealt_shares: Vec::new(),
+            commitments: vec![None; n],
+            feldman: vec![None; n],
+            shares: vec![None; n],
+            complaints: Vec::new(),
+            disqualified: vec![false; n],
+            exposed: vec![false; n],
+            output: None,
+        })
+    }
+
+    pub fn current_round(&self) -> usize {
+        self.round
+    }
+
+    pub fn last_round(&self) -> usize {
+        match self.variant {
+            DkgVariant::Pedersen => 3,
+            DkgVariant::Gjkr => 6,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.output.is_some()
+    }
+
+    pub fn output(&self) -> Option<&LocalKey<P>> {
+        self.output.as_ref()
+    }
+
+    /// the dealers disqualified so far
+    pub fn disqualified(&self) -> Vec<usize> {
+        (0..self.parameters.share_count)
+            .filter(|i| self.disqualified[*i])
+            .collect()
+    }
+
+    /// stores a message of the current or of a later round
+    pub fn handle_incoming(&mut self, msg: Msg<P>) -> Result<(), DkgError> {
+        let round = msg.body.round();
+        let p2p = matches!(msg.body, MsgBody::Share(_));
+        let expected_receiver = if p2p { Some(self.index) } else { None };
+        let variant_ok = match (&msg.body, self.variant) {
+            (MsgBody::Commitments(_), DkgVariant::Gjkr) => false,
+            (MsgBody::PedersenCommitments(_), DkgVariant::Pedersen) => false,
+            (MsgBody::Share(share), DkgVariant::Pedersen) => share.blinding.is_none(),
+            (MsgBody::Share(share), DkgVariant::Gjkr) => share.blinding.is_some(),
+            _ => true,
+        };
+        let duplicate = self.inbox.iter().any(|m| {
+            m.sender == msg.sender
+                && std::mem::discriminant(&m.body) == std::mem::discriminant(&msg.body)
+        });
+        if msg.sender >= self.parameters.share_count
+            || msg.sender == self.index
+            || msg.receiver != expected_receiver
+            || !variant_ok
+            || duplicate
+            || round < self.round
+            || round > self.last_round()
+        {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.inbox.push(msg);
+        Ok(())
+    }
+
+    /// ends the current round with the messages received so far and returns the messages of
+    /// the next one. Missing messages are treated as misbehaviour of their sender.
+    pub fn proceed(&mut self) -> Result<Vec<Msg<P>>, DkgError> {
+        if self.is_finished() {
+            return Err(DkgError::Finished);
+        }
+        let msgs = match self.round {
+            0 => self.deal(),
+            1 => self.complain(),
+            2 => self.justify(),
+            3 => self.qualify()?,
+            4 => self.check_extractions(),
+            5 => self.expose(),
+            _ => self.reconstruct()?,
+        };
+        self.round += 1;
+        Ok(msgs)
+    }
+
+    fn deal(&mut self) -> Vec<Msg<P>> {
+        let t = self.parameters.threshold;
+        let n = self.parameters.share_count;
+        let secret: P::Scalar = ECScalar::new_random();
+        self.coefficients = VerifiableSS::<P>::sample_polynomial(t, &secret);
+        let index_vec: Vec<usize> = (1..=n).collect();
+        let shares = VerifiableSS::<P>::evaluate_polynomial(&self.coefficients, &index_vec);
+        let feldman = self.feldman_commitments();
+        let (commitments, body, blindings) = match self.variant {
+            DkgVariant::Pedersen => {
+                let body = MsgBody::Commitments(FeldmanCommitments {
+                    vss_scheme: feldman.clone(),
+                    proof: DLogProof::prove(&secret),
+                });
+                (feldman.clone(), body, vec![None; n])
+            }
+            DkgVariant::Gjkr => {
+                let blinding_secret: P::Scalar = ECScalar::new_random();
+                let blinding_coefficients =
+                    VerifiableSS::<P>::sample_polynomial(t, &blinding_secret);
+                let pedersen = VerifiableSS {
+                    parameters: self.parameters.clone(),
+                    commitments: (0..=t)
+                        .map(|k| {
+                            P::mul_base(&self.coefficients[k])
+                                + P::mul_base_point2(&blinding_coefficients[k])
+                        })
+                        .collect(),
+                };
+                let blindings =
+                    VerifiableSS::<P>::evaluate_polynomial(&blinding_coefficients, &index_vec);
+                let body = MsgBody::PedersenCommitments(pedersen.commitments.clone());
+                (pedersen, body, blindings.into_iter().map(Some).collect())
+            }
+        };
+        self.dealt_shares = shares
+            .into_iter()
+            .zip(blindings)
+            .map(|(share, blinding)| DealtShare { share, blinding })
+            .collect();
+        self.commitments[self.index] = Some(commitments);
+        self.shares[self.index] = Some(self.dealt_shares[self.index].clone());
+        if self.variant == DkgVariant::Pedersen {
+            self.feldman[self.index] = Some(feldman);
+        }
+
+        let mut msgs = vec![self.broadcast(body)];
+        msgs.extend((0..n).filter(|j| *j != self.index).map(|j| Msg {
+            sender: self.index,
+            receiver: Some(j),
+            body: MsgBody::Share(self.dealt_shares[j].clone()),
+        }));
+        msgs
+    }
+
+    fn complain(&mut self) -> Vec<Msg<P>> {
+        let t = self.parameters.threshold;
+        for msg in self.take_round(1) {
+            match msg.body {
+                MsgBody::Commitments(feldman) => {
+                    if self.check_feldman_commitments(&feldman) {
+                        self.commitments[msg.sender] = Some(feldman.vss_scheme.clone());
+                        self.feldman[msg.sender] = Some(feldman.vss_scheme);
+                    }
+                }
+                MsgBody::PedersenCommitments(commitments) => {
+                    if commitments.len() == t + 1 {
+                        self.commitments[msg.sender] = Some(VerifiableSS {
+                            parameters: self.parameters.clone(),
+                            commitments,
+                        });
+                    }
+                }
+                MsgBody::Share(share) => self.shares[msg.sender] = Some(share),
+                _ => unreachable!(),
+            }
+        }
+        // an invalid or missing broadcast is seen by everybody
+        for i in 0..self.parameters.share_count {
+            if self.commitments[i].is_none() {
+                self.disqualified[i] = true;
+            }
+        }
+        let complaints = (0..self.parameters.share_count)
+            .filter(|i| {
+                !self.disqualified[*i]
+                    && !self.shares[*i]
+                        .as_ref()
+                        .map(|share| self.check_share(*i, self.index, share))
+                        .unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+        self.complaints = complaints.iter().map(|i| (*i, self.index)).collect();
+        vec![self.broadcast(MsgBody::Complaints(complaints))]
+    }
+
+    fn justify(&mut self) -> Vec<Msg<P>> {
+        let n = self.parameters.share_count;
+        let mut complaints = std::mem::take(&mut self.complaints);
+        for msg in self.take_round(2) {
+            if let MsgBody::Complaints(dealers) = msg.body {
+                for dealer in dealers {
+                    if dealer < n
+                        && dealer != msg.sender
+                        && !complaints.contains(&(dealer, msg.sender))
+                    {
+                        complaints.push((dealer, msg.sender));
+                    }
+                }
+            }
+        }
+        let revealed = complaints
+            .iter()
+            .filter(|(dealer, _)| *dealer == self.index)
+            .map(|(_, receiver)| RevealedShare {
+                dealer: self.index,
+                receiver: *receiver,
+                share: self.dealt_shares[*receiver].clone(),
+            })
+            .collect();
+        self.complaints = complaints;
+        vec![self.broadcast(MsgBody::Justification(revealed))]
+    }
+
+    fn qualify(&mut self) -> Result<Vec<Msg<P>>, DkgError> {
+        let n = self.parameters.share_count;
+        let t = self.parameters.threshold;
+        let justifications = self
+            .take_round(3)
+            .into_iter()
+            .filter_map(|msg| match msg.body {
+                MsgBody::Justification(revealed) => Some((msg.sender, revealed)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for dealer in 0..n {
+            let complainers = self
+                .complaints
+                .iter()
+                .filter(|(d, _)| *d == dealer)
+                .map(|(_, receiver)| *receiver)
+                .collect::<Vec<_>>();
+            if complainers.is_empty() || self.disqualified[dealer] {
+                continue;
+            }
+            if complainers.len() > t {
+                self.disqualified[dealer] = true;
+                continue;
+            }
+            for receiver in complainers {
+                if dealer == self.index {
+                    continue;
+                }
+                let answer = justifications
+                    .iter()
+                    .filter(|(sender, _)| *sender == dealer)
+                    .flat_map(|(_, revealed)| revealed.iter())
+                    .find(|r| r.dealer == dealer && r.receiver == receiver)
+                    .map(|r| r.share.clone());
+                match answer {
+                    Some(ref share) if self.check_share(dealer, receiver, share) => {
+                        if receiver == self.index {
+                            self.shares[dealer] = answer;
+                        }
+                    }
+                    _ => self.disqualified[dealer] = true,
+                }
+            }
+        }
+        if self.qualified().len() <= t {
+            return Err(DkgError::NotEnoughQualifiedParties);
+        }
+        match self.variant {
+            DkgVariant::Pedersen => {
+                self.finish();
+                Ok(Vec::new())
+            }
+            DkgVariant::Gjkr => {
+                let feldman = self.feldman_commitments();
+                self.feldman[self.index] = Some(feldman.clone());
+                Ok(vec![self.broadcast(MsgBody::Extraction(
+                    FeldmanCommitments {
+                        vss_scheme: feldman,
+                        proof: DLogProof::prove(&self.coefficients[0]),
+                    },
+                ))])
+            }
+        }
+    }
+
+    fn check_extractions(&mut self) -> Vec<Msg<P>> {
+        for msg in self.take_round(4) {
+            if let MsgBody::Extraction(feldman) = msg.body {
+                if !self.disqualified[msg.sender] && self.check_feldman_commitments(&feldman) {
+                    self.feldman[msg.sender] = Some(feldman.vss_scheme);
+                }
+            }
+        }
+        let mut revealed = Vec::new();
+        for dealer in self.qualified() {
+            match self.feldman[dealer] {
+                // an invalid or missing extraction is seen by everybody
+                None => self.exposed[dealer] = true,
+                Some(ref vss_scheme) => {
+                    let share = self.shares[dealer].clone().unwrap();
+                    if vss_scheme
+                        .validate_share(&share.share, self.index + 1)
+                        .is_err()
+                    {
+                        self.exposed[dealer] = true;
+                        revealed.push(RevealedShare {
+                            dealer,
+                            receiver: self.index,
+                            share,
+                        });
+                    }
+                }
+            }
+        }
+        vec![self.broadcast(MsgBody::ExtractionComplaints(revealed))]
+    }
+
+    fn expose(&mut self) -> Vec<Msg<P>> {
+        for msg in self.take_round(5) {
+            if let MsgBody::ExtractionComplaints(revealed) = msg.body {
+                for r in revealed {
+                    // the complaint must be about a share which is valid for the Pedersen
+                    // commitments but not for the Feldman ones
+                    let valid = r.receiver == msg.sender
+                        && r.dealer < self.parameters.share_count
+                        && !self.disqualified[r.dealer]
+                        && self.check_share(r.dealer, r.receiver, &r.share)
+                        && self.feldman[r.dealer]
+                            .as_ref()
+                            .map(|vss| vss.validate_share(&r.share.share, r.receiver + 1).is_err())
+                            .unwrap_or(true);
+                    if valid {
+                        self.exposed[r.dealer] = true;
+                    }
+                }
+            }
+        }
+        let revealed = self
+            .qualified()
+            .into_iter()
+            .filter(|dealer| self.exposed[*dealer])
+            .map(|dealer| RevealedShare {
+                dealer,
+                receiver: self.index,
+                share: self.shares[dealer].clone().unwrap(),
+            })
+            .collect();
+        vec![self.broadcast(MsgBody::Reconstruction(revealed))]
+    }
+
+    fn reconstruct(&mut self) -> Result<Vec<Msg<P>>, DkgError> {
+        let t = self.parameters.threshold;
+        let mut revealed = self
+            .take_round(6)
+            .into_iter()
+            .filter_map(|msg| {
+                let sender = msg.sender;
+                match msg.body {
+                    MsgBody::Reconstruction(revealed) => Some(
+                        revealed
+                            .into_iter()
+                            .filter(|r| r.receiver == sender)
+                            .collect::<Vec<_>>(),
+                    ),
+                    _ => None,
+                }
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        for dealer in self.qualified() {
+            if !self.exposed[dealer] {
+                continue;
+            }
+            revealed.push(RevealedShare {
+                dealer,
+                receiver: self.index,
+                share: self.shares[dealer].clone().unwrap(),
+            });
+            let mut receivers = Vec::new();
+            let mut values = Vec::new();
+            for r in revealed.iter().filter(|r| r.dealer == dealer) {
+                if receivers.len() <= t
+                    && !receivers.contains(&r.receiver)
+                    && self.check_share(dealer, r.receiver, &r.share)
+                {
+                    receivers.push(r.receiver);
+                    values.push(r.share.share.clone());
+                }
+            }
+            if receivers.len() <= t {
+                return Err(DkgError::NotEnoughShares);
+            }
+            let points = receivers
+                .iter()
+                .map(|i| ECScalar::from(&BigInt::from(*i as u32 + 1)))
+                .collect::<Vec<P::Scalar>>();
+            let coefficients = interpolate_polynomial::<P>(&points, &values);
+            self.feldman[dealer] = Some(VerifiableSS {
+                parameters: self.parameters.clone(),
+                commitments: coefficients.iter().map(P::mul_base).collect(),
+            });
+        }
+        self.finish();
+        Ok(Vec::new())
+    }
+
+    fn finish(&mut self) {
+        let qualified = self.qualified();
+        let secret_share = qualified
+            .iter()
+            .map(|i| self.shares[*i].as_ref().unwrap().share.clone())
+            .fold(ECScalar::zero(), |acc: P::Scalar, x| acc + x);
+        let commitments = (0..=self.parameters.threshold)
+            .map(|k| {
+                qualified
+                    .iter()
+                    .map(|i| self.feldman[*i].as_ref().unwrap().commitments[k].clone())
+                    .fold(P::zero(), |acc, x| acc + x)
+            })
+            .collect();
+        for coefficient in self.coefficients.iter_mut() {
+            coefficient.zeroize();
+        }
+        self.output = Some(LocalKey {
+            index: self.index,
+            vss_scheme: VerifiableSS {
+                parameters: self.parameters.clone(),
+                commitments,
+            },
+            secret_share,
+            qualified,
+        });
+    }
+
+    fn qualified(&self) -> Vec<usize> {
+        (0..self.parameters.share_count)
+            .filter(|i| !self.disqualified[*i])
+            .collect()
+    }
+
+    fn feldman_commitments(&self) -> VerifiableSS<P> {
+        VerifiableSS {
+            parameters: self.parameters.clone(),
+            commitments: self.coefficients.iter().map(P::mul_base).collect(),
+        }
+    }
+
+    fn check_feldman_commitments(&self, feldman: &FeldmanCommitments<P>) -> bool {
+        feldman.vss_scheme.parameters == self.parameters
+            && feldman.vss_scheme.commitments.len() == self.parameters.threshold + 1
+            && feldman.proof.pk == feldman.vss_scheme.commitments[0]
+            && DLogProof::verify(&feldman.proof).is_ok()
+    }
+
+    // checks f_dealer(receiver + 1) against the round 1 commitments of the dealer
+    fn check_share(&self, dealer: usize, receiver: usize, share: &DealtShare<P>) -> bool {
+        let vss_scheme = match self.commitments[dealer] {
+            Some(ref vss_scheme) => vss_scheme,
+            None => return false,
+        };
+        match (self.variant, &share.blinding) {
+            (DkgVariant::Pedersen, None) => vss_scheme
+                .validate_share(&share.share, receiver + 1)
+                .is_ok(),
+            (DkgVariant::Gjkr, Some(blinding)) => vss_scheme
+                .validate_share_public(
+                    &(P::mul_base(&share.share) + P::mul_base_point2(blinding)),
+                    receiver + 1,
+                )
+                .is_ok(),
+            _ => false,
+        }
+    }
+
+    fn take_round(&mut self, round: usize) -> Vec<Msg<P>> {
+        let (msgs, inbox) = self
+            .inbox
+            .drain(..)
+            .partition(|msg| msg.body.round() == round);
+        self.inbox = inbox;
+        msgs
+    }
+
+    fn broadcast(&self, body: MsgBody<P>) -> Msg<P> {
+        Msg {
+            sender: self.index,
+            receiver: None,
+            body,
+        }
+    }
+}
+
+// the coefficients of the polynomial of degree points.len() - 1 through (points[j], values[j])
+fn interpolate_polynomial<P>(points: &[P::Scalar], values: &[P::Scalar]) -> Vec<P::Scalar>
+where
+    P: ECPoint,
+    P::Scalar: Clone,
+{
+    let one: P::Scalar = ECScalar::from(&BigInt::one());
+    let mut coefficients: Vec<P::Scalar> = points.iter().map(|_| ECScalar::zero()).collect();
+    for (j, xj) in points.iter().enumerate() {
+        // basis = prod_{m != j} (x - x_m) / (x_j - x_m)
+        let mut basis = vec![one.clone()];
+        let mut denominator = one.clone();
+        for (m, xm) in points.iter().enumerate() {
+            if m == j {
+                continue;
+            }
+            let mut next: Vec<P::Scalar> = vec![ECScalar::zero(); basis.len() + 1];
+            for (k, b) in basis.iter().enumerate() {
+                next[k + 1] = next[k + 1].add(&b.get_element());
+                next[k] = next[k].sub(&b.mul(&xm.get_element()).get_element());
+            }
+            basis = next;
+            denominator = denominator * xj.sub(&xm.get_element());
+        }
+        let scale = values[j].clone() * denominator.invert();
+        for (c, b) in coefficients.iter_mut().zip(basis.iter()) {
+            *c = c.add(&b.mul(&scale.get_element()).get_element());
+        }
+    }
+    coefficients
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    // in-memory router: runs the rounds of all the parties, delivering the messages which the
+    // adversary lets through (it may also modify them)
+    fn run_keygen<P, F>(
+        t: usize,
+        n: usize,
+        variant: DkgVariant,
+        mut adversary: F,
+    ) -> Vec<Result<LocalKey<P>, DkgError>>
+    where
+        P: ECPoint + Clone + PartialEq + Debug,
+        P::Scalar: Clone + PartialEq + Debug + Zeroize,
+        F: FnMut(&mut Msg<P>) -> bool,
+    {
+        let mut parties = (0..n)
+            .map(|i| Keygen::<P>::new(i, t, n, variant).unwrap())
+            .collect::<Vec<_>>();
+        let mut results: Vec<Option<Result<LocalKey<P>, DkgError>>> = vec![None; n];
+        while results.iter().any(|r| r.is_none()) {
+            let mut outgoing = Vec::new();
+            for (i, party) in parties.iter_mut().enumerate() {
+                if results[i].is_some() {
+                    continue;
+                }
+                match party.proceed() {
+                    Ok(msgs) => outgoing.extend(msgs),
+                    Err(e) => results[i] = Some(Err(e)),
+                }
+                if let Some(key) = party.output() {
+                    results[i] = Some(Ok(key.clone()));
+                }
+            }
+            for mut msg in outgoing {
+                if !adversary(&mut msg) {
+                    continue;
+                }
+                for (j, party) in parties.iter_mut().enumerate() {
+                    if j != msg.sender && msg.receiver.map(|r| r == j).unwrap_or(true) {
+                        party.handle_incoming(msg.clone()).unwrap();
+                    }
+                }
+            }
+        }
+        results.into_iter().map(Option::unwrap).collect()
+    }
+
+    fn check_keys<P>(keys: &[LocalKey<P>], qualified: &[usize])
+    where
+        P: ECPoint + Clone + PartialEq + Debug,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let t = keys[0].vss_scheme.parameters.threshold;
+        for key in keys {
+            assert_eq!(key.qualified, qualified);
+            assert_eq!(key.vss_scheme, keys[0].vss_scheme);
+            assert!(key
+                .vss_scheme
+                .validate_share(&key.secret_share, key.index + 1)
+                .is_ok());
+        }
+        let indices = keys.iter().take(t + 1).map(|k| k.index).collect::<Vec<_>>();
+        let shares = keys
+            .iter()
+            .take(t + 1)
+            .map(|k| k.secret_share.clone())
+            .collect::<Vec<_>>();
+        let secret = keys[0].vss_scheme.reconstruct(&indices, &shares);
+        assert_eq!(P::mul_base(&secret), keys[0].public_key());
+    }
+
+    fn keys_of<P: ECPoint>(
+        results: Vec<Result<LocalKey<P>, DkgError>>,
+        honest: &[usize],
+    ) -> Vec<LocalKey<P>> {
+        results
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| honest.contains(i))
+            .map(|(_, r)| r.unwrap())
+            .collect()
+    }
+
+    fn tamper_share<P: ECPoint>(msg: &mut Msg<P>, dealer: usize, receivers: &[usize])
+    where
+        P::Scalar: Clone,
+    {
+        if msg.sender != dealer
+            || !msg
+                .receiver
+                .map(|r| receivers.contains(&r))
+                .unwrap_or(false)
+        {
+            return;
+        }
+        if let MsgBody::Share(ref mut dealt) = msg.body {
+            let one: P::Scalar = ECScalar::from(&BigInt::one());
+            dealt.share = dealt.share.add(&one.get_element());
+        }
+    }
+
+    test_for_all_curves!(test_dkg_honest_parties);
+
+    fn test_dkg_honest_parties<P>()
+    where
+        P: ECPoint + Clone + PartialEq + Debug,
+        P::Scalar: Clone + PartialEq + Debug + Zeroize,
+    {
+        for variant in [DkgVariant::Pedersen, DkgVariant::Gjkr].iter() {
+            let results = run_keygen::<P, _>(2, 5, *variant, |_| true);
+            check_keys(&keys_of(results, &[0, 1, 2, 3, 4]), &[0, 1, 2, 3, 4]);
+        }
+    }
+
+    test_for_all_curves!(test_dkg_answered_complaint);
+
+    fn test_dkg_answered_complaint<P>()
+    where
+        P: ECPoint + Clone + PartialEq + Debug,
+        P::Scalar: Clone + PartialEq + Debug + Zeroize,
+    {
+        // the share of party 1 is corrupted in transit, party 0 reveals the right one
+        for variant in [DkgVariant::Pedersen, DkgVariant::Gjkr].iter() {
+            let results = run_keygen::<P, _>(2, 5, *variant, |msg| {
+                tamper_share(msg, 0, &[1]);
+                true
+            });
+            check_keys(&keys_of(results, &[0, 1, 2, 3, 4]), &[0, 1, 2, 3, 4]);
+        }
+    }
+
+    test_for_all_curves!(test_dkg_disqualify_dealers);
+
+    fn test_dkg_disqualify_dealers<P>()
+    where
+        P: ECPoint + Clone + PartialEq + Debug,
+        P::Scalar: Clone + PartialEq + Debug + Zeroize,
+    {
+        for variant in [DkgVariant::Pedersen, DkgVariant::Gjkr].iter() {
+            // party 0 deals a bad share and doesn't answer the complaint, party 1 deals bad
+            // shares to more than t parties
+            let results = run_keygen::<P, _>(2, 6, *variant, |msg| {
+                tamper_share(msg, 0, &[2]);
+                tamper_share(msg, 1, &[2, 3, 4]);
+                match msg.body {
+                    MsgBody::Justification(_) => msg.sender != 0,
+                    _ => true,
+                }
+            });
+            check_keys(&keys_of(results, &[2, 3, 4, 5]), &[2, 3, 4, 5]);
+        }
+
+        // a wrong proof of knowledge of the constant term disqualifies a Pedersen dealer
+        let results = run_keygen::<P, _>(1, 3, DkgVariant::Pedersen, |msg| {
+            if let (0, MsgBody::Commitments(ref mut feldman)) = (msg.sender, &mut msg.body) {
+                feldman.proof = DLogProof::prove(&ECScalar::new_random());
+            }
+            true
+        });
+        check_keys(&keys_of(results, &[1, 2]), &[1, 2]);
+    }
+
+    test_for_all_curves!(test_gjkr_reconstruct_exposed_dealers);
+
+    fn test_gjkr_reconstruct_exposed_dealers<P>()
+    where
+        P: ECPoint + Clone + PartialEq + Debug,
+        P::Scalar: Clone + PartialEq + Debug + Zeroize,
+    {
+        // once qualified, party 0 can neither withhold nor change its Feldman commitments:
+        // its polynomial is reconstructed and it still contributes to the key
+        let results = run_keygen::<P, _>(2, 5, DkgVariant::Gjkr, |msg| match msg.body {
+            MsgBody::Extraction(_) => msg.sender != 0,
+            _ => true,
+        });
+        check_keys(&keys_of(results, &[1, 2, 3, 4]), &[0, 1, 2, 3, 4]);
+
+        let results = run_keygen::<P, _>(2, 5, DkgVariant::Gjkr, |msg| {
+            if let (0, MsgBody::Extraction(ref mut feldman)) = (msg.sender, &mut msg.body) {
+                let secret: P::Scalar = ECScalar::new_random();
+                let (vss_scheme, _) = VerifiableSS::<P>::share(2, 5, &secret);
+                feldman.vss_scheme = vss_scheme;
+                feldman.proof = DLogProof::prove(&secret);
+            }
+            true
+        });
+        check_keys(&keys_of(results, &[1, 2, 3, 4]), &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_handle_incoming_rejects_unexpected_messages() {
+        use crate::elliptic::curves::secp256_k1::GE;
+        let mut dealer = Keygen::<GE>::new(0, 1, 3, DkgVariant::Gjkr).unwrap();
+        let mut party = Keygen::<GE>::new(1, 1, 3, DkgVariant::Gjkr).unwrap();
+        assert!(Keygen::<GE>::new(3, 1, 3, DkgVariant::Gjkr).is_err());
+        assert!(Keygen::<GE>::new(0, 3, 3, DkgVariant::Gjkr).is_err());
+        let msgs = dealer.proceed().unwrap();
+        let share_to_2 = msgs.iter().find(|m| m.receiver == Some(2)).unwrap().clone();
+        // p2p message for another party
+        assert_eq!(
+            party.handle_incoming(share_to_2),
+            Err(DkgError::UnexpectedMessage)
+        );
+        // a round 1 message of the other variant
+        let feldman = Msg {
+            sender: 0,
+            receiver: None,
+            body: MsgBody::Commitments(FeldmanCommitments {
+                vss_scheme: VerifiableSS::share(1, 3, &ECScalar::new_random()).0,
+                proof: DLogProof::prove(&ECScalar::new_random()),
+            }),
+        };
+        assert_eq!(
+            party.handle_incoming(feldman),
+            Err(DkgError::UnexpectedMessage)
+        );
+        party.handle_incoming(msgs[0].clone()).unwrap();
+        // duplicate
+        assert_eq!(
+            party.handle_incoming(msgs[0].clone()),
+            Err(DkgError::UnexpectedMessage)
+        );
+        party.proceed().unwrap();
+        party.proceed().unwrap();
+        // message of a past round
+        let share_to_1 = msgs.into_iter().find(|m| m.receiver == Some(1)).unwrap();
+        assert_eq!(
+            party.handle_incoming(share_to_1),
+            Err(DkgError::UnexpectedMessage)
+        );
+    }
+
+    #[test]
+    fn test_interpolate_polynomial() {
+        use crate::elliptic::curves::secp256_k1::{FE, GE};
+        let coefficients = VerifiableSS::<GE>::sample_polynomial(3, &ECScalar::new_random());
+        let values = VerifiableSS::<GE>::evaluate_polynomial(&coefficients, &[2, 3, 5, 7]);
+        let points = [2u32, 3, 5, 7]
+            .iter()
+            .map(|x| ECScalar::from(&BigInt::from(*x)))
+            .collect::<Vec<FE>>();
+        assert_eq!(interpolate_polynomial::<GE>(&points, &values), coefficients);
+    }
+}
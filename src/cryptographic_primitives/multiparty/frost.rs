@@ -0,0 +1,812 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use std::fmt::Debug;
+
+use crate::arithmetic::traits::Converter;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::cryptographic_primitives::signatures::SignatureError;
+use crate::elliptic::curves::curve_ristretto::{RistrettoCurvPoint, RistrettoScalar};
+use crate::elliptic::curves::ed25519::{Ed25519Point, Ed25519Scalar};
+use crate::elliptic::curves::hash_to_curve::hash_to_field;
+use crate::elliptic::curves::p256::{Secp256r1Point, Secp256r1Scalar};
+use crate::elliptic::curves::secp256_k1::{Secp256k1Point, Secp256k1Scalar};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use rand::{thread_rng, Rng};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256, Sha512};
+
+/// FROST t-of-n threshold Schnorr signatures as specified in RFC 9591:
+/// D. Connolly, C. Komlo, I. Goldberg and C. A. Wood. The Flexible Round-Optimized Schnorr
+/// Threshold (FROST) Protocol for Two-Round Schnorr Signatures. RFC 9591, 2024.
+///
+/// The secret key is shared with Feldman VSS (by a dealer or a DKG): party i (0-based like in
+/// VerifiableSS::reconstruct) holds f(i + 1) and has identifier i + 1, and the commitments give
+/// the group public key and the public key of every share.
+///
+/// protocol:
+/// 1) every signer runs commit and sends its SigningCommitments to the coordinator
+/// 2) the coordinator picks t + 1 or more signers and sends them the message with the list of
+///    their commitments
+/// 3) every signer runs sign and returns a SignatureShare
+/// 4) the coordinator verifies the shares and aggregates them into the signature
+///
+/// SigningNonces must never be used twice, sign consumes them.
+pub trait Ciphersuite: Clone + Copy + Debug + PartialEq {
+    type Scalar: ECScalar + Clone + PartialEq + Debug + Serialize + DeserializeOwned;
+    type Point: ECPoint<Scalar = Self::Scalar>
+        + Clone
+        + PartialEq
+        + Debug
+        + Serialize
+        + DeserializeOwned;
+
+    const CONTEXT_STRING: &'static str;
+
+    /// hashes contextString || tag || m to a scalar
+    fn hash_to_scalar(tag: &[u8], m: &[u8]) -> Self::Scalar;
+
+    /// hashes contextString || tag || m
+    fn hash(tag: &[u8], m: &[u8]) -> Vec<u8>;
+
+    fn serialize_element(point: &Self::Point) -> Vec<u8>;
+
+    fn serialize_scalar(scalar: &Self::Scalar) -> Vec<u8>;
+
+    fn deserialize_scalar(bytes: &[u8]) -> Self::Scalar;
+
+    /// binding factors
+    fn h1(m: &[u8]) -> Self::Scalar {
+        Self::hash_to_scalar(b"rho", m)
+    }
+
+    /// challenge
+    fn h2(m: &[u8]) -> Self::Scalar {
+        Self::hash_to_scalar(b"chal", m)
+    }
+
+    /// nonces
+    fn h3(m: &[u8]) -> Self::Scalar {
+        Self::hash_to_scalar(b"nonce", m)
+    }
+
+    /// message
+    fn h4(m: &[u8]) -> Vec<u8> {
+        Self::hash(b"msg", m)
+    }
+
+    /// commitment list
+    fn h5(m: &[u8]) -> Vec<u8> {
+        Self::hash(b"com", m)
+    }
+}
+
+/// FROST(Ed25519, SHA-512), the signatures are Ed25519 signatures (RFC 8032)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ed25519Sha512;
+
+/// FROST(ristretto255, SHA-512)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ristretto255Sha512;
+
+/// FROST(P-256, SHA-256)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct P256Sha256;
+
+/// FROST(secp256k1, SHA-256)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Secp256k1Sha256;
+
+pub struct KeyShare<C: Ciphersuite> {
+    pub index: usize,
+    pub vss_scheme: VerifiableSS<C::Point>,
+    secret_share: C::Scalar,
+}
+
+pub struct SigningNonces<C: Ciphersuite> {
+    hiding: C::Scalar,
+    binding: C::Scalar,
+    commitments: SigningCommitments<C>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SigningCommitments<C: Ciphersuite> {
+    pub index: usize,
+    pub hiding: C::Point,
+    pub binding: C::Point,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SignatureShare<C: Ciphersuite> {
+    pub index: usize,
+    pub z: C::Scalar,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FrostSignature<C: Ciphersuite> {
+    pub r: C::Point,
+    pub z: C::Scalar,
+}
+
+impl<C: Ciphersuite> KeyShare<C> {
+    /// checks the share against the commitments of the dealer
+    pub fn new(
+        index: usize,
+        secret_share: C::Scalar,
+        vss_scheme: VerifiableSS<C::Point>,
+    ) -> Result<KeyShare<C>, SignatureError> {
+        if index >= vss_scheme.parameters.share_count {
+            return Err(SignatureError);
+        }
+        vss_scheme
+            .validate_share(&secret_share, index + 1)
+            .map_err(|_| SignatureError)?;
+        Ok(KeyShare {
+            index,
+            vss_scheme,
+            secret_share,
+        })
+    }
+
+    pub fn group_public_key(&self) -> C::Point {
+        self.vss_scheme.commitments[0].clone()
+    }
+
+    /// round one: the nonces are kept for sign, the commitments are sent to the coordinator
+    pub fn commit(&self) -> (SigningNonces<C>, SigningCommitments<C>) {
+        let mut hiding_randomness = [0u8; 32];
+        let mut binding_randomness = [0u8; 32];
+        thread_rng().fill(&mut hiding_randomness[..]);
+        thread_rng().fill(&mut binding_randomness[..]);
+        self.commit_with_randomness(&hiding_randomness, &binding_randomness)
+    }
+
+    /// round two: fails if the commitments are not those of t + 1 or more distinct signers
+    /// sorted by index, including the commitments of our nonces.
+    pub fn sign(
+        &self,
+        nonces: SigningNonces<C>,
+        commitments: &[SigningCommitments<C>],
+        msg: &[u8],
+    ) -> Result<SignatureShare<C>, SignatureError> {
+        let indices = signers::<C>(commitments, &self.vss_scheme)?;
+        let position = indices
+            .iter()
+            .position(|i| *i == self.index)
+            .ok_or(SignatureError)?;
+        if commitments[position] != nonces.commitments {
+            return Err(SignatureError);
+        }
+        let group_public_key = self.group_public_key();
+        let binding_factors = binding_factors(&group_public_key, commitments, msg);
+        let r = group_commitment(commitments, &binding_factors);
+        // SerializeElement fails on the identity
+        if r.is_zero() {
+            return Err(SignatureError);
+        }
+        let lambda = self
+            .vss_scheme
+            .map_share_to_new_params(self.index, &indices);
+        let c = challenge::<C>(&r, &group_public_key, msg);
+        let z = nonces.hiding
+            + nonces.binding * binding_factors[position].clone()
+            + lambda * self.secret_share.clone() * c;
+        Ok(SignatureShare {
+            index: self.index,
+            z,
+        })
+    }
+
+    // nonce_generate of the RFC with the given random bytes
+    fn commit_with_randomness(
+        &self,
+        hiding_randomness: &[u8; 32],
+        binding_randomness: &[u8; 32],
+    ) -> (SigningNonces<C>, SigningCommitments<C>) {
+        let secret = C::serialize_scalar(&self.secret_share);
+        let hiding = C::h3(&[&hiding_randomness[..], &secret].concat());
+        let binding = C::h3(&[&binding_randomness[..], &secret].concat());
+        let commitments = SigningCommitments {
+            index: self.index,
+            hiding: C::Point::mul_base(&hiding),
+            binding: C::Point::mul_base(&binding),
+        };
+        let nonces = SigningNonces {
+            hiding,
+            binding,
+            commitments: commitments.clone(),
+        };
+        (nonces, commitments)
+    }
+}
+
+impl<C: Ciphersuite> SignatureShare<C> {
+    /// z_i * G = D_i + rho_i * E_i + (c * lambda_i) * PK_i where PK_i is the public key of the
+    /// share of the signer.
+    pub fn verify(
+        &self,
+        commitments: &[SigningCommitments<C>],
+        msg: &[u8],
+        vss_scheme: &VerifiableSS<C::Point>,
+    ) -> Result<(), SignatureError> {
+        let indices = signers::<C>(commitments, vss_scheme)?;
+        let position = indices
+            .iter()
+            .position(|i| *i == self.index)
+            .ok_or(SignatureError)?;
+        let group_public_key = vss_scheme.commitments[0].clone();
+        let binding_factors = binding_factors(&group_public_key, commitments, msg);
+        let r = group_commitment(commitments, &binding_factors);
+        if r.is_zero() {
+            return Err(SignatureError);
+        }
+        let lambda = vss_scheme.map_share_to_new_params(self.index, &indices);
+        let c = challenge::<C>(&r, &group_public_key, msg);
+        let commitment_share = commitments[position].hiding.clone()
+            + commitments[position].binding.clone() * binding_factors[position].clone();
+        let pk = vss_scheme.get_point_commitment(self.index + 1);
+        if C::Point::mul_base(&self.z) == commitment_share + pk * (c * lambda) {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+
+    /// aggregates the shares of all the signers of the commitment list. The shares are not
+    /// verified, a single invalid one makes the result invalid.
+    pub fn aggregate(
+        shares: &[SignatureShare<C>],
+        commitments: &[SigningCommitments<C>],
+        msg: &[u8],
+        vss_scheme: &VerifiableSS<C::Point>,
+    ) -> Result<FrostSignature<C>, SignatureError> {
+        let indices = signers::<C>(commitments, vss_scheme)?;
+        let mut share_indices = shares.iter().map(|share| share.index).collect::<Vec<_>>();
+        share_indices.sort();
+        if share_indices != indices {
+            return Err(SignatureError);
+        }
+        let binding_factors = binding_factors(&vss_scheme.commitments[0], commitments, msg);
+        let r = group_commitment(commitments, &binding_factors);
+        if r.is_zero() {
+            return Err(SignatureError);
+        }
+        Ok(FrostSignature {
+            r,
+            z: shares
+                .iter()
+                .fold(C::Scalar::zero(), |acc, share| acc + share.z.clone()),
+        })
+    }
+}
+
+impl<C: Ciphersuite> FrostSignature<C> {
+    /// z * G = R + c * PK
+    pub fn verify(&self, msg: &[u8], pk: &C::Point) -> Result<(), SignatureError> {
+        if self.r.is_zero() || pk.is_zero() {
+            return Err(SignatureError);
+        }
+        let c = challenge::<C>(&self.r, pk, msg);
+        if C::Point::mul_base(&self.z) == self.r.clone() + pk.clone() * c {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+
+    /// SerializeElement(R) || SerializeScalar(z)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [C::serialize_element(&self.r), C::serialize_scalar(&self.z)].concat()
+    }
+}
+
+// checks the commitment list and returns the indices of the signers, the commitments may not
+// be the identity
+fn signers<C: Ciphersuite>(
+    commitments: &[SigningCommitments<C>],
+    vss_scheme: &VerifiableSS<C::Point>,
+) -> Result<Vec<usize>, SignatureError> {
+    let indices = commitments.iter().map(|c| c.index).collect::<Vec<_>>();
+    if indices.len() < vss_scheme.reconstruct_limit()
+        || indices.windows(2).any(|w| w[0] >= w[1])
+        || indices
+            .iter()
+            .any(|i| *i >= vss_scheme.parameters.share_count)
+        || commitments
+            .iter()
+            .any(|c| c.hiding.is_zero() || c.binding.is_zero())
+    {
+        return Err(SignatureError);
+    }
+    Ok(indices)
+}
+
+fn identifier<C: Ciphersuite>(index: usize) -> C::Scalar {
+    ECScalar::from(&BigInt::from(index as u64 + 1))
+}
+
+// rho_i = H1(group_public_key || H4(msg) || H5(encoded commitment list) || identifier_i)
+fn binding_factors<C: Ciphersuite>(
+    group_public_key: &C::Point,
+    commitments: &[SigningCommitments<C>],
+    msg: &[u8],
+) -> Vec<C::Scalar> {
+    let encoded_commitments = commitments
+        .iter()
+        .flat_map(|c| {
+            [
+                C::serialize_scalar(&identifier::<C>(c.index)),
+                C::serialize_element(&c.hiding),
+                C::serialize_element(&c.binding),
+            ]
+            .concat()
+        })
+        .collect::<Vec<u8>>();
+    let prefix = [
+        C::serialize_element(group_public_key),
+        C::h4(msg),
+        C::h5(&encoded_commitments),
+    ]
+    .concat();
+    commitments
+        .iter()
+        .map(|c| C::h1(&[&prefix[..], &C::serialize_scalar(&identifier::<C>(c.index))].concat()))
+        .collect()
+}
+
+// R = sum_i D_i + rho_i * E_i
+fn group_commitment<C: Ciphersuite>(
+    commitments: &[SigningCommitments<C>],
+    binding_factors: &[C::Scalar],
+) -> C::Point {
+    let points = commitments
+        .iter()
+        .flat_map(|c| vec![c.hiding.clone(), c.binding.clone()])
+        .collect::<Vec<_>>();
+    let scalars = binding_factors
+        .iter()
+        .flat_map(|rho| vec![ECScalar::from(&BigInt::one()), rho.clone()])
+        .collect::<Vec<_>>();
+    C::Point::multi_scalar_mul(&points, &scalars)
+}
+
+fn challenge<C: Ciphersuite>(r: &C::Point, pk: &C::Point, msg: &[u8]) -> C::Scalar {
+    C::h2(&[&C::serialize_element(r)[..], &C::serialize_element(pk), msg].concat())
+}
+
+// SHA-512 of the input read as a little endian integer mod q
+fn sha512_to_scalar<S: ECScalar>(data: &[&[u8]]) -> S {
+    let mut hasher = Sha512::new();
+    for d in data {
+        hasher.input(d);
+    }
+    let mut bytes = hasher.result().to_vec();
+    bytes.reverse();
+    ECScalar::from(&BigInt::modulus(&BigInt::from(&bytes[..]), &S::q()))
+}
+
+fn to_bytes_le<S: ECScalar>(scalar: &S) -> Vec<u8> {
+    let mut bytes = to_bytes_be(scalar);
+    bytes.reverse();
+    bytes
+}
+
+fn to_bytes_be<S: ECScalar>(scalar: &S) -> Vec<u8> {
+    let v = BigInt::to_vec(&scalar.to_big_int());
+    let mut bytes = vec![0u8; 32 - v.len()];
+    bytes.extend_from_slice(&v);
+    bytes
+}
+
+fn from_bytes_le<S: ECScalar>(bytes: &[u8]) -> S {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    ECScalar::from(&BigInt::from(&bytes[..]))
+}
+
+fn sha256(data: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for d in data {
+        hasher.input(d);
+    }
+    hasher.result().to_vec()
+}
+
+impl Ciphersuite for Ed25519Sha512 {
+    type Scalar = Ed25519Scalar;
+    type Point = Ed25519Point;
+
+    const CONTEXT_STRING: &'static str = "FROST-ED25519-SHA512-v1";
+
+    fn hash_to_scalar(tag: &[u8], m: &[u8]) -> Ed25519Scalar {
+        sha512_to_scalar(&[Self::CONTEXT_STRING.as_bytes(), tag, m])
+    }
+
+    fn hash(tag: &[u8], m: &[u8]) -> Vec<u8> {
+        Sha512::digest(&[Self::CONTEXT_STRING.as_bytes(), tag, m].concat()).to_vec()
+    }
+
+    fn serialize_element(point: &Ed25519Point) -> Vec<u8> {
+        point.pk_to_key_slice()
+    }
+
+    fn serialize_scalar(scalar: &Ed25519Scalar) -> Vec<u8> {
+        to_bytes_le(scalar)
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Ed25519Scalar {
+        from_bytes_le(bytes)
+    }
+
+    // the challenge of Ed25519, without the context string
+    fn h2(m: &[u8]) -> Ed25519Scalar {
+        sha512_to_scalar(&[m])
+    }
+}
+
+impl Ciphersuite for Ristretto255Sha512 {
+    type Scalar = RistrettoScalar;
+    type Point = RistrettoCurvPoint;
+
+    const CONTEXT_STRING: &'static str = "FROST-RISTRETTO255-SHA512-v1";
+
+    fn hash_to_scalar(tag: &[u8], m: &[u8]) -> RistrettoScalar {
+        sha512_to_scalar(&[Self::CONTEXT_STRING.as_bytes(), tag, m])
+    }
+
+    fn hash(tag: &[u8], m: &[u8]) -> Vec<u8> {
+        Sha512::digest(&[Self::CONTEXT_STRING.as_bytes(), tag, m].concat()).to_vec()
+    }
+
+    fn serialize_element(point: &RistrettoCurvPoint) -> Vec<u8> {
+        point.pk_to_key_slice()
+    }
+
+    fn serialize_scalar(scalar: &RistrettoScalar) -> Vec<u8> {
+        to_bytes_le(scalar)
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> RistrettoScalar {
+        from_bytes_le(bytes)
+    }
+}
+
+impl Ciphersuite for P256Sha256 {
+    type Scalar = Secp256r1Scalar;
+    type Point = Secp256r1Point;
+
+    const CONTEXT_STRING: &'static str = "FROST-P256-SHA256-v1";
+
+    // hash_to_field of RFC 9380 with expand_message_xmd and L = 48
+    fn hash_to_scalar(tag: &[u8], m: &[u8]) -> Secp256r1Scalar {
+        let dst = [Self::CONTEXT_STRING.as_bytes(), tag].concat();
+        ECScalar::from(&hash_to_field::<Sha256>(m, &dst, 1, &Secp256r1Scalar::q(), 48)[0])
+    }
+
+    fn hash(tag: &[u8], m: &[u8]) -> Vec<u8> {
+        sha256(&[Self::CONTEXT_STRING.as_bytes(), tag, m])
+    }
+
+    // compressed SEC1 encoding
+    fn serialize_element(point: &Secp256r1Point) -> Vec<u8> {
+        BigInt::to_vec(&point.bytes_compressed_to_big_int())
+    }
+
+    fn serialize_scalar(scalar: &Secp256r1Scalar) -> Vec<u8> {
+        to_bytes_be(scalar)
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Secp256r1Scalar {
+        ECScalar::from(&BigInt::from(bytes))
+    }
+}
+
+impl Ciphersuite for Secp256k1Sha256 {
+    type Scalar = Secp256k1Scalar;
+    type Point = Secp256k1Point;
+
+    const CONTEXT_STRING: &'static str = "FROST-secp256k1-SHA256-v1";
+
+    // hash_to_field of RFC 9380 with expand_message_xmd and L = 48
+    fn hash_to_scalar(tag: &[u8], m: &[u8]) -> Secp256k1Scalar {
+        let dst = [Self::CONTEXT_STRING.as_bytes(), tag].concat();
+        ECScalar::from(&hash_to_field::<Sha256>(m, &dst, 1, &Secp256k1Scalar::q(), 48)[0])
+    }
+
+    fn hash(tag: &[u8], m: &[u8]) -> Vec<u8> {
+        sha256(&[Self::CONTEXT_STRING.as_bytes(), tag, m])
+    }
+
+    // compressed SEC1 encoding
+    fn serialize_element(point: &Secp256k1Point) -> Vec<u8> {
+        BigInt::to_vec(&point.bytes_compressed_to_big_int())
+    }
+
+    fn serialize_scalar(scalar: &Secp256k1Scalar) -> Vec<u8> {
+        to_bytes_be(scalar)
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Secp256k1Scalar {
+        ECScalar::from(&BigInt::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::secret_sharing::feldman_vss::ShamirSecretSharing;
+    use crate::cryptographic_primitives::signatures::eddsa::{
+        Ed25519Signature, PublicKey, VerificationMode,
+    };
+
+    #[derive(Deserialize)]
+    struct Vectors {
+        inputs: Inputs,
+        round_one_outputs: Outputs<RoundOneOutput>,
+        round_two_outputs: Outputs<RoundTwoOutput>,
+        final_output: FinalOutput,
+    }
+
+    #[derive(Deserialize)]
+    struct Inputs {
+        participant_list: Vec<usize>,
+        group_secret_key: String,
+        verifying_key_key: String,
+        message: String,
+        share_polynomial_coefficients: Vec<String>,
+        participant_shares: Vec<ParticipantShare>,
+    }
+
+    #[derive(Deserialize)]
+    struct ParticipantShare {
+        identifier: usize,
+        participant_share: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Outputs<T> {
+        outputs: Vec<T>,
+    }
+
+    #[derive(Deserialize)]
+    struct RoundOneOutput {
+        identifier: usize,
+        hiding_nonce_randomness: String,
+        binding_nonce_randomness: String,
+        hiding_nonce: String,
+        binding_nonce: String,
+        hiding_nonce_commitment: String,
+        binding_nonce_commitment: String,
+        binding_factor: String,
+    }
+
+    #[derive(Deserialize)]
+    struct RoundTwoOutput {
+        identifier: usize,
+        sig_share: String,
+    }
+
+    #[derive(Deserialize)]
+    struct FinalOutput {
+        sig: String,
+    }
+
+    fn scalar<C: Ciphersuite>(hex: &str) -> C::Scalar {
+        C::deserialize_scalar(&hex::decode(hex).unwrap())
+    }
+
+    fn bytes32(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    }
+
+    fn test_rfc9591_vectors<C: Ciphersuite>(json: &str) -> FrostSignature<C> {
+        let vectors: Vectors = serde_json::from_str(json).unwrap();
+        let inputs = &vectors.inputs;
+        let coefficients = [
+            scalar::<C>(&inputs.group_secret_key),
+            scalar::<C>(&inputs.share_polynomial_coefficients[0]),
+        ];
+        let vss_scheme = VerifiableSS {
+            parameters: ShamirSecretSharing {
+                threshold: 1,
+                share_count: 3,
+            },
+            commitments: coefficients.iter().map(C::Point::mul_base).collect(),
+        };
+        assert_eq!(
+            hex::encode(C::serialize_element(&vss_scheme.commitments[0])),
+            inputs.verifying_key_key
+        );
+        let key_shares = inputs
+            .participant_shares
+            .iter()
+            .map(|share| {
+                let secret_share = scalar::<C>(&share.participant_share);
+                KeyShare::<C>::new(share.identifier - 1, secret_share, vss_scheme.clone()).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let msg = hex::decode(&inputs.message).unwrap();
+
+        let round_one = &vectors.round_one_outputs.outputs;
+        let (nonces, commitments): (Vec<_>, Vec<_>) = round_one
+            .iter()
+            .map(|output| {
+                let key_share = &key_shares[output.identifier - 1];
+                let (nonces, commitments) = key_share.commit_with_randomness(
+                    &bytes32(&output.hiding_nonce_randomness),
+                    &bytes32(&output.binding_nonce_randomness),
+                );
+                assert_eq!(nonces.hiding, scalar::<C>(&output.hiding_nonce));
+                assert_eq!(nonces.binding, scalar::<C>(&output.binding_nonce));
+                assert_eq!(
+                    hex::encode(C::serialize_element(&commitments.hiding)),
+                    output.hiding_nonce_commitment
+                );
+                assert_eq!(
+                    hex::encode(C::serialize_element(&commitments.binding)),
+                    output.binding_nonce_commitment
+                );
+                (nonces, commitments)
+            })
+            .unzip();
+        let signers = commitments.iter().map(|c| c.index + 1).collect::<Vec<_>>();
+        assert_eq!(signers, inputs.participant_list);
+        let rho = binding_factors(&vss_scheme.commitments[0], &commitments, &msg);
+        for (rho_i, output) in rho.iter().zip(round_one.iter()) {
+            assert_eq!(*rho_i, scalar::<C>(&output.binding_factor));
+        }
+
+        let shares = nonces
+            .into_iter()
+            .zip(round_one.iter())
+            .map(|(nonces, output)| {
+                key_shares[output.identifier - 1]
+                    .sign(nonces, &commitments, &msg)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        for (share, output) in shares.iter().zip(vectors.round_two_outputs.outputs.iter()) {
+            assert_eq!(share.index + 1, output.identifier);
+            assert_eq!(share.z, scalar::<C>(&output.sig_share));
+            assert!(share.verify(&commitments, &msg, &vss_scheme).is_ok());
+        }
+
+        let signature =
+            SignatureShare::aggregate(&shares, &commitments, &msg, &vss_scheme).unwrap();
+        assert_eq!(hex::encode(signature.to_bytes()), vectors.final_output.sig);
+        assert!(signature.verify(&msg, &vss_scheme.commitments[0]).is_ok());
+        signature
+    }
+
+    #[test]
+    fn test_rfc9591_ed25519() {
+        let signature = test_rfc9591_vectors::<Ed25519Sha512>(include_str!(
+            "../../../tests/data/frost/ed25519.json"
+        ));
+        // FROST(Ed25519, SHA-512) signatures are Ed25519 signatures
+        let sig = Ed25519Signature::from_bytes(&signature.to_bytes()).unwrap();
+        let pk = PublicKey::from_bytes(
+            &hex::decode("15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673")
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(sig
+            .verify(b"test", &pk, VerificationMode::Cofactored)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rfc9591_ristretto255() {
+        test_rfc9591_vectors::<Ristretto255Sha512>(include_str!(
+            "../../../tests/data/frost/ristretto255.json"
+        ));
+    }
+
+    #[test]
+    fn test_rfc9591_p256() {
+        test_rfc9591_vectors::<P256Sha256>(include_str!("../../../tests/data/frost/p256.json"));
+    }
+
+    #[test]
+    fn test_rfc9591_secp256k1() {
+        test_rfc9591_vectors::<Secp256k1Sha256>(include_str!(
+            "../../../tests/data/frost/secp256k1.json"
+        ));
+    }
+
+    fn test_threshold_signing<C: Ciphersuite>() {
+        let secret: C::Scalar = ECScalar::new_random();
+        let (vss_scheme, shares) = VerifiableSS::<C::Point>::share(2, 5, &secret);
+        let key_shares = shares
+            .into_iter()
+            .enumerate()
+            .map(|(i, share)| KeyShare::<C>::new(i, share, vss_scheme.clone()).unwrap())
+            .collect::<Vec<_>>();
+        let msg = b"message";
+
+        let signers = [0, 2, 4];
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            signers.iter().map(|i| key_shares[*i].commit()).unzip();
+        let shares = nonces
+            .into_iter()
+            .zip(signers.iter())
+            .map(|(nonces, i)| key_shares[*i].sign(nonces, &commitments, msg).unwrap())
+            .collect::<Vec<_>>();
+        for share in shares.iter() {
+            assert!(share.verify(&commitments, msg, &vss_scheme).is_ok());
+            assert!(share.verify(&commitments, b"other", &vss_scheme).is_err());
+        }
+        let signature = SignatureShare::aggregate(&shares, &commitments, msg, &vss_scheme).unwrap();
+        let group_public_key = key_shares[0].group_public_key();
+        assert!(signature.verify(msg, &group_public_key).is_ok());
+        assert!(signature.verify(b"other", &group_public_key).is_err());
+
+        // a wrong share is detected
+        let mut forged = shares[1].clone();
+        forged.z = forged.z + shares[0].z.clone();
+        assert!(forged.verify(&commitments, msg, &vss_scheme).is_err());
+
+        // t signers, unsorted commitments and missing shares are rejected
+        let (nonces, _) = key_shares[0].commit();
+        assert!(key_shares[0].sign(nonces, &commitments[..2], msg).is_err());
+        let unsorted = [
+            commitments[1].clone(),
+            commitments[0].clone(),
+            commitments[2].clone(),
+        ];
+        let (nonces, _) = key_shares[0].commit();
+        assert!(key_shares[0].sign(nonces, &unsorted, msg).is_err());
+        // nonces which don't match the commitments
+        let (nonces, _) = key_shares[0].commit();
+        assert!(key_shares[0].sign(nonces, &commitments, msg).is_err());
+        assert!(SignatureShare::aggregate(&shares[..2], &commitments, msg, &vss_scheme).is_err());
+
+        // identity commitments are rejected
+        let mut identity = commitments.clone();
+        identity[1].binding = C::Point::zero();
+        let (nonces, _) = key_shares[0].commit();
+        assert!(key_shares[0].sign(nonces, &identity, msg).is_err());
+        assert!(shares[0].verify(&identity, msg, &vss_scheme).is_err());
+        assert!(SignatureShare::aggregate(&shares, &identity, msg, &vss_scheme).is_err());
+
+        // and so are an identity R or public key
+        let identity_r = FrostSignature::<C> {
+            r: C::Point::zero(),
+            z: C::Scalar::zero(),
+        };
+        assert!(identity_r.verify(msg, &group_public_key).is_err());
+        let zero_key = FrostSignature::<C> {
+            r: C::Point::mul_base(&signature.z),
+            z: signature.z.clone(),
+        };
+        assert!(zero_key.verify(msg, &C::Point::zero()).is_err());
+    }
+
+    #[test]
+    fn test_threshold_signing_all_ciphersuites() {
+        test_threshold_signing::<Ed25519Sha512>();
+        test_threshold_signing::<Ristretto255Sha512>();
+        test_threshold_signing::<P256Sha256>();
+        test_threshold_signing::<Secp256k1Sha256>();
+    }
+
+    #[test]
+    fn test_serialize_messages() {
+        let secret: Secp256k1Scalar = ECScalar::new_random();
+        let (vss_scheme, shares) = VerifiableSS::<Secp256k1Point>::share(1, 2, &secret);
+        let key_share = KeyShare::<Secp256k1Sha256>::new(0, shares[0], vss_scheme).unwrap();
+        let (_, commitments) = key_share.commit();
+        let s = serde_json::to_string(&commitments).unwrap();
+        let des: SigningCommitments<Secp256k1Sha256> = serde_json::from_str(&s).unwrap();
+        assert_eq!(des, commitments);
+    }
+}
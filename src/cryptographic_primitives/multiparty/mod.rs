@@ -0,0 +1,23 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+/// Distributed key generation (Pedersen and GJKR) for t-of-n keys shared with Feldman VSS,
+/// with complaints against and disqualification of the dishonest dealers.
+pub mod dkg;
+
+/// FROST t-of-n threshold Schnorr signatures (RFC 9591) on key shares of a VerifiableSS, with
+/// the ed25519, ristretto255, P-256 and secp256k1 ciphersuites.
+pub mod frost;
+
+/// MuSig2 n-of-n Schnorr multi-signatures (BIP-327): the signers aggregate their public keys,
+/// exchange two nonces each in a single round (which can be run before the message is known)
+/// and send partial signatures which sum to a plain Schnorr signature under the aggregate key.
+pub mod musig2;
+
+/// Proactive refresh of the shares of a t-of-n key and resharing to a new (t', n') committee,
+/// keeping the group public key.
+pub mod reshare;
@@ -0,0 +1,806 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use crate::arithmetic::traits::NumberTests;
+use crate::cryptographic_primitives::signatures::bip340::{
+    negate, tagged_hash, to_bytes32, Bip340Signature, XOnlyPublicKey,
+};
+use crate::cryptographic_primitives::signatures::SignatureError;
+use crate::elliptic::curves::secp256_k1::Secp256k1Point;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use rand::{thread_rng, Rng};
+use serde::de;
+use serde::{Deserialize, Deserializer};
+
+/// MuSig2 as specified in BIP-327:
+/// J. Nick, T. Ruffing and E. Jin. MuSig2 for BIP340-compatible Multi-Signatures.
+/// https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki
+///
+/// protocol:
+/// 1) the signers aggregate their public keys with KeyAggContext::new and optionally tweak the
+///    aggregate key
+/// 2) every signer generates a SecretNonce and broadcasts its PublicNonce
+/// 3) anyone sums the public nonces into an AggregateNonce
+/// 4) every signer opens a SigningSession for the message and sends its PartialSignature
+/// 5) the partial signatures are checked with partial_verify and aggregated into the signature
+///
+/// The protocol is generic, the curve specific parts are in Musig2Curve. For Secp256k1Point the
+/// result is a BIP-340 signature under the x-only aggregate key. A SecretNonce must never be
+/// used twice: two partial signatures with the same nonce leak the secret key, so partial_sign
+/// consumes it.
+pub trait Musig2Curve: ECPoint + Clone {
+    type AggregatePublicKey;
+    type Signature;
+
+    /// the encoding of points in the hashes (cbytes_ext), all zero for the point at infinity
+    fn serialize_point(&self) -> Vec<u8>;
+
+    /// the encoding of the aggregate key in the hashes (xbytes)
+    fn serialize_aggregate_key(&self) -> Vec<u8>;
+
+    /// Schnorr signatures with x-only keys use the point with even y, so the aggregate key and R
+    /// are negated when this is false.
+    fn has_even_y(&self) -> bool;
+
+    /// the challenge e of the signature equation s * G = R + e * Q
+    fn challenge(r: &Self, aggregate_key: &Self, msg: &[u8]) -> Self::Scalar;
+
+    fn to_public_key(aggregate_key: &Self) -> Self::AggregatePublicKey;
+
+    fn to_signature(r: &Self, s: &Self::Scalar) -> Self::Signature;
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct KeyAggContext<P: ECPoint> {
+    pub pks: Vec<P>,
+    pub aggregate_key: P,
+    // the aggregate key is gacc * (a_1 * P_1 + ... + a_n * P_n) + tacc * G after the tweaks,
+    // gacc is 1 or -1
+    gacc: P::Scalar,
+    tacc: P::Scalar,
+    // hash of the list of public keys (L)
+    key_list_hash: [u8; 32],
+}
+
+pub struct SecretNonce<P: ECPoint> {
+    k1: P::Scalar,
+    k2: P::Scalar,
+    pk: P,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PublicNonce<P: ECPoint> {
+    pub r1: P,
+    pub r2: P,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AggregateNonce<P: ECPoint> {
+    pub r1: P,
+    pub r2: P,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PartialSignature<P: ECPoint> {
+    pub s: P::Scalar,
+}
+
+pub struct SigningSession<P: ECPoint> {
+    key_agg: KeyAggContext<P>,
+    // nonce coefficient, final nonce and challenge
+    b: P::Scalar,
+    r: P,
+    e: P::Scalar,
+}
+
+impl<P> KeyAggContext<P>
+where
+    P: Musig2Curve,
+    P::Scalar: Clone,
+{
+    /// the order of the public keys matters, sort them first to get a key independent of it.
+    pub fn new(pks: &[P]) -> Result<KeyAggContext<P>, SignatureError> {
+        if pks.is_empty() || pks.iter().any(|pk| pk.is_zero()) {
+            return Err(SignatureError);
+        }
+        let encoded = pks
+            .iter()
+            .map(|pk| pk.serialize_point())
+            .collect::<Vec<Vec<u8>>>();
+        let key_list_hash = tagged_hash(
+            "KeyAgg list",
+            &encoded.iter().map(|pk| &pk[..]).collect::<Vec<&[u8]>>(),
+        );
+        let mut ctx = KeyAggContext {
+            pks: pks.to_vec(),
+            aggregate_key: P::zero(),
+            gacc: ECScalar::from(&BigInt::one()),
+            tacc: ECScalar::zero(),
+            key_list_hash,
+        };
+        ctx.aggregate_key = pks
+            .iter()
+            .map(|pk| pk.clone() * ctx.coefficient(pk).unwrap())
+            .fold(P::zero(), |acc, x| acc + x);
+        if ctx.aggregate_key.is_zero() {
+            return Err(SignatureError);
+        }
+        Ok(ctx)
+    }
+
+    /// the key aggregation coefficient a_i of a signer, the second distinct key gets 1.
+    pub fn coefficient(&self, pk: &P) -> Result<P::Scalar, SignatureError> {
+        if !self.pks.iter().any(|p| p == pk) {
+            return Err(SignatureError);
+        }
+        let second_key = self.pks.iter().find(|p| **p != self.pks[0]);
+        if second_key == Some(pk) {
+            return Ok(ECScalar::from(&BigInt::one()));
+        }
+        let hash = tagged_hash(
+            "KeyAgg coefficient",
+            &[&self.key_list_hash, &pk.serialize_point()],
+        );
+        Ok(ECScalar::from(&BigInt::from(&hash[..])))
+    }
+
+    /// Q' = Q + t * G, used for BIP-32 derivation of the aggregate key
+    pub fn apply_plain_tweak(&self, tweak: &BigInt) -> Result<KeyAggContext<P>, SignatureError> {
+        self.apply_tweak(tweak, false)
+    }
+
+    /// Q' = Q + t * G where Q is first negated if its y is odd, used for Taproot tweaks
+    pub fn apply_xonly_tweak(&self, tweak: &BigInt) -> Result<KeyAggContext<P>, SignatureError> {
+        self.apply_tweak(tweak, true)
+    }
+
+    pub fn aggregate_public_key(&self) -> P::AggregatePublicKey {
+        P::to_public_key(&self.aggregate_key)
+    }
+
+    fn apply_tweak(
+        &self,
+        tweak: &BigInt,
+        is_xonly: bool,
+    ) -> Result<KeyAggContext<P>, SignatureError> {
+        if *tweak < BigInt::zero() || *tweak >= P::Scalar::q() {
+            return Err(SignatureError);
+        }
+        let g: P::Scalar = if is_xonly && !self.aggregate_key.has_even_y() {
+            negate::<P::Scalar>(&ECScalar::from(&BigInt::one()))
+        } else {
+            ECScalar::from(&BigInt::one())
+        };
+        let t: P::Scalar = ECScalar::from(tweak);
+        let aggregate_key = self.aggregate_key.clone() * g.clone() + P::mul_base(&t);
+        if aggregate_key.is_zero() {
+            return Err(SignatureError);
+        }
+        Ok(KeyAggContext {
+            pks: self.pks.clone(),
+            aggregate_key,
+            gacc: g.clone() * self.gacc.clone(),
+            tacc: t + g * self.tacc.clone(),
+            key_list_hash: self.key_list_hash,
+        })
+    }
+
+    // g * gacc, the sign applied to the sum of the keys
+    fn key_sign(&self) -> P::Scalar {
+        if self.aggregate_key.has_even_y() {
+            self.gacc.clone()
+        } else {
+            negate::<P::Scalar>(&self.gacc)
+        }
+    }
+}
+
+// a deserialized context is recomputed from the public keys, the aggregate key must be the one
+// of the deserialized tweaks
+impl<'de, P> Deserialize<'de> for KeyAggContext<P>
+where
+    P: Musig2Curve + Deserialize<'de>,
+    P::Scalar: Clone + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<KeyAggContext<P>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+        struct KeyAggContextFields<P: ECPoint> {
+            pks: Vec<P>,
+            aggregate_key: P,
+            gacc: P::Scalar,
+            tacc: P::Scalar,
+            key_list_hash: [u8; 32],
+        }
+
+        let fields = KeyAggContextFields::<P>::deserialize(deserializer)?;
+        let ctx = KeyAggContext::new(&fields.pks).map_err(de::Error::custom)?;
+        let gacc = fields.gacc.to_big_int();
+        if gacc != BigInt::one() && gacc != P::Scalar::q() - BigInt::one() {
+            return Err(de::Error::custom(SignatureError));
+        }
+        let aggregate_key =
+            ctx.aggregate_key.clone() * fields.gacc.clone() + P::mul_base(&fields.tacc);
+        if aggregate_key.is_zero()
+            || aggregate_key != fields.aggregate_key
+            || ctx.key_list_hash != fields.key_list_hash
+        {
+            return Err(de::Error::custom(SignatureError));
+        }
+        Ok(KeyAggContext {
+            pks: ctx.pks,
+            aggregate_key,
+            gacc: fields.gacc,
+            tacc: fields.tacc,
+            key_list_hash: ctx.key_list_hash,
+        })
+    }
+}
+
+impl<P> SecretNonce<P>
+where
+    P: Musig2Curve,
+    P::Scalar: Clone,
+{
+    /// NonceGen of BIP-327. The aggregate key and the message are optional, they only add
+    /// protection against a bad random number generator.
+    pub fn generate(
+        sk: &P::Scalar,
+        key_agg: Option<&KeyAggContext<P>>,
+        msg: Option<&[u8]>,
+    ) -> (SecretNonce<P>, PublicNonce<P>) {
+        let aggregate_key = key_agg.map(|ctx| ctx.aggregate_key.serialize_aggregate_key());
+        // a zero nonce only happens with negligible probability, the randomness is drawn again
+        loop {
+            let mut rand = [0u8; 32];
+            thread_rng().fill(&mut rand[..]);
+            if let Ok(nonces) =
+                SecretNonce::generate_internal(&rand, sk, aggregate_key.as_ref(), msg)
+            {
+                return nonces;
+            }
+        }
+    }
+
+    // fails if k1 or k2 is zero
+    fn generate_internal(
+        rand_prime: &[u8; 32],
+        sk: &P::Scalar,
+        aggregate_key: Option<&Vec<u8>>,
+        msg: Option<&[u8]>,
+    ) -> Result<(SecretNonce<P>, PublicNonce<P>), SignatureError> {
+        let pk = P::mul_base(sk);
+        let aux = tagged_hash("MuSig/aux", &[rand_prime]);
+        let rand = to_bytes32(&sk.to_big_int())
+            .iter()
+            .zip(aux.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<u8>>();
+        let pk_bytes = pk.serialize_point();
+        let aggregate_key = aggregate_key.map(|q| &q[..]).unwrap_or(&[]);
+        let msg_prefixed = match msg {
+            None => vec![0u8],
+            Some(msg) => {
+                let mut prefixed = vec![1u8];
+                prefixed.extend_from_slice(&(msg.len() as u64).to_be_bytes());
+                prefixed.extend_from_slice(msg);
+                prefixed
+            }
+        };
+        let k = |i: u8| {
+            let hash = tagged_hash(
+                "MuSig/nonce",
+                &[
+                    &rand,
+                    &[pk_bytes.len() as u8],
+                    &pk_bytes,
+                    &[aggregate_key.len() as u8],
+                    aggregate_key,
+                    &msg_prefixed,
+                    // no extra input
+                    &0u32.to_be_bytes(),
+                    &[i],
+                ],
+            );
+            let k: P::Scalar = ECScalar::from(&BigInt::from(&hash[..]));
+            if k.to_big_int() == BigInt::zero() {
+                return Err(SignatureError);
+            }
+            Ok(k)
+        };
+        let (k1, k2) = (k(0)?, k(1)?);
+        let public_nonce = PublicNonce {
+            r1: P::mul_base(&k1),
+            r2: P::mul_base(&k2),
+        };
+        Ok((SecretNonce { k1, k2, pk }, public_nonce))
+    }
+}
+
+impl<P> AggregateNonce<P>
+where
+    P: Musig2Curve,
+    P::Scalar: Clone,
+{
+    /// the public nonces must not contain the point at infinity, their sums may.
+    pub fn new(nonces: &[PublicNonce<P>]) -> Result<AggregateNonce<P>, SignatureError> {
+        if nonces.is_empty()
+            || nonces
+                .iter()
+                .any(|nonce| nonce.r1.is_zero() || nonce.r2.is_zero())
+        {
+            return Err(SignatureError);
+        }
+        Ok(AggregateNonce {
+            r1: nonces
+                .iter()
+                .fold(P::zero(), |acc, nonce| acc + nonce.r1.clone()),
+            r2: nonces
+                .iter()
+                .fold(P::zero(), |acc, nonce| acc + nonce.r2.clone()),
+        })
+    }
+}
+
+impl<P> SigningSession<P>
+where
+    P: Musig2Curve,
+    P::Scalar: Clone,
+{
+    pub fn new(
+        key_agg: &KeyAggContext<P>,
+        aggregate_nonce: &AggregateNonce<P>,
+        msg: &[u8],
+    ) -> SigningSession<P> {
+        let hash = tagged_hash(
+            "MuSig/noncecoef",
+            &[
+                &aggregate_nonce.r1.serialize_point(),
+                &aggregate_nonce.r2.serialize_point(),
+                &key_agg.aggregate_key.serialize_aggregate_key(),
+                msg,
+            ],
+        );
+        let b: P::Scalar = ECScalar::from(&BigInt::from(&hash[..]));
+        let mut r = aggregate_nonce.r1.clone() + aggregate_nonce.r2.clone() * b.clone();
+        // only a malicious signer can make R the point at infinity, G is used instead
+        if r.is_zero() {
+            r = P::generator();
+        }
+        let e = P::challenge(&r, &key_agg.aggregate_key, msg);
+        SigningSession {
+            key_agg: key_agg.clone(),
+            b,
+            r,
+            e,
+        }
+    }
+
+    /// fails if the secret key doesn't match the nonce or is not one of the aggregated keys.
+    pub fn partial_sign(
+        &self,
+        secret_nonce: SecretNonce<P>,
+        sk: &P::Scalar,
+    ) -> Result<PartialSignature<P>, SignatureError> {
+        let pk = P::mul_base(sk);
+        if pk != secret_nonce.pk {
+            return Err(SignatureError);
+        }
+        let a = self.key_agg.coefficient(&pk)?;
+        let d = self.key_agg.key_sign() * sk.clone();
+        let (k1, k2) = if self.r.has_even_y() {
+            (secret_nonce.k1, secret_nonce.k2)
+        } else {
+            (
+                negate::<P::Scalar>(&secret_nonce.k1),
+                negate::<P::Scalar>(&secret_nonce.k2),
+            )
+        };
+        Ok(PartialSignature {
+            s: k1 + self.b.clone() * k2 + self.e.clone() * a * d,
+        })
+    }
+
+    /// s_i * G = R_i + e * a_i * g * P_i, where R_i = R_i1 + b * R_i2 is negated like R
+    pub fn partial_verify(
+        &self,
+        partial_signature: &PartialSignature<P>,
+        public_nonce: &PublicNonce<P>,
+        pk: &P,
+    ) -> Result<(), SignatureError> {
+        let a = self.key_agg.coefficient(pk)?;
+        let mut r = public_nonce.r1.clone() + public_nonce.r2.clone() * self.b.clone();
+        if !self.r.has_even_y() {
+            r = r * negate::<P::Scalar>(&ECScalar::from(&BigInt::one()));
+        }
+        let c = self.e.clone() * a * self.key_agg.key_sign();
+        if P::mul_base(&partial_signature.s) == r + pk.clone() * c {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+
+    /// the partial signatures are not verified, a single invalid one makes the result invalid.
+    pub fn aggregate(&self, partial_signatures: &[PartialSignature<P>]) -> P::Signature {
+        let g: P::Scalar = if self.key_agg.aggregate_key.has_even_y() {
+            ECScalar::from(&BigInt::one())
+        } else {
+            negate::<P::Scalar>(&ECScalar::from(&BigInt::one()))
+        };
+        let s = partial_signatures
+            .iter()
+            .fold(self.e.clone() * g * self.key_agg.tacc.clone(), |acc, x| {
+                acc + x.s.clone()
+            });
+        P::to_signature(&self.r, &s)
+    }
+}
+
+impl Musig2Curve for Secp256k1Point {
+    type AggregatePublicKey = XOnlyPublicKey;
+    type Signature = Bip340Signature;
+
+    fn serialize_point(&self) -> Vec<u8> {
+        if self.is_zero() {
+            vec![0u8; 33]
+        } else {
+            self.get_element().serialize().to_vec()
+        }
+    }
+
+    fn serialize_aggregate_key(&self) -> Vec<u8> {
+        to_bytes32(&self.x_coor().unwrap()).to_vec()
+    }
+
+    fn has_even_y(&self) -> bool {
+        self.y_coor().is_some_and(|y| BigInt::is_even(&y))
+    }
+
+    fn challenge(r: &Self, aggregate_key: &Self, msg: &[u8]) -> Self::Scalar {
+        let e = tagged_hash(
+            "BIP0340/challenge",
+            &[
+                &r.serialize_aggregate_key(),
+                &aggregate_key.serialize_aggregate_key(),
+                msg,
+            ],
+        );
+        ECScalar::from(&BigInt::from(&e[..]))
+    }
+
+    fn to_public_key(aggregate_key: &Self) -> XOnlyPublicKey {
+        XOnlyPublicKey::from_point(aggregate_key).unwrap().0
+    }
+
+    fn to_signature(r: &Self, s: &Self::Scalar) -> Bip340Signature {
+        Bip340Signature {
+            r_x: r.x_coor().unwrap(),
+            s: *s,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::traits::Converter;
+    use crate::elliptic::curves::secp256_k1::Secp256k1Scalar;
+
+    fn point(compressed: &str) -> Secp256k1Point {
+        let pk = secp256k1::PublicKey::from_slice(&hex::decode(compressed).unwrap()).unwrap();
+        Secp256k1Point::from_bytes(&pk.serialize_uncompressed()[1..]).unwrap()
+    }
+
+    fn public_nonce(encoded: &str) -> PublicNonce<Secp256k1Point> {
+        PublicNonce {
+            r1: point(&encoded[..66]),
+            r2: point(&encoded[66..]),
+        }
+    }
+
+    #[test]
+    fn test_key_agg_vectors() {
+        // key_agg_vectors.json of BIP-327
+        let pks = [
+            point("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            point("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+            point("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"),
+        ];
+        let vectors: [(&[usize], &str); 4] = [
+            (
+                &[0, 1, 2],
+                "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c",
+            ),
+            (
+                &[2, 1, 0],
+                "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b",
+            ),
+            (
+                &[0, 0, 0],
+                "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935",
+            ),
+            (
+                &[0, 0, 1, 1],
+                "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e",
+            ),
+        ];
+        for (indices, expected) in vectors.iter() {
+            let keys = indices.iter().map(|i| pks[*i]).collect::<Vec<_>>();
+            let ctx = KeyAggContext::new(&keys).unwrap();
+            assert_eq!(
+                hex::encode(ctx.aggregate_public_key().to_bytes()),
+                *expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_tweaks() {
+        let pks = [
+            point("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+            point("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            point("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+        ];
+        let tweak = |hex: &str| BigInt::from_hex(hex);
+        let ctx = KeyAggContext::new(&pks)
+            .unwrap()
+            .apply_xonly_tweak(&tweak(
+                "E8F791FF9225A2AF0102AFFF4A9A723D9612A682A25EBE79802B263CDFCD83BB",
+            ))
+            .unwrap()
+            .apply_xonly_tweak(&tweak(
+                "AE2EA797CC0FE72AC5B97B97F3C6957D7E4199A167A58EB08BCAFFDA70AC0455",
+            ))
+            .unwrap()
+            .apply_plain_tweak(&tweak(
+                "F52ECBC565B3D8BEA2DFD5B75A4F457E54369809322E4120831626F290FA87E0",
+            ))
+            .unwrap()
+            .apply_plain_tweak(&tweak(
+                "1969AD73CC177FA0B4FCED6DF1F7BF9907E665FDE9BA196A74FED0A3CF5AEF9D",
+            ))
+            .unwrap();
+        assert_eq!(
+            ctx.aggregate_key,
+            point("0269434B39A026A4AAC9E6C1AEBDD3993FFA581C8F7F21B6FAAE15608057F5CE85")
+        );
+        assert_eq!(ctx.gacc.to_big_int(), Secp256k1Scalar::q() - BigInt::one());
+        assert_eq!(
+            ctx.tacc.to_big_int(),
+            tweak("A5BEB2D09000E2391E98EEBC8AA80CD4FB13845DC75B673D8466609410627D0B")
+        );
+
+        assert!(ctx.apply_plain_tweak(&Secp256k1Scalar::q()).is_err());
+    }
+
+    #[test]
+    fn test_nonce_agg_vectors() {
+        // nonce_agg_vectors.json of BIP-327
+        let nonces = [
+            public_nonce(
+                "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E666\
+                 03BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+            ),
+            public_nonce(
+                "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6\
+                 0248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            ),
+        ];
+        let aggregate_nonce = AggregateNonce::new(&nonces).unwrap();
+        assert_eq!(
+            hex::encode(aggregate_nonce.r1.serialize_point()),
+            "035fe1873b4f2967f52fea4a06ad5a8eccbe9d0fd73068012c894e2e87ccb5804b"
+        );
+        assert_eq!(
+            hex::encode(aggregate_nonce.r2.serialize_point()),
+            "024725377345bde0e9c33af3c43c0a29a9249f2f2956fa8cfeb55c8573d0262dc8"
+        );
+
+        // the sum may be the point at infinity, encoded as 33 zero bytes
+        let negated = PublicNonce {
+            r1: nonces[1].r1,
+            r2: Secp256k1Point::zero().sub_point(&nonces[0].r2.get_element()),
+        };
+        let aggregate_nonce = AggregateNonce::new(&[nonces[0].clone(), negated]).unwrap();
+        assert_eq!(aggregate_nonce.r2.serialize_point(), vec![0u8; 33]);
+
+        // but a public nonce may not
+        let invalid = PublicNonce {
+            r1: nonces[0].r1,
+            r2: Secp256k1Point::zero(),
+        };
+        assert!(AggregateNonce::new(&[nonces[1].clone(), invalid]).is_err());
+        assert!(AggregateNonce::<Secp256k1Point>::new(&[]).is_err());
+    }
+
+    #[test]
+    fn test_sign_verify_vectors() {
+        // sign_verify_vectors.json of BIP-327, the valid test cases without the point at infinity
+        let sk: Secp256k1Scalar = ECScalar::from(&BigInt::from_hex(
+            "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671",
+        ));
+        let pks = [
+            point("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+            point("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            point("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661"),
+        ];
+        let nonces = [
+            public_nonce(
+                "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA\
+                 0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+            ),
+            public_nonce(
+                "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798\
+                 0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            ),
+            public_nonce(
+                "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE93\
+                 03E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+            ),
+        ];
+        let msg = hex::decode("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF")
+            .unwrap();
+        let vectors: [(&[usize], &str); 3] = [
+            (
+                &[0, 1, 2],
+                "012abbcb52b3016ac03ad82395a1a415c48b93def78718e62a7a90052fe224fb",
+            ),
+            (
+                &[1, 0, 2],
+                "9ff2f7aaa856150cc8819254218d3adeeb0535269051897724f9db3789513a52",
+            ),
+            (
+                &[1, 2, 0],
+                "fa23c359f6fac4e7796bb93bc9f0532a95468c539ba20ff86d7c76ed92227900",
+            ),
+        ];
+        for (indices, expected) in vectors.iter() {
+            let keys = indices.iter().map(|i| pks[*i]).collect::<Vec<_>>();
+            let public_nonces = indices
+                .iter()
+                .map(|i| nonces[*i].clone())
+                .collect::<Vec<_>>();
+            let ctx = KeyAggContext::new(&keys).unwrap();
+            let aggregate_nonce = AggregateNonce::new(&public_nonces).unwrap();
+            let session = SigningSession::new(&ctx, &aggregate_nonce, &msg);
+            let secret_nonce = SecretNonce {
+                k1: ECScalar::from(&BigInt::from_hex(
+                    "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61",
+                )),
+                k2: ECScalar::from(&BigInt::from_hex(
+                    "FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7",
+                )),
+                pk: pks[0],
+            };
+            let partial_signature = session.partial_sign(secret_nonce, &sk).unwrap();
+            assert_eq!(partial_signature.s.to_big_int(), BigInt::from_hex(expected));
+            assert!(session
+                .partial_verify(&partial_signature, &nonces[0], &pks[0])
+                .is_ok());
+        }
+    }
+
+    fn sign(
+        sks: &[Secp256k1Scalar],
+        ctx: &KeyAggContext<Secp256k1Point>,
+        msg: &[u8],
+    ) -> Bip340Signature {
+        let pks = sks.iter().map(Secp256k1Point::mul_base).collect::<Vec<_>>();
+        let (secret_nonces, public_nonces): (Vec<_>, Vec<_>) = sks
+            .iter()
+            .map(|sk| SecretNonce::generate(sk, Some(ctx), Some(msg)))
+            .unzip();
+        let aggregate_nonce = AggregateNonce::new(&public_nonces).unwrap();
+        let session = SigningSession::new(ctx, &aggregate_nonce, msg);
+        let partial_signatures = secret_nonces
+            .into_iter()
+            .zip(sks.iter())
+            .map(|(secret_nonce, sk)| session.partial_sign(secret_nonce, sk).unwrap())
+            .collect::<Vec<_>>();
+        for i in 0..sks.len() {
+            assert!(session
+                .partial_verify(&partial_signatures[i], &public_nonces[i], &pks[i])
+                .is_ok());
+            let j = (i + 1) % sks.len();
+            assert!(session
+                .partial_verify(&partial_signatures[i], &public_nonces[j], &pks[j])
+                .is_err());
+        }
+        session.aggregate(&partial_signatures)
+    }
+
+    #[test]
+    fn test_sign() {
+        let sks = (0..3)
+            .map(|_| ECScalar::new_random())
+            .collect::<Vec<Secp256k1Scalar>>();
+        let pks = sks.iter().map(Secp256k1Point::mul_base).collect::<Vec<_>>();
+        let ctx = KeyAggContext::new(&pks).unwrap();
+        let msg = b"message";
+        let signature = sign(&sks, &ctx, msg);
+        assert!(signature.verify(msg, &ctx.aggregate_public_key()).is_ok());
+        assert!(signature
+            .verify(b"other message", &ctx.aggregate_public_key())
+            .is_err());
+
+        // a tweaked key signs for the tweaked aggregate key
+        let tweaked = ctx
+            .apply_xonly_tweak(&BigInt::from(7))
+            .unwrap()
+            .apply_plain_tweak(&BigInt::from(11))
+            .unwrap();
+        let signature = sign(&sks, &tweaked, msg);
+        assert!(signature
+            .verify(msg, &tweaked.aggregate_public_key())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_signers() {
+        let sks = (0..2)
+            .map(|_| ECScalar::new_random())
+            .collect::<Vec<Secp256k1Scalar>>();
+        let pks = sks.iter().map(Secp256k1Point::mul_base).collect::<Vec<_>>();
+        let ctx = KeyAggContext::new(&pks).unwrap();
+        let (nonce_0, public_nonce_0) = SecretNonce::generate(&sks[0], None, None);
+        let (nonce_1, public_nonce_1) = SecretNonce::generate(&sks[1], None, None);
+        let aggregate_nonce = AggregateNonce::new(&[public_nonce_0, public_nonce_1]).unwrap();
+        let session = SigningSession::new(&ctx, &aggregate_nonce, b"message");
+
+        // the nonce of another signer, and a key which was not aggregated
+        assert!(session.partial_sign(nonce_0, &sks[1]).is_err());
+        let other: Secp256k1Scalar = ECScalar::new_random();
+        let (nonce_other, _) = SecretNonce::generate(&other, None, None);
+        assert!(session.partial_sign(nonce_other, &other).is_err());
+        assert!(session.partial_sign(nonce_1, &sks[1]).is_ok());
+
+        assert!(KeyAggContext::<Secp256k1Point>::new(&[]).is_err());
+        assert!(KeyAggContext::new(&[pks[0], Secp256k1Point::zero()]).is_err());
+    }
+
+    #[test]
+    fn test_serialize_messages() {
+        let sk: Secp256k1Scalar = ECScalar::new_random();
+        let (_, public_nonce) = SecretNonce::<Secp256k1Point>::generate(&sk, None, None);
+        let s = serde_json::to_string(&public_nonce).unwrap();
+        let des: PublicNonce<Secp256k1Point> = serde_json::from_str(&s).unwrap();
+        assert_eq!(des, public_nonce);
+
+        let partial_signature = PartialSignature::<Secp256k1Point> { s: sk };
+        let s = serde_json::to_string(&partial_signature).unwrap();
+        let des: PartialSignature<Secp256k1Point> = serde_json::from_str(&s).unwrap();
+        assert_eq!(des, partial_signature);
+
+        let pks = (0..2)
+            .map(|_| Secp256k1Point::mul_base(&ECScalar::new_random()))
+            .collect::<Vec<_>>();
+        let ctx = KeyAggContext::new(&pks)
+            .unwrap()
+            .apply_xonly_tweak(&BigInt::from(7))
+            .unwrap();
+        let s = serde_json::to_string(&ctx).unwrap();
+        let des: KeyAggContext<Secp256k1Point> = serde_json::from_str(&s).unwrap();
+        assert_eq!(des.aggregate_key, ctx.aggregate_key);
+        assert_eq!(des.pks, ctx.pks);
+
+        // the aggregate key must match the public keys and the tweaks
+        let mut tampered = ctx.clone();
+        tampered.aggregate_key = Secp256k1Point::zero();
+        let s = serde_json::to_string(&tampered).unwrap();
+        assert!(serde_json::from_str::<KeyAggContext<Secp256k1Point>>(&s).is_err());
+        let mut tampered = ctx;
+        tampered.tacc = ECScalar::from(&BigInt::from(8));
+        let s = serde_json::to_string(&tampered).unwrap();
+        assert!(serde_json::from_str::<KeyAggContext<Secp256k1Point>>(&s).is_err());
+    }
+}
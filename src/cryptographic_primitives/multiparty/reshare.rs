@@ -0,0 +1,432 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use std::error::Error;
+use std::fmt::{self, Debug};
+
+use super::dkg::LocalKey;
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::elliptic::curves::traits::*;
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+
+/// Proactive refresh and resharing of a t-of-n key, e.g. a LocalKey of the DKG. Both keep the
+/// secret, so the group public key (commitments[0]) is checked to be unchanged.
+///
+/// Refresh: A. Herzberg, S. Jarecki, H. Krawczyk and M. Yung. Proactive Secret Sharing Or: How
+/// to Cope With Perpetual Leakage. In CRYPTO 1995, Springer (LNCS 963), pages 339–352, 1995.
+/// Every party deals a VerifiableSS of zero and adds the sub-shares it receives to its share,
+/// the shares of the previous period become useless.
+///
+/// Reshare: Y. Desmedt and S. Jajodia. Redistributing secret shares to new access structures
+/// and its applications. Technical Report ISSE TR-97-01, George Mason University, 1997.
+/// t + 1 or more parties S of the old committee deal a (t', n') VerifiableSS of
+/// map_share_to_new_params(i, S) * x_i, and every party of the new committee sums its
+/// sub-shares. The new parties check the constant term of every dealing against the public
+/// key of the share of its dealer in the old VerifiableSS.
+///
+/// In both protocols the commitments of a Dealing are broadcast and sub_share(j) is sent to
+/// party j only.
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct Dealing<P: ECPoint> {
+    pub dealer: usize,
+    pub vss_scheme: VerifiableSS<P>,
+    shares: Vec<P::Scalar>,
+}
+
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct SubShare<P: ECPoint> {
+    pub dealer: usize,
+    pub vss_scheme: VerifiableSS<P>,
+    pub share: P::Scalar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReshareError {
+    InvalidParameters,
+    /// the dealing of this party is invalid
+    InvalidSubShare(usize),
+    PublicKeyChanged,
+}
+
+impl fmt::Display for ReshareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ReshareError::{:?}", self)
+    }
+}
+
+impl Error for ReshareError {
+    fn description(&self) -> &str {
+        "Error while refreshing or resharing"
+    }
+}
+
+impl<P> Dealing<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    /// the sub-share of party index (0-based) of the new committee
+    pub fn sub_share(&self, index: usize) -> SubShare<P> {
+        SubShare {
+            dealer: self.dealer,
+            vss_scheme: self.vss_scheme.clone(),
+            share: self.shares[index].clone(),
+        }
+    }
+}
+
+pub fn refresh_dealing<P>(key: &LocalKey<P>) -> Dealing<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    let parameters = &key.vss_scheme.parameters;
+    let (vss_scheme, shares) = VerifiableSS::share(
+        parameters.threshold,
+        parameters.share_count,
+        &ECScalar::zero(),
+    );
+    Dealing {
+        dealer: key.index,
+        vss_scheme,
+        shares,
+    }
+}
+
+/// all the parties must use the sub-shares of the same dealers, including their own
+pub fn refresh<P>(
+    key: &LocalKey<P>,
+    sub_shares: &[SubShare<P>],
+) -> Result<LocalKey<P>, ReshareError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    let n = key.vss_scheme.parameters.share_count;
+    check_dealers(sub_shares, n)?;
+    for sub_share in sub_shares {
+        if sub_share.vss_scheme.parameters != key.vss_scheme.parameters
+            || sub_share.vss_scheme.commitments.len() != key.vss_scheme.commitments.len()
+            || !sub_share.vss_scheme.commitments[0].is_zero()
+        {
+            return Err(ReshareError::InvalidSubShare(sub_share.dealer));
+        }
+    }
+    let mut refreshed = combine(key.index, sub_shares)?;
+    refreshed.secret_share = refreshed.secret_share + key.secret_share.clone();
+    refreshed.vss_scheme.commitments = refreshed
+        .vss_scheme
+        .commitments
+        .into_iter()
+        .zip(key.vss_scheme.commitments.iter())
+        .map(|(c, old)| c + old.clone())
+        .collect();
+    refreshed.qualified = key.qualified.clone();
+    check_public_key(&refreshed, &key.vss_scheme)?;
+    Ok(refreshed)
+}
+
+/// dealt by the parties of the old committee in dealers, which must be the same for all of
+/// them and hold t + 1 indices or more
+pub fn reshare_dealing<P>(
+    key: &LocalKey<P>,
+    dealers: &[usize],
+    new_threshold: usize,
+    new_share_count: usize,
+) -> Result<Dealing<P>, ReshareError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    check_indices(dealers, &key.vss_scheme)?;
+    if !dealers.contains(&key.index) || new_threshold >= new_share_count {
+        return Err(ReshareError::InvalidParameters);
+    }
+    let lambda = key.vss_scheme.map_share_to_new_params(key.index, dealers);
+    let (vss_scheme, shares) = VerifiableSS::share(
+        new_threshold,
+        new_share_count,
+        &(lambda * key.secret_share.clone()),
+    );
+    Ok(Dealing {
+        dealer: key.index,
+        vss_scheme,
+        shares,
+    })
+}
+
+/// run by party index of the new committee with the sub-shares of all the dealers
+pub fn reshare<P>(
+    old_vss_scheme: &VerifiableSS<P>,
+    index: usize,
+    sub_shares: &[SubShare<P>],
+) -> Result<LocalKey<P>, ReshareError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    let dealers = sub_shares.iter().map(|s| s.dealer).collect::<Vec<_>>();
+    check_indices(&dealers, old_vss_scheme)?;
+    let parameters = &sub_shares[0].vss_scheme.parameters;
+    if index >= parameters.share_count {
+        return Err(ReshareError::InvalidParameters);
+    }
+    for sub_share in sub_shares {
+        let lambda = old_vss_scheme.map_share_to_new_params(sub_share.dealer, &dealers);
+        let public_share = old_vss_scheme.get_point_commitment(sub_share.dealer + 1);
+        if sub_share.vss_scheme.parameters != *parameters
+            || sub_share.vss_scheme.commitments.len() != parameters.threshold + 1
+            || sub_share.vss_scheme.commitments[0] != public_share * lambda
+        {
+            return Err(ReshareError::InvalidSubShare(sub_share.dealer));
+        }
+    }
+    let mut reshared = combine(index, sub_shares)?;
+    reshared.qualified = dealers;
+    check_public_key(&reshared, old_vss_scheme)?;
+    Ok(reshared)
+}
+
+// validates the sub-shares of party index and sums them with their commitments
+fn combine<P>(index: usize, sub_shares: &[SubShare<P>]) -> Result<LocalKey<P>, ReshareError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    for sub_share in sub_shares {
+        if sub_share
+            .vss_scheme
+            .validate_share(&sub_share.share, index + 1)
+            .is_err()
+        {
+            return Err(ReshareError::InvalidSubShare(sub_share.dealer));
+        }
+    }
+    let secret_share = sub_shares
+        .iter()
+        .fold(ECScalar::zero(), |acc: P::Scalar, s| acc + s.share.clone());
+    let commitments = (0..sub_shares[0].vss_scheme.commitments.len())
+        .map(|k| {
+            sub_shares.iter().fold(P::zero(), |acc, s| {
+                acc + s.vss_scheme.commitments[k].clone()
+            })
+        })
+        .collect();
+    Ok(LocalKey {
+        index,
+        vss_scheme: VerifiableSS {
+            parameters: sub_shares[0].vss_scheme.parameters.clone(),
+            commitments,
+        },
+        secret_share,
+        qualified: Vec::new(),
+    })
+}
+
+fn check_dealers<P: ECPoint>(sub_shares: &[SubShare<P>], n: usize) -> Result<(), ReshareError> {
+    let mut dealers = sub_shares.iter().map(|s| s.dealer).collect::<Vec<_>>();
+    dealers.sort_unstable();
+    dealers.dedup();
+    if dealers.is_empty() || dealers.len() != sub_shares.len() || dealers[dealers.len() - 1] >= n {
+        return Err(ReshareError::InvalidParameters);
+    }
+    Ok(())
+}
+
+fn check_indices<P>(dealers: &[usize], vss_scheme: &VerifiableSS<P>) -> Result<(), ReshareError>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    let mut sorted = dealers.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != dealers.len()
+        || dealers.len() < vss_scheme.reconstruct_limit()
+        || sorted[sorted.len() - 1] >= vss_scheme.parameters.share_count
+    {
+        return Err(ReshareError::InvalidParameters);
+    }
+    Ok(())
+}
+
+fn check_public_key<P>(
+    key: &LocalKey<P>,
+    old_vss_scheme: &VerifiableSS<P>,
+) -> Result<(), ReshareError>
+where
+    P: ECPoint + Clone,
+{
+    if key.vss_scheme.commitments[0] == old_vss_scheme.commitments[0] {
+        Ok(())
+    } else {
+        Err(ReshareError::PublicKeyChanged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    fn deal_keys<P>(t: usize, n: usize, secret: &P::Scalar) -> Vec<LocalKey<P>>
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let (vss_scheme, shares) = VerifiableSS::<P>::share(t, n, secret);
+        shares
+            .into_iter()
+            .enumerate()
+            .map(|(index, secret_share)| LocalKey {
+                index,
+                vss_scheme: vss_scheme.clone(),
+                secret_share,
+                qualified: (0..n).collect(),
+            })
+            .collect()
+    }
+
+    fn reconstruct<P>(keys: &[LocalKey<P>]) -> P::Scalar
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let indices = keys.iter().map(|k| k.index).collect::<Vec<_>>();
+        let shares = keys
+            .iter()
+            .map(|k| k.secret_share.clone())
+            .collect::<Vec<_>>();
+        keys[0].vss_scheme.reconstruct(&indices, &shares)
+    }
+
+    test_for_all_curves!(test_refresh);
+
+    fn test_refresh<P>()
+    where
+        P: ECPoint + Clone + Debug,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let keys = deal_keys::<P>(2, 5, &secret);
+        let dealings = keys.iter().map(refresh_dealing).collect::<Vec<_>>();
+        let refreshed = keys
+            .iter()
+            .map(|key| {
+                let sub_shares = dealings
+                    .iter()
+                    .map(|d| d.sub_share(key.index))
+                    .collect::<Vec<_>>();
+                refresh(key, &sub_shares).unwrap()
+            })
+            .collect::<Vec<_>>();
+        for (key, old) in refreshed.iter().zip(keys.iter()) {
+            assert_ne!(key.secret_share, old.secret_share);
+            assert_eq!(key.vss_scheme, refreshed[0].vss_scheme);
+            assert_eq!(key.vss_scheme.commitments[0], old.vss_scheme.commitments[0]);
+            assert!(key
+                .vss_scheme
+                .validate_share(&key.secret_share, key.index + 1)
+                .is_ok());
+        }
+        assert_eq!(reconstruct(&refreshed[1..4]), secret);
+        // old and new shares don't mix
+        let mixed = vec![keys[0].clone(), refreshed[1].clone(), refreshed[2].clone()];
+        assert_ne!(reconstruct(&mixed), secret);
+
+        // a dealing of a non zero secret would change the key
+        let mut sub_shares = dealings.iter().map(|d| d.sub_share(0)).collect::<Vec<_>>();
+        let (vss_scheme, shares) = VerifiableSS::<P>::share(2, 5, &ECScalar::new_random());
+        sub_shares[3] = SubShare {
+            dealer: 3,
+            vss_scheme,
+            share: shares[0].clone(),
+        };
+        assert_eq!(
+            refresh(&keys[0], &sub_shares),
+            Err(ReshareError::InvalidSubShare(3))
+        );
+        // a wrong sub-share
+        let mut sub_shares = dealings.iter().map(|d| d.sub_share(0)).collect::<Vec<_>>();
+        sub_shares[1].share = dealings[1].sub_share(1).share;
+        assert_eq!(
+            refresh(&keys[0], &sub_shares),
+            Err(ReshareError::InvalidSubShare(1))
+        );
+    }
+
+    test_for_all_curves!(test_reshare);
+
+    fn test_reshare<P>()
+    where
+        P: ECPoint + Clone + Debug,
+        P::Scalar: Clone + PartialEq + Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let keys = deal_keys::<P>(2, 5, &secret);
+        let old_vss_scheme = keys[0].vss_scheme.clone();
+        let dealers = [0, 2, 4];
+        let dealings = dealers
+            .iter()
+            .map(|i| reshare_dealing(&keys[*i], &dealers, 3, 7).unwrap())
+            .collect::<Vec<_>>();
+        let reshared = (0..7)
+            .map(|j| {
+                let sub_shares = dealings.iter().map(|d| d.sub_share(j)).collect::<Vec<_>>();
+                reshare(&old_vss_scheme, j, &sub_shares).unwrap()
+            })
+            .collect::<Vec<_>>();
+        for key in reshared.iter() {
+            assert_eq!(key.vss_scheme, reshared[0].vss_scheme);
+            assert_eq!(key.vss_scheme.parameters.threshold, 3);
+            assert_eq!(key.vss_scheme.parameters.share_count, 7);
+            assert_eq!(key.public_key(), old_vss_scheme.commitments[0]);
+            assert!(key
+                .vss_scheme
+                .validate_share(&key.secret_share, key.index + 1)
+                .is_ok());
+        }
+        assert_eq!(reconstruct(&reshared[2..6]), secret);
+
+        // t parties can't reshare
+        assert_eq!(
+            reshare_dealing(&keys[0], &[0, 1], 3, 7).unwrap_err(),
+            ReshareError::InvalidParameters
+        );
+        let sub_shares = dealings[..2]
+            .iter()
+            .map(|d| d.sub_share(0))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reshare(&old_vss_scheme, 0, &sub_shares).unwrap_err(),
+            ReshareError::InvalidParameters
+        );
+        // a dealer which doesn't reshare its own share is detected
+        let mut sub_shares = dealings.iter().map(|d| d.sub_share(0)).collect::<Vec<_>>();
+        let (vss_scheme, shares) = VerifiableSS::<P>::share(3, 7, &ECScalar::new_random());
+        sub_shares[1] = SubShare {
+            dealer: 2,
+            vss_scheme,
+            share: shares[0].clone(),
+        };
+        assert_eq!(
+            reshare(&old_vss_scheme, 0, &sub_shares).unwrap_err(),
+            ReshareError::InvalidSubShare(2)
+        );
+    }
+}
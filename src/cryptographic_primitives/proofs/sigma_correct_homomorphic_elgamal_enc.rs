@@ -10,6 +10,8 @@ use super::ProofError;
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::elliptic::curves::traits::*;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// This is a proof of knowledge that a pair of group elements {D, E}
@@ -50,8 +52,16 @@ where
         w: &HomoElGamalWitness<P::Scalar>,
         delta: &HomoElGamalStatement<P>,
     ) -> HomoELGamalProof<P> {
-        let mut s1: P::Scalar = ECScalar::new_random();
-        let mut s2: P::Scalar = ECScalar::new_random();
+        Self::prove_with_rng(w, delta, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        w: &HomoElGamalWitness<P::Scalar>,
+        delta: &HomoElGamalStatement<P>,
+        rng: &mut R,
+    ) -> HomoELGamalProof<P> {
+        let mut s1: P::Scalar = ECScalar::new_random_with_rng(rng);
+        let mut s2: P::Scalar = ECScalar::new_random_with_rng(rng);
         let mut A1 = delta.H.clone() * s1.clone();
         let mut A2 = delta.Y.clone() * s2.clone();
         let A3 = delta.G.clone() * s2.clone();
@@ -10,6 +10,8 @@ use super::ProofError;
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::elliptic::curves::traits::*;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// This is a proof of knowledge that a pair of group elements {D, E}
@@ -51,8 +53,16 @@ where
         w: &HomoElGamalDlogWitness<P::Scalar>,
         delta: &HomoElGamalDlogStatement<P>,
     ) -> HomoELGamalDlogProof<P> {
-        let mut s1: P::Scalar = ECScalar::new_random();
-        let mut s2: P::Scalar = ECScalar::new_random();
+        Self::prove_with_rng(w, delta, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        w: &HomoElGamalDlogWitness<P::Scalar>,
+        delta: &HomoElGamalDlogStatement<P>,
+        rng: &mut R,
+    ) -> HomoELGamalDlogProof<P> {
+        let mut s1: P::Scalar = ECScalar::new_random_with_rng(rng);
+        let mut s2: P::Scalar = ECScalar::new_random_with_rng(rng);
         let A1 = delta.G.clone() * s1.clone();
         let A2 = delta.Y.clone() * s2.clone();
         let A3 = delta.G.clone() * s2.clone();
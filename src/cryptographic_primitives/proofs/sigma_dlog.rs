@@ -11,6 +11,8 @@ use crate::elliptic::curves::traits::*;
 
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// This is implementation of Schnorr's identification protocol for elliptic curve groups or a
@@ -36,12 +38,15 @@ where
     P::Scalar: Zeroize,
 {
     pub fn prove(sk: &P::Scalar) -> DLogProof<P> {
+        Self::prove_with_rng(sk, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(sk: &P::Scalar, rng: &mut R) -> DLogProof<P> {
         let base_point: P = ECPoint::generator();
         let generator_x = base_point.bytes_compressed_to_big_int();
-        let mut sk_t_rand_commitment: P::Scalar = ECScalar::new_random();
-        let pk_t_rand_commitment = base_point.scalar_mul(&sk_t_rand_commitment.get_element());
-        let ec_point: P = ECPoint::generator();
-        let pk = ec_point.scalar_mul(&sk.get_element());
+        let mut sk_t_rand_commitment: P::Scalar = ECScalar::new_random_with_rng(rng);
+        let pk_t_rand_commitment = P::mul_base(&sk_t_rand_commitment);
+        let pk = P::mul_base(sk);
         let challenge = HSha256::create_hash(&[
             &pk_t_rand_commitment.bytes_compressed_to_big_int(),
             &generator_x,
@@ -67,14 +72,14 @@ where
         ]);
 
         let sk_challenge: P::Scalar = ECScalar::from(&challenge);
-        let pk = proof.pk.clone();
-        let pk_challenge = pk.scalar_mul(&sk_challenge.get_element());
-
         let base_point: P = ECPoint::generator();
 
-        let mut pk_verifier = base_point.scalar_mul(&proof.challenge_response.get_element());
-
-        pk_verifier = pk_verifier.add_point(&pk_challenge.get_element());
+        // challenge_response * G + challenge * pk
+        let challenge_response: P::Scalar = ECScalar::from(&proof.challenge_response.to_big_int());
+        let pk_verifier = P::multi_scalar_mul(
+            &[base_point, proof.pk.clone()],
+            &[challenge_response, sk_challenge],
+        );
 
         if pk_verifier == proof.pk_t_rand_commitment {
             Ok(())
@@ -102,4 +107,25 @@ mod tests {
             Err(_e) => assert!(false),
         }
     }
+
+    crate::test_for_all_curves!(test_dlog_proof_with_rng);
+    fn test_dlog_proof_with_rng<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Zeroize + PartialEq + std::fmt::Debug,
+    {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // the same seed gives the same witness and proof
+        let witness: P::Scalar = ECScalar::new_random_with_rng(&mut StdRng::from_seed([7; 32]));
+        let other: P::Scalar = ECScalar::new_random_with_rng(&mut StdRng::from_seed([7; 32]));
+        assert_eq!(witness, other);
+        let dlog_proof = DLogProof::<P>::prove_with_rng(&witness, &mut StdRng::from_seed([1; 32]));
+        let other = DLogProof::<P>::prove_with_rng(&witness, &mut StdRng::from_seed([1; 32]));
+        assert_eq!(dlog_proof, other);
+        assert!(DLogProof::verify(&dlog_proof).is_ok());
+        let other = DLogProof::<P>::prove_with_rng(&witness, &mut StdRng::from_seed([2; 32]));
+        assert_ne!(dlog_proof, other);
+    }
 }
@@ -11,6 +11,8 @@ use crate::elliptic::curves::traits::*;
 
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// This is implementation of Schnorr's identification protocol for elliptic curve groups or a
@@ -37,12 +39,21 @@ impl<P> DLogEqProof<P>
         P::Scalar: Zeroize,
 {
     pub fn prove(sk: &P::Scalar, g0: &P, g1: &P) -> DLogEqProof<P> {
+        Self::prove_with_rng(sk, g0, g1, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        sk: &P::Scalar,
+        g0: &P,
+        g1: &P,
+        rng: &mut R,
+    ) -> DLogEqProof<P> {
         //g_0 g_1 in the form of BigInt
         let generator_0 = g0.bytes_compressed_to_big_int();
         let generator_1 = g1.bytes_compressed_to_big_int();
 
         //u from Z_n
-        let mut sk_t_rand_commitment: P::Scalar = ECScalar::new_random();
+        let mut sk_t_rand_commitment: P::Scalar = ECScalar::new_random_with_rng(rng);
 
         //a_0=g_0^(u) a_1=g_1^(u)
         let pk_t_rand_commitment_0 = g0.scalar_mul(&sk_t_rand_commitment.get_element());
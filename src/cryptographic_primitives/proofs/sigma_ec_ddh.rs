@@ -9,6 +9,8 @@ use super::ProofError;
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::elliptic::curves::traits::*;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// This protocol is the elliptic curve form of the protocol from :
@@ -49,7 +51,15 @@ where
     P::Scalar: Zeroize + Clone,
 {
     pub fn prove(w: &ECDDHWitness<P::Scalar>, delta: &ECDDHStatement<P>) -> ECDDHProof<P> {
-        let mut s: P::Scalar = ECScalar::new_random();
+        Self::prove_with_rng(w, delta, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        w: &ECDDHWitness<P::Scalar>,
+        delta: &ECDDHStatement<P>,
+        rng: &mut R,
+    ) -> ECDDHProof<P> {
+        let mut s: P::Scalar = ECScalar::new_random_with_rng(rng);
         let a1 = delta.g1.clone() * s.clone();
         let a2 = delta.g2.clone() * s.clone();
         let e =
@@ -11,6 +11,9 @@ use crate::cryptographic_primitives::commitments::traits::Commitment;
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// protocol for proving that Pedersen commitment c was constructed correctly which is the same as
@@ -38,10 +41,18 @@ where
     P::Scalar: Zeroize,
 {
     pub fn prove(m: &P::Scalar, r: &P::Scalar) -> PedersenProof<P> {
+        Self::prove_with_rng(m, r, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        m: &P::Scalar,
+        r: &P::Scalar,
+        rng: &mut R,
+    ) -> PedersenProof<P> {
         let g: P = ECPoint::generator();
         let h: P = ECPoint::base_point2();
-        let mut s1: P::Scalar = ECScalar::new_random();
-        let mut s2: P::Scalar = ECScalar::new_random();
+        let mut s1: P::Scalar = ECScalar::new_random_with_rng(rng);
+        let mut s2: P::Scalar = ECScalar::new_random_with_rng(rng);
         let a1 = g.scalar_mul(&s1.get_element());
         let a2 = h.scalar_mul(&s2.get_element());
         let com: P = PedersenCommitment::create_commitment_with_user_defined_randomness(
@@ -88,13 +99,14 @@ where
         ]);
         let e: P::Scalar = ECScalar::from(&challenge);
 
-        let z1g = g.scalar_mul(&proof.z1.get_element());
-        let z2h = h.scalar_mul(&proof.z2.get_element());
-        let lhs = z1g.add_point(&z2h.get_element());
-        let rhs = proof.a1.add_point(&proof.a2.get_element());
-        let com_clone = proof.com.clone();
-        let ecom = com_clone.scalar_mul(&e.get_element());
-        let rhs = rhs.add_point(&ecom.get_element());
+        let one = || -> P::Scalar { ECScalar::from(&BigInt::one()) };
+        let z1: P::Scalar = ECScalar::from(&proof.z1.to_big_int());
+        let z2: P::Scalar = ECScalar::from(&proof.z2.to_big_int());
+        let lhs = P::multi_scalar_mul(&[g, h], &[z1, z2]);
+        let rhs = P::multi_scalar_mul(
+            &[proof.a1.clone(), proof.a2.clone(), proof.com.clone()],
+            &[one(), one(), e],
+        );
 
         if lhs == rhs {
             Ok(())
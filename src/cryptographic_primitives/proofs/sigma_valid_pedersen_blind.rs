@@ -12,6 +12,8 @@ use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::elliptic::curves::traits::*;
 
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
 /// protocol for proving that Pedersen commitment c was constructed correctly which is the same as
@@ -38,8 +40,16 @@ where
 {
     //TODO: add self verification to prover proof
     pub fn prove(m: &P::Scalar, r: &P::Scalar) -> PedersenBlindingProof<P> {
+        Self::prove_with_rng(m, r, &mut OsRng::new().unwrap())
+    }
+
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        m: &P::Scalar,
+        r: &P::Scalar,
+        rng: &mut R,
+    ) -> PedersenBlindingProof<P> {
         let h: P = ECPoint::base_point2();
-        let mut s: P::Scalar = ECScalar::new_random();
+        let mut s: P::Scalar = ECScalar::new_random_with_rng(rng);
         let a = h.scalar_mul(&s.get_element());
         let com: P = PedersenCommitment::create_commitment_with_user_defined_randomness(
             &m.to_big_int(),
@@ -8,7 +8,9 @@
 
 use crate::elliptic::curves::traits::*;
 use crate::BigInt;
-use crate::ErrorSS::{self, VerifyShareError};
+use crate::ErrorSS::{self, NotEnoughValidShares, VerifyShareError};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct ShamirSecretSharing {
@@ -42,9 +44,8 @@ where
         let index_vec: Vec<usize> = (1..=n).collect();
         let secret_shares = VerifiableSS::<P>::evaluate_polynomial(&poly, &index_vec);
 
-        let G: P = ECPoint::generator();
         let commitments = (0..poly.len())
-            .map(|i| G.clone() * poly[i].clone())
+            .map(|i| P::mul_base(&poly[i]))
             .collect::<Vec<P>>();
         (
             VerifiableSS {
@@ -69,9 +70,8 @@ where
         let poly = VerifiableSS::<P>::sample_polynomial(t, secret);
         let secret_shares = VerifiableSS::<P>::evaluate_polynomial(&poly, index_vec);
 
-        let G: P = ECPoint::generator();
         let commitments = (0..poly.len())
-            .map(|i| G.clone() * poly[i].clone())
+            .map(|i| P::mul_base(&poly[i]))
             .collect::<Vec<P>>();
         (
             VerifiableSS {
@@ -87,9 +87,18 @@ where
 
     // returns vector of coefficients
     pub fn sample_polynomial(t: usize, coef0: &P::Scalar) -> Vec<P::Scalar> {
+        Self::sample_polynomial_with_rng(t, coef0, &mut OsRng::new().unwrap())
+    }
+
+    pub fn sample_polynomial_with_rng<R: CryptoRng + RngCore>(
+        t: usize,
+        coef0: &P::Scalar,
+        rng: &mut R,
+    ) -> Vec<P::Scalar> {
         let mut coefficients = vec![coef0.clone()];
         // sample the remaining coefficients randomly using secure randomness
-        let random_coefficients: Vec<P::Scalar> = (0..t).map(|_| ECScalar::new_random()).collect();
+        let random_coefficients: Vec<P::Scalar> =
+            (0..t).map(|_| ECScalar::new_random_with_rng(rng)).collect();
         coefficients.extend(random_coefficients);
         // return
         coefficients
@@ -132,6 +141,37 @@ where
         VerifiableSS::<P>::lagrange_interpolation_at_zero(&points, &shares)
     }
 
+    // reconstructs the secret from the shares which match the commitments, and returns the
+    // indices of the parties whose share doesn't. A repeated index counts once, its other shares
+    // are reported as invalid. Fails if less than t + 1 distinct shares are valid.
+    pub fn reconstruct_robust(
+        &self,
+        indices: &[usize],
+        shares: &[P::Scalar],
+    ) -> Result<(P::Scalar, Vec<usize>), ErrorSS> {
+        assert_eq!(shares.len(), indices.len());
+        let points: Vec<usize> = indices.iter().map(|i| i + 1).collect();
+        let all_valid = self.validate_shares(shares, &points).is_ok();
+        let mut valid: Vec<usize> = Vec::new();
+        let mut invalid: Vec<usize> = Vec::new();
+        for j in 0..shares.len() {
+            // interpolating over a repeated point would invert zero
+            let repeated = valid.iter().any(|v| indices[*v] == indices[j]);
+            if !repeated && (all_valid || self.validate_share(&shares[j], points[j]).is_ok()) {
+                valid.push(j);
+            } else {
+                invalid.push(j);
+            }
+        }
+        if valid.len() < self.reconstruct_limit() {
+            return Err(NotEnoughValidShares);
+        }
+        let valid_indices: Vec<usize> = valid.iter().map(|j| indices[*j]).collect();
+        let valid_shares: Vec<P::Scalar> = valid.iter().map(|j| shares[*j].clone()).collect();
+        let secret = self.reconstruct(&valid_indices, &valid_shares);
+        Ok((secret, invalid.iter().map(|j| indices[*j]).collect()))
+    }
+
     // Performs a Lagrange interpolation in field Zp at the origin
     // for a polynomial defined by `points` and `values`.
     // `points` and `values` are expected to be two arrays of the same size, containing
@@ -173,15 +213,13 @@ where
                 num * denum * yi.clone()
             })
             .collect::<Vec<P::Scalar>>();
-        let mut lag_coef_iter = lag_coef.iter();
-        let head = lag_coef_iter.next().unwrap();
-        let tail = lag_coef_iter;
-        tail.fold(head.clone(), |acc, x| acc.add(&x.get_element()))
+        lag_coef.iter().fold(ECScalar::zero(), |acc: P::Scalar, x| {
+            acc.add(&x.get_element())
+        })
     }
 
     pub fn validate_share(&self, secret_share: &P::Scalar, index: usize) -> Result<(), ErrorSS> {
-        let G: P = ECPoint::generator();
-        let ss_point = G * secret_share.clone();
+        let ss_point = P::mul_base(secret_share);
         self.validate_share_public(&ss_point, index)
     }
 
@@ -195,14 +233,48 @@ where
     }
 
     pub fn get_point_commitment(&self, index: usize) -> P {
+        // sum_k commitments[k] * index^k
         let index_fe: P::Scalar = ECScalar::from(&BigInt::from(index as u32));
-        let mut comm_iterator = self.commitments.iter().rev();
-        let head = comm_iterator.next().unwrap();
-        let tail = comm_iterator;
-        let comm_to_point = tail.fold(head.clone(), |acc, x: &P| {
-            x.clone() + acc * index_fe.clone()
-        });
-        comm_to_point.clone()
+        let powers = VerifiableSS::<P>::powers(&index_fe, self.commitments.len());
+        P::multi_scalar_mul(&self.commitments, &powers)
+    }
+
+    // validates many shares at once: for random weights r_j we check
+    // G * sum_j r_j * s_j == sum_k commitments[k] * (sum_j r_j * index_j^k)
+    // which costs a single multi scalar multiplication instead of one per share.
+    pub fn validate_shares(
+        &self,
+        secret_shares: &[P::Scalar],
+        indices: &[usize],
+    ) -> Result<(), ErrorSS> {
+        if secret_shares.len() != indices.len() {
+            return Err(VerifyShareError);
+        }
+        let mut weighted_share: P::Scalar = ECScalar::zero();
+        let mut coefficients: Vec<P::Scalar> = (0..self.commitments.len())
+            .map(|_| ECScalar::zero())
+            .collect();
+        for (share, index) in secret_shares.iter().zip(indices.iter()) {
+            let r: P::Scalar = ECScalar::new_random();
+            weighted_share = weighted_share.add(&r.mul(&share.get_element()).get_element());
+            let index_fe: P::Scalar = ECScalar::from(&BigInt::from(*index as u32));
+            let powers = VerifiableSS::<P>::powers(&index_fe, self.commitments.len());
+            coefficients = coefficients
+                .iter()
+                .zip(powers.iter())
+                .map(|(c, power)| c.add(&r.mul(&power.get_element()).get_element()))
+                .collect();
+        }
+        if P::mul_base(&weighted_share) == P::multi_scalar_mul(&self.commitments, &coefficients) {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    // returns [1, x, x^2, ..., x^(n-1)]
+    pub(crate) fn powers(x: &P::Scalar, n: usize) -> Vec<P::Scalar> {
+        scalar_powers(x, n)
     }
 
     //compute \lambda_{index,S}, a lagrangian coefficient that change the (t,n) scheme to (|S|,|S|)
@@ -241,11 +313,193 @@ where
     }
 }
 
+impl ShamirSecretSharing {
+    // reconstructs the secret without commitments: decodes the shares as a Reed-Solomon codeword
+    // with the Berlekamp-Welch algorithm, which corrects up to (m - t - 1) / 2 wrong shares out
+    // of m. Returns the secret and the indices of the parties whose share is wrong.
+    // Fails when the shares are too far from any polynomial of degree t.
+    pub fn reconstruct_robust<S>(
+        &self,
+        indices: &[usize],
+        shares: &[S],
+    ) -> Result<(S, Vec<usize>), ErrorSS>
+    where
+        S: ECScalar + Clone + PartialEq,
+    {
+        assert_eq!(shares.len(), indices.len());
+        let t = self.threshold;
+        let m = shares.len();
+        if m <= t {
+            return Err(NotEnoughValidShares);
+        }
+        let points: Vec<S> = indices
+            .iter()
+            .map(|i| ECScalar::from(&BigInt::from(*i as u32 + 1)))
+            .collect();
+        let e = (m - t - 1) / 2;
+        let coefficients = berlekamp_welch(&points, shares, t, e).ok_or(NotEnoughValidShares)?;
+        let invalid: Vec<usize> = (0..m)
+            .filter(|j| evaluate(&coefficients, &points[*j]) != shares[*j])
+            .map(|j| indices[j])
+            .collect();
+        if invalid.len() > e {
+            return Err(NotEnoughValidShares);
+        }
+        Ok((coefficients[0].clone(), invalid))
+    }
+}
+
+// finds the error locator E (monic, of degree e) and Q (of degree e + t) such that
+// Q(x_i) = y_i * E(x_i) for all the points, and returns the coefficients of Q / E
+fn berlekamp_welch<S>(points: &[S], values: &[S], t: usize, e: usize) -> Option<Vec<S>>
+where
+    S: ECScalar + Clone + PartialEq,
+{
+    // unknowns: q_0, .., q_{e+t}, e_0, .., e_{e-1}
+    // equations: sum_k q_k x^k - y * sum_k e_k x^k = y * x^e
+    let q_len = e + t + 1;
+    let mut rows: Vec<Vec<S>> = points
+        .iter()
+        .zip(values.iter())
+        .map(|(x, y)| {
+            let powers = scalar_powers(x, q_len + 1);
+            let mut row: Vec<S> = powers[..q_len].to_vec();
+            row.extend(powers[..e].iter().map(|p| negate(&(y.clone() * p.clone()))));
+            row.push(y.clone() * powers[e].clone());
+            row
+        })
+        .collect();
+    let solution = solve(&mut rows, q_len + e)?;
+    let q = solution[..q_len].to_vec();
+    let mut locator = solution[q_len..].to_vec();
+    locator.push(ECScalar::from(&BigInt::one()));
+    divide(&q, &locator)
+}
+
+// returns [1, x, x^2, ..., x^(n-1)]
+fn scalar_powers<S: ECScalar + Clone>(x: &S, n: usize) -> Vec<S> {
+    let mut powers: Vec<S> = Vec::with_capacity(n);
+    let mut power: S = ECScalar::from(&BigInt::one());
+    for _ in 0..n {
+        let next = power.clone() * x.clone();
+        powers.push(power);
+        power = next;
+    }
+    powers
+}
+
+fn negate<S: ECScalar>(x: &S) -> S {
+    let zero: S = ECScalar::zero();
+    ECScalar::sub(&zero, &x.get_element())
+}
+
+// Horner's rule
+fn evaluate<S: ECScalar + Clone>(coefficients: &[S], x: &S) -> S {
+    coefficients
+        .iter()
+        .rev()
+        .fold(ECScalar::zero(), |acc: S, c| acc * x.clone() + c.clone())
+}
+
+// Gauss-Jordan elimination of the augmented matrix rows, the free unknowns are set to zero
+fn solve<S>(rows: &mut [Vec<S>], unknowns: usize) -> Option<Vec<S>>
+where
+    S: ECScalar + Clone + PartialEq,
+{
+    let zero: S = ECScalar::zero();
+    let mut pivots = Vec::new();
+    let mut r = 0;
+    for c in 0..unknowns {
+        let pivot = match (r..rows.len()).find(|i| rows[*i][c] != zero) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        rows.swap(r, pivot);
+        let inv = rows[r][c].invert();
+        rows[r] = rows[r].iter().map(|a| a.clone() * inv.clone()).collect();
+        for i in 0..rows.len() {
+            if i != r && rows[i][c] != zero {
+                let factor = rows[i][c].clone();
+                rows[i] = rows[i]
+                    .iter()
+                    .zip(rows[r].iter())
+                    .map(|(a, b)| ECScalar::sub(a, &(b.clone() * factor.clone()).get_element()))
+                    .collect();
+            }
+        }
+        pivots.push(c);
+        r += 1;
+    }
+    // inconsistent system: 0 = b with b != 0
+    if rows[r..].iter().any(|row| row[unknowns] != zero) {
+        return None;
+    }
+    let mut solution = vec![zero; unknowns];
+    for (i, c) in pivots.into_iter().enumerate() {
+        solution[c] = rows[i][unknowns].clone();
+    }
+    Some(solution)
+}
+
+// divides by a monic polynomial, returns None if the remainder is not zero
+fn divide<S>(numerator: &[S], denominator: &[S]) -> Option<Vec<S>>
+where
+    S: ECScalar + Clone + PartialEq,
+{
+    let zero: S = ECScalar::zero();
+    let d = denominator.len() - 1;
+    if numerator.len() <= d {
+        return None;
+    }
+    let mut remainder = numerator.to_vec();
+    let mut quotient = vec![zero.clone(); numerator.len() - d];
+    for k in (0..quotient.len()).rev() {
+        let q = remainder[k + d].clone();
+        for (j, b) in denominator.iter().enumerate() {
+            remainder[k + j] =
+                ECScalar::sub(&remainder[k + j], &(q.clone() * b.clone()).get_element());
+        }
+        quotient[k] = q;
+    }
+    if remainder.iter().any(|a| *a != zero) {
+        return None;
+    }
+    Some(quotient)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_for_all_curves;
 
+    test_for_all_curves!(test_sample_polynomial_with_rng);
+
+    fn test_sample_polynomial_with_rng<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let secret: P::Scalar = ECScalar::new_random();
+        let poly = VerifiableSS::<P>::sample_polynomial_with_rng(
+            3,
+            &secret,
+            &mut StdRng::from_seed([9; 32]),
+        );
+        assert_eq!(poly.len(), 4);
+        assert_eq!(poly[0], secret);
+        assert_eq!(
+            VerifiableSS::<P>::sample_polynomial_with_rng(
+                3,
+                &secret,
+                &mut StdRng::from_seed([9; 32])
+            ),
+            poly
+        );
+    }
+
     test_for_all_curves!(test_secret_sharing_3_out_of_5_at_indices);
 
     fn test_secret_sharing_3_out_of_5_at_indices<P>()
@@ -295,6 +549,12 @@ mod tests {
         let valid1 = vss_scheme.validate_share(&secret_shares[0], 1);
         assert!(valid3.is_ok());
         assert!(valid1.is_ok());
+        let valid_all = vss_scheme.validate_shares(&secret_shares, &[1, 2, 3, 4, 5]);
+        assert!(valid_all.is_ok());
+        let invalid = vss_scheme.validate_shares(&secret_shares, &[1, 2, 3, 5, 4]);
+        assert!(invalid.is_err());
+        let mismatched = vss_scheme.validate_shares(&secret_shares, &[1, 2, 3, 4]);
+        assert!(mismatched.is_err());
 
         let g: P = ECPoint::generator();
         let share1_public = g * secret_shares[0].clone();
@@ -438,4 +698,92 @@ mod tests {
         let w = l0 * secret_shares[0].clone() + l2 * secret_shares[2].clone();
         assert_eq!(w, secret_reconstructed);
     }
+
+    test_for_all_curves!(test_reconstruct_robust);
+
+    fn test_reconstruct_robust<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let (vss_scheme, mut secret_shares) = VerifiableSS::<P>::share(3, 7, &secret);
+        let indices: Vec<usize> = (0..7).collect();
+
+        let (reconstructed, invalid) = vss_scheme
+            .reconstruct_robust(&indices, &secret_shares)
+            .unwrap();
+        assert_eq!(reconstructed, secret);
+        assert!(invalid.is_empty());
+
+        // corrupted shares are filtered against the commitments
+        secret_shares[1] = ECScalar::new_random();
+        secret_shares[5] = ECScalar::new_random();
+        let (reconstructed, invalid) = vss_scheme
+            .reconstruct_robust(&indices, &secret_shares)
+            .unwrap();
+        assert_eq!(reconstructed, secret);
+        assert_eq!(invalid, vec![1, 5]);
+
+        // a repeated share only counts once
+        let repeated: Vec<usize> = vec![0, 2, 2, 3, 4];
+        let shares: Vec<P::Scalar> = repeated.iter().map(|i| secret_shares[*i].clone()).collect();
+        let (reconstructed, invalid) = vss_scheme.reconstruct_robust(&repeated, &shares).unwrap();
+        assert_eq!(reconstructed, secret);
+        assert_eq!(invalid, vec![2]);
+        assert_eq!(
+            vss_scheme.reconstruct_robust(&repeated[..4], &shares[..4]),
+            Err(NotEnoughValidShares)
+        );
+
+        secret_shares[0] = ECScalar::new_random();
+        secret_shares[6] = ECScalar::new_random();
+        assert_eq!(
+            vss_scheme.reconstruct_robust(&indices, &secret_shares),
+            Err(NotEnoughValidShares)
+        );
+    }
+
+    test_for_all_curves!(test_reconstruct_robust_without_commitments);
+
+    fn test_reconstruct_robust_without_commitments<P>()
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let (vss_scheme, secret_shares) = VerifiableSS::<P>::share(2, 10, &secret);
+        let parameters = &vss_scheme.parameters;
+        // 9 shares correct up to (9 - 2 - 1) / 2 = 3 errors
+        let indices = vec![0, 1, 2, 4, 5, 6, 7, 8, 9];
+        let mut shares: Vec<P::Scalar> =
+            indices.iter().map(|i| secret_shares[*i].clone()).collect();
+
+        let (reconstructed, invalid) = parameters.reconstruct_robust(&indices, &shares).unwrap();
+        assert_eq!(reconstructed, secret);
+        assert!(invalid.is_empty());
+
+        for (count, j) in [0, 4, 8].iter().enumerate() {
+            shares[*j] = ECScalar::new_random();
+            let (reconstructed, invalid) =
+                parameters.reconstruct_robust(&indices, &shares).unwrap();
+            assert_eq!(reconstructed, secret);
+            assert_eq!(invalid.len(), count + 1);
+        }
+        let (_, invalid) = parameters.reconstruct_robust(&indices, &shares).unwrap();
+        assert_eq!(invalid, vec![0, 5, 9]);
+
+        // one error too many
+        shares[2] = ECScalar::new_random();
+        assert_eq!(
+            parameters.reconstruct_robust(&indices, &shares),
+            Err(NotEnoughValidShares)
+        );
+        // t + 1 shares can't correct anything but still interpolate
+        let (reconstructed, invalid) = parameters
+            .reconstruct_robust(&indices[5..8], &shares[5..8])
+            .unwrap();
+        assert_eq!(reconstructed, secret);
+        assert!(invalid.is_empty());
+    }
 }
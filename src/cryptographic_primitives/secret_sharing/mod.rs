@@ -6,3 +6,6 @@
 */
 
 pub mod feldman_vss;
+pub mod pedersen_vss;
+pub mod pvss;
+pub mod repair;
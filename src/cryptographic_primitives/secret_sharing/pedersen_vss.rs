@@ -0,0 +1,274 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use super::feldman_vss::{ShamirSecretSharing, VerifiableSS};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use crate::ErrorSS::{self, VerifyShareError};
+
+/// Pedersen VSS, based on Torben Pryds Pedersen. 1991. Non-interactive and information-theoretic
+/// secure verifiable secret sharing. In CRYPTO 1991, Springer (LNCS 576), pages 129–140.
+///
+/// The dealer samples a second, blinding, polynomial b and commits to every coefficient with
+/// G * a_k + H * b_k where H = base_point2, so unlike VerifiableSS the commitments reveal
+/// nothing about the secret (not even G * secret). Each party gets a share f(i) and a blinding
+/// share b(i), and needs both to validate its share. Indices are handled as in VerifiableSS.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PedersenVSS<P> {
+    pub parameters: ShamirSecretSharing,
+    pub commitments: Vec<P>,
+}
+
+impl<P> PedersenVSS<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    pub fn reconstruct_limit(&self) -> usize {
+        self.parameters.threshold + 1
+    }
+
+    // generate PedersenVSS from a secret, returns the shares and the blinding shares
+    pub fn share(
+        t: usize,
+        n: usize,
+        secret: &P::Scalar,
+    ) -> (PedersenVSS<P>, Vec<P::Scalar>, Vec<P::Scalar>) {
+        let index_vec: Vec<usize> = (1..=n).collect();
+        PedersenVSS::<P>::share_at_indices(t, n, secret, &index_vec)
+    }
+
+    // generate PedersenVSS from a secret and user defined x values
+    pub fn share_at_indices(
+        t: usize,
+        n: usize,
+        secret: &P::Scalar,
+        index_vec: &[usize],
+    ) -> (PedersenVSS<P>, Vec<P::Scalar>, Vec<P::Scalar>) {
+        assert!(t < n);
+        assert_eq!(n, index_vec.len());
+        let poly = VerifiableSS::<P>::sample_polynomial(t, secret);
+        let blinding_poly = VerifiableSS::<P>::sample_polynomial(t, &ECScalar::new_random());
+        let secret_shares = VerifiableSS::<P>::evaluate_polynomial(&poly, index_vec);
+        let blinding_shares = VerifiableSS::<P>::evaluate_polynomial(&blinding_poly, index_vec);
+
+        let commitments = (0..poly.len())
+            .map(|i| P::mul_base(&poly[i]) + P::mul_base_point2(&blinding_poly[i]))
+            .collect::<Vec<P>>();
+        (
+            PedersenVSS {
+                parameters: ShamirSecretSharing {
+                    threshold: t,
+                    share_count: n,
+                },
+                commitments,
+            },
+            secret_shares,
+            blinding_shares,
+        )
+    }
+
+    pub fn reconstruct(&self, indices: &[usize], shares: &[P::Scalar]) -> P::Scalar {
+        assert_eq!(shares.len(), indices.len());
+        assert!(shares.len() >= self.reconstruct_limit());
+        // add one to indices to get points
+        let points = indices
+            .iter()
+            .map(|i| {
+                let index_bn = BigInt::from(*i as u32 + 1);
+                ECScalar::from(&index_bn)
+            })
+            .collect::<Vec<P::Scalar>>();
+        VerifiableSS::<P>::lagrange_interpolation_at_zero(&points, shares)
+    }
+
+    pub fn validate_share(
+        &self,
+        secret_share: &P::Scalar,
+        blinding_share: &P::Scalar,
+        index: usize,
+    ) -> Result<(), ErrorSS> {
+        let ss_point = P::mul_base(secret_share) + P::mul_base_point2(blinding_share);
+        self.validate_share_public(&ss_point, index)
+    }
+
+    // ss_point = G * secret_share + H * blinding_share
+    pub fn validate_share_public(&self, ss_point: &P, index: usize) -> Result<(), ErrorSS> {
+        let comm_to_point = self.get_point_commitment(index);
+        if *ss_point == comm_to_point {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    // checks an opening of the secret, e.g. after the secret and the blinding were both
+    // reconstructed
+    pub fn validate_secret(&self, secret: &P::Scalar, blinding: &P::Scalar) -> Result<(), ErrorSS> {
+        if P::mul_base(secret) + P::mul_base_point2(blinding) == self.commitments[0] {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    pub fn get_point_commitment(&self, index: usize) -> P {
+        // sum_k commitments[k] * index^k
+        let index_fe: P::Scalar = ECScalar::from(&BigInt::from(index as u32));
+        let powers = VerifiableSS::<P>::powers(&index_fe, self.commitments.len());
+        P::multi_scalar_mul(&self.commitments, &powers)
+    }
+
+    // validates many shares at once with random weights r_j, like VerifiableSS::validate_shares:
+    // G * sum_j r_j * s_j + H * sum_j r_j * b_j == sum_k commitments[k] * (sum_j r_j * index_j^k)
+    pub fn validate_shares(
+        &self,
+        secret_shares: &[P::Scalar],
+        blinding_shares: &[P::Scalar],
+        indices: &[usize],
+    ) -> Result<(), ErrorSS> {
+        if secret_shares.len() != indices.len() || blinding_shares.len() != indices.len() {
+            return Err(VerifyShareError);
+        }
+        let mut weighted_share: P::Scalar = ECScalar::zero();
+        let mut weighted_blinding: P::Scalar = ECScalar::zero();
+        let mut coefficients: Vec<P::Scalar> = (0..self.commitments.len())
+            .map(|_| ECScalar::zero())
+            .collect();
+        for ((share, blinding), index) in secret_shares
+            .iter()
+            .zip(blinding_shares.iter())
+            .zip(indices.iter())
+        {
+            let r: P::Scalar = ECScalar::new_random();
+            weighted_share = weighted_share.add(&r.mul(&share.get_element()).get_element());
+            weighted_blinding =
+                weighted_blinding.add(&r.mul(&blinding.get_element()).get_element());
+            let index_fe: P::Scalar = ECScalar::from(&BigInt::from(*index as u32));
+            let powers = VerifiableSS::<P>::powers(&index_fe, self.commitments.len());
+            coefficients = coefficients
+                .iter()
+                .zip(powers.iter())
+                .map(|(c, power)| c.add(&r.mul(&power.get_element()).get_element()))
+                .collect();
+        }
+        if P::mul_base(&weighted_share) + P::mul_base_point2(&weighted_blinding)
+            == P::multi_scalar_mul(&self.commitments, &coefficients)
+        {
+            Ok(())
+        } else {
+            Err(VerifyShareError)
+        }
+    }
+
+    //compute \lambda_{index,S}, a lagrangian coefficient that change the (t,n) scheme to (|S|,|S|)
+    pub fn map_share_to_new_params(&self, index: usize, s: &[usize]) -> P::Scalar {
+        VerifiableSS::<P> {
+            parameters: self.parameters.clone(),
+            commitments: Vec::new(),
+        }
+        .map_share_to_new_params(index, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(test_pedersen_secret_sharing_3_out_of_5);
+
+    fn test_pedersen_secret_sharing_3_out_of_5<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+
+        let (vss_scheme, secret_shares, blinding_shares) = PedersenVSS::<P>::share(3, 5, &secret);
+
+        // the commitments hide the secret
+        assert_ne!(vss_scheme.commitments[0], P::mul_base(&secret));
+
+        //test reconstruction
+        let indices = vec![0, 1, 2, 4];
+        let shares_vec = indices
+            .iter()
+            .map(|i| secret_shares[*i].clone())
+            .collect::<Vec<_>>();
+        let blindings_vec = indices
+            .iter()
+            .map(|i| blinding_shares[*i].clone())
+            .collect::<Vec<_>>();
+        let secret_reconstructed = vss_scheme.reconstruct(&indices, &shares_vec);
+        assert_eq!(secret, secret_reconstructed);
+        let blinding_reconstructed = vss_scheme.reconstruct(&indices, &blindings_vec);
+        assert!(vss_scheme
+            .validate_secret(&secret_reconstructed, &blinding_reconstructed)
+            .is_ok());
+        assert!(vss_scheme
+            .validate_secret(&ECScalar::new_random(), &blinding_reconstructed)
+            .is_err());
+
+        // test secret shares are verifiable
+        for i in 0..5 {
+            assert!(vss_scheme
+                .validate_share(&secret_shares[i], &blinding_shares[i], i + 1)
+                .is_ok());
+        }
+        assert!(vss_scheme
+            .validate_share(&secret_shares[0], &blinding_shares[1], 1)
+            .is_err());
+        assert!(vss_scheme
+            .validate_share(&secret_shares[0], &blinding_shares[0], 2)
+            .is_err());
+        let valid_all =
+            vss_scheme.validate_shares(&secret_shares, &blinding_shares, &[1, 2, 3, 4, 5]);
+        assert!(valid_all.is_ok());
+        let invalid =
+            vss_scheme.validate_shares(&secret_shares, &blinding_shares, &[1, 2, 3, 5, 4]);
+        assert!(invalid.is_err());
+        let mismatched =
+            vss_scheme.validate_shares(&secret_shares, &blinding_shares[..4], &[1, 2, 3, 4, 5]);
+        assert!(mismatched.is_err());
+
+        let share1_public =
+            P::mul_base(&secret_shares[0]) + P::mul_base_point2(&blinding_shares[0]);
+        assert!(vss_scheme.validate_share_public(&share1_public, 1).is_ok());
+
+        // test map (t,n) - (t',t')
+        let s = &[0, 1, 2, 3, 4];
+        let w = s.iter().fold(ECScalar::zero(), |acc: P::Scalar, i| {
+            acc + vss_scheme.map_share_to_new_params(*i, s) * secret_shares[*i].clone()
+        });
+        assert_eq!(w, secret_reconstructed);
+    }
+
+    test_for_all_curves!(test_pedersen_secret_sharing_at_indices);
+
+    fn test_pedersen_secret_sharing_at_indices<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let parties = [1, 2, 4, 5, 6];
+        let (vss_scheme, secret_shares, blinding_shares) =
+            PedersenVSS::<P>::share_at_indices(1, 5, &secret, &parties);
+
+        for (i, index) in parties.iter().enumerate() {
+            assert!(vss_scheme
+                .validate_share(&secret_shares[i], &blinding_shares[i], *index)
+                .is_ok());
+        }
+        let secret_reconstructed = vss_scheme.reconstruct(
+            &[3, 5],
+            &[secret_shares[2].clone(), secret_shares[4].clone()],
+        );
+        assert_eq!(secret, secret_reconstructed);
+    }
+}
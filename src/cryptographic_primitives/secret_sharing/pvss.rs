@@ -0,0 +1,248 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+use super::feldman_vss::{ShamirSecretSharing, VerifiableSS};
+use crate::cryptographic_primitives::proofs::sigma_dlog_eq::DLogEqProof;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use crate::ErrorSS::{self, NotEnoughValidShares, VerifyShareError};
+
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use zeroize::Zeroize;
+
+/// Publicly verifiable secret sharing, based on Berry Schoenmakers. 1999. A Simple Publicly
+/// Verifiable Secret Sharing Scheme and its Application to Electronic Voting. In CRYPTO 1999,
+/// Springer (LNCS 1666), pages 148–164.
+///
+/// The polynomial is committed with H = base_point2: C_j = H * a_j, and party i (0-based) with
+/// key pair (x_i, y_i = G * x_i) gets the encrypted share Y_i = y_i * f(i + 1) along with a
+/// DLogEqProof that log_H(X_i) = log_{y_i}(Y_i) where X_i = sum_j C_j * (i + 1)^j. Anyone can
+/// thus check the dealing. Party i decrypts S_i = Y_i * x_i^-1 = G * f(i + 1) and proves with a
+/// DLogEqProof that log_G(y_i) = log_{S_i}(Y_i). The secret G * f(0) is reconstructed in the
+/// exponent from t + 1 decrypted shares.
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct PubliclyVerifiableSS<P: ECPoint> {
+    pub parameters: ShamirSecretSharing,
+    pub commitments: Vec<P>,
+    pub encrypted_shares: Vec<P>,
+    pub proofs: Vec<DLogEqProof<P>>,
+}
+
+#[derive(Serialize, Deserialize, Derivative)]
+#[derivative(Clone(bound = "P: Clone, P::Scalar: Clone"))]
+#[derivative(PartialEq(bound = "P: PartialEq, P::Scalar: PartialEq"))]
+#[derivative(Debug(bound = "P: Debug, P::Scalar: Debug"))]
+#[serde(bound(serialize = "P: Serialize, P::Scalar: Serialize"))]
+#[serde(bound(deserialize = "P: Deserialize<'de>, P::Scalar: Deserialize<'de>"))]
+pub struct DecryptedShare<P: ECPoint> {
+    pub index: usize,
+    pub share: P,
+    pub proof: DLogEqProof<P>,
+}
+
+impl<P> PubliclyVerifiableSS<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone + Zeroize,
+{
+    pub fn reconstruct_limit(&self) -> usize {
+        self.parameters.threshold + 1
+    }
+
+    // shares G * secret among the owners of the public keys pks
+    pub fn share(t: usize, secret: &P::Scalar, pks: &[P]) -> PubliclyVerifiableSS<P> {
+        let n = pks.len();
+        assert!(t < n);
+        let poly = VerifiableSS::<P>::sample_polynomial(t, secret);
+        let index_vec: Vec<usize> = (1..=n).collect();
+        let secret_shares = VerifiableSS::<P>::evaluate_polynomial(&poly, &index_vec);
+        let h = P::base_point2();
+        let commitments = poly.iter().map(P::mul_base_point2).collect::<Vec<P>>();
+        let proofs = secret_shares
+            .iter()
+            .zip(pks.iter())
+            .map(|(share, pk)| DLogEqProof::prove(share, &h, pk))
+            .collect::<Vec<_>>();
+        PubliclyVerifiableSS {
+            parameters: ShamirSecretSharing {
+                threshold: t,
+                share_count: n,
+            },
+            commitments,
+            encrypted_shares: proofs.iter().map(|proof| proof.pk.1.clone()).collect(),
+            proofs,
+        }
+    }
+
+    // can be run by anyone who knows the public keys of the participants
+    pub fn validate(&self, pks: &[P]) -> Result<(), ErrorSS> {
+        let n = self.parameters.share_count;
+        if pks.len() != n
+            || self.encrypted_shares.len() != n
+            || self.proofs.len() != n
+            || self.commitments.len() != self.reconstruct_limit()
+        {
+            return Err(VerifyShareError);
+        }
+        let h = P::base_point2();
+        for (i, (proof, pk)) in self.proofs.iter().zip(pks.iter()).enumerate() {
+            if proof.generator != (h.clone(), pk.clone())
+                || proof.pk
+                    != (
+                        self.get_point_commitment(i + 1),
+                        self.encrypted_shares[i].clone(),
+                    )
+                || DLogEqProof::verify(proof).is_err()
+            {
+                return Err(VerifyShareError);
+            }
+        }
+        Ok(())
+    }
+
+    // X_index = sum_j commitments[j] * index^j = H * f(index)
+    pub fn get_point_commitment(&self, index: usize) -> P {
+        let index_fe: P::Scalar = ECScalar::from(&BigInt::from(index as u32));
+        let powers = VerifiableSS::<P>::powers(&index_fe, self.commitments.len());
+        P::multi_scalar_mul(&self.commitments, &powers)
+    }
+
+    // run by party index with its secret key
+    pub fn decrypt_share(
+        &self,
+        index: usize,
+        sk: &P::Scalar,
+    ) -> Result<DecryptedShare<P>, ErrorSS> {
+        if index >= self.encrypted_shares.len() {
+            return Err(VerifyShareError);
+        }
+        let share = self.encrypted_shares[index].clone() * sk.invert();
+        let proof = DLogEqProof::prove(sk, &P::generator(), &share);
+        Ok(DecryptedShare {
+            index,
+            share,
+            proof,
+        })
+    }
+
+    pub fn validate_decrypted_share(
+        &self,
+        decrypted: &DecryptedShare<P>,
+        pk: &P,
+    ) -> Result<(), ErrorSS> {
+        if decrypted.index >= self.encrypted_shares.len()
+            || decrypted.proof.generator != (P::generator(), decrypted.share.clone())
+            || decrypted.proof.pk != (pk.clone(), self.encrypted_shares[decrypted.index].clone())
+            || DLogEqProof::verify(&decrypted.proof).is_err()
+        {
+            return Err(VerifyShareError);
+        }
+        Ok(())
+    }
+
+    // interpolates G * secret from the decrypted shares which are proven correct
+    pub fn reconstruct(&self, decrypted: &[DecryptedShare<P>], pks: &[P]) -> Result<P, ErrorSS> {
+        let mut valid = Vec::new();
+        for share in decrypted {
+            if valid.len() < self.reconstruct_limit()
+                && share.index < pks.len()
+                && !valid
+                    .iter()
+                    .any(|s: &&DecryptedShare<P>| s.index == share.index)
+                && self
+                    .validate_decrypted_share(share, &pks[share.index])
+                    .is_ok()
+            {
+                valid.push(share);
+            }
+        }
+        if valid.len() < self.reconstruct_limit() {
+            return Err(NotEnoughValidShares);
+        }
+        let indices = valid.iter().map(|s| s.index).collect::<Vec<_>>();
+        let vss_scheme = VerifiableSS::<P> {
+            parameters: self.parameters.clone(),
+            commitments: Vec::new(),
+        };
+        let points = valid.iter().map(|s| s.share.clone()).collect::<Vec<_>>();
+        let lambdas = indices
+            .iter()
+            .map(|i| vss_scheme.map_share_to_new_params(*i, &indices))
+            .collect::<Vec<_>>();
+        Ok(P::multi_scalar_mul(&points, &lambdas))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+
+    test_for_all_curves!(test_pvss_2_out_of_5);
+
+    fn test_pvss_2_out_of_5<P>()
+    where
+        P: ECPoint + Clone + PartialEq + std::fmt::Debug,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug + Zeroize,
+    {
+        let sks = (0..5)
+            .map(|_| ECScalar::new_random())
+            .collect::<Vec<P::Scalar>>();
+        let pks = sks.iter().map(P::mul_base).collect::<Vec<P>>();
+        let secret: P::Scalar = ECScalar::new_random();
+        let pvss = PubliclyVerifiableSS::<P>::share(2, &secret, &pks);
+        assert!(pvss.validate(&pks).is_ok());
+
+        // a tampered encrypted share or the keys of other parties are detected
+        let mut tampered = pvss.clone();
+        tampered.encrypted_shares[3] = P::mul_base(&ECScalar::new_random());
+        assert!(tampered.validate(&pks).is_err());
+        let mut other_pks = pks.clone();
+        other_pks.swap(0, 1);
+        assert!(pvss.validate(&other_pks).is_err());
+
+        let decrypted = (0..5)
+            .map(|i| pvss.decrypt_share(i, &sks[i]).unwrap())
+            .collect::<Vec<_>>();
+        for share in decrypted.iter() {
+            assert!(pvss
+                .validate_decrypted_share(share, &pks[share.index])
+                .is_ok());
+        }
+        let expected = P::mul_base(&secret);
+        assert_eq!(pvss.reconstruct(&decrypted[1..4], &pks).unwrap(), expected);
+
+        // a wrong decryption is skipped
+        let wrong = pvss.decrypt_share(0, &sks[1]).unwrap();
+        assert!(pvss.validate_decrypted_share(&wrong, &pks[0]).is_err());
+        assert!(pvss.decrypt_share(5, &sks[0]).is_err());
+        // share_count doesn't bound the index of a dealing with missing shares
+        let mut truncated = pvss.clone();
+        truncated.encrypted_shares.truncate(3);
+        assert!(truncated
+            .validate_decrypted_share(&decrypted[4], &pks[4])
+            .is_err());
+        let shares = vec![
+            wrong,
+            decrypted[2].clone(),
+            decrypted[4].clone(),
+            decrypted[3].clone(),
+        ];
+        assert_eq!(pvss.reconstruct(&shares, &pks).unwrap(), expected);
+        assert_eq!(
+            pvss.reconstruct(&shares[..3], &pks),
+            Err(NotEnoughValidShares)
+        );
+    }
+}
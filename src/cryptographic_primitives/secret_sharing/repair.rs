@@ -0,0 +1,165 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+//! Share repair (enrollment) of a lost share of a VerifiableSS, based on Laing and Stinson. 2017.
+//! A Survey and Refinement of Repairable Threshold Schemes. Journal of Mathematical Cryptology
+//! 12(1), pages 57–81.
+//!
+//! A set S of t + 1 or more helpers (0-based indices as in map_share_to_new_params) repairs the
+//! share of party `lost`, which may also be a new index. Helper j computes its share weighted
+//! with the Lagrange coefficient of S at x = lost + 1, splits it into |S| random summands and
+//! sends the k-th one to the k-th helper of S (repair_sub_shares). Every helper sums what it
+//! received and sends the sum to the lost party (repair_sum), which adds the sums up and
+//! validates the result with VerifiableSS::validate_share (repair_share). The sub-shares are
+//! uniformly random, so no helper learns anything about the repaired share.
+//!
+//! Wrong sub-shares of a helper are only detected by the lost party, which can retry with
+//! another set of helpers.
+
+use super::feldman_vss::VerifiableSS;
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+use crate::ErrorSS;
+
+// run by helper with index `helper`, returns the sub-shares for the helpers in the order of
+// `helpers`
+pub fn repair_sub_shares<P>(
+    vss_scheme: &VerifiableSS<P>,
+    helper: usize,
+    share: &P::Scalar,
+    helpers: &[usize],
+    lost: usize,
+) -> Vec<P::Scalar>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    assert!(helpers.len() >= vss_scheme.reconstruct_limit());
+    assert!(helpers.contains(&helper));
+    assert!(!helpers.contains(&lost));
+    assert!(helpers
+        .iter()
+        .enumerate()
+        .all(|(k, i)| !helpers[..k].contains(i)));
+    let delta = lagrange_coefficient_at::<P>(helper, helpers, lost) * share.clone();
+    let mut sub_shares = (1..helpers.len())
+        .map(|_| ECScalar::new_random())
+        .collect::<Vec<P::Scalar>>();
+    let last = sub_shares
+        .iter()
+        .fold(delta, |acc, mask| ECScalar::sub(&acc, &mask.get_element()));
+    sub_shares.push(last);
+    sub_shares
+}
+
+// run by every helper on the sub-shares it received from all helpers
+pub fn repair_sum<P>(sub_shares: &[P::Scalar]) -> P::Scalar
+where
+    P: ECPoint,
+    P::Scalar: Clone,
+{
+    sub_shares
+        .iter()
+        .fold(ECScalar::zero(), |acc: P::Scalar, x| acc + x.clone())
+}
+
+// run by the lost party on the sums of all helpers
+pub fn repair_share<P>(
+    vss_scheme: &VerifiableSS<P>,
+    lost: usize,
+    sums: &[P::Scalar],
+) -> Result<P::Scalar, ErrorSS>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    assert!(sums.len() >= vss_scheme.reconstruct_limit());
+    let share = repair_sum::<P>(sums);
+    vss_scheme.validate_share(&share, lost + 1)?;
+    Ok(share)
+}
+
+// prod_{k in S, k != index} (x - x_k) / (x_index - x_k) where x_k = k + 1 and x = at + 1
+fn lagrange_coefficient_at<P>(index: usize, s: &[usize], at: usize) -> P::Scalar
+where
+    P: ECPoint,
+    P::Scalar: Clone,
+{
+    let point = |i: usize| -> P::Scalar { ECScalar::from(&BigInt::from(i as u32 + 1)) };
+    let x = point(at);
+    let xi = point(index);
+    let (num, denum) = s.iter().filter(|k| **k != index).fold(
+        (
+            ECScalar::from(&BigInt::one()),
+            ECScalar::from(&BigInt::one()),
+        ),
+        |(num, denum): (P::Scalar, P::Scalar), k| {
+            let xk = point(*k);
+            (
+                num * ECScalar::sub(&x, &xk.get_element()),
+                denum * ECScalar::sub(&xi, &xk.get_element()),
+            )
+        },
+    );
+    num * denum.invert()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_for_all_curves;
+    use crate::ErrorSS::VerifyShareError;
+
+    test_for_all_curves!(test_repair_share);
+
+    fn test_repair_share<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let (vss_scheme, secret_shares) = VerifiableSS::<P>::share(2, 6, &secret);
+
+        let lost = 4;
+        let helpers = [0, 2, 5];
+        let sub_shares = helpers
+            .iter()
+            .map(|j| repair_sub_shares(&vss_scheme, *j, &secret_shares[*j], &helpers, lost))
+            .collect::<Vec<_>>();
+        let sums = (0..helpers.len())
+            .map(|k| {
+                let received = sub_shares.iter().map(|s| s[k].clone()).collect::<Vec<_>>();
+                repair_sum::<P>(&received)
+            })
+            .collect::<Vec<_>>();
+        let repaired = repair_share(&vss_scheme, lost, &sums).unwrap();
+        assert_eq!(repaired, secret_shares[lost]);
+
+        // a wrong sum is detected by the lost party
+        let mut wrong_sums = sums.clone();
+        wrong_sums[1] = ECScalar::new_random();
+        assert_eq!(
+            repair_share(&vss_scheme, lost, &wrong_sums),
+            Err(VerifyShareError)
+        );
+    }
+
+    test_for_all_curves!(
+        #[should_panic]
+        test_repair_share_not_enough_helpers
+    );
+
+    fn test_repair_share_not_enough_helpers<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let secret: P::Scalar = ECScalar::new_random();
+        let (vss_scheme, secret_shares) = VerifiableSS::<P>::share(2, 6, &secret);
+        repair_sub_shares(&vss_scheme, 0, &secret_shares[0], &[0, 1], 4);
+    }
+}
@@ -0,0 +1,618 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use super::SignatureError;
+
+use crate::arithmetic::traits::{Converter, Modulo, NumberTests};
+use crate::elliptic::curves::secp256_k1::{Secp256k1Point, Secp256k1Scalar, FIELD_PRIME};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use serde::de;
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+
+/// Schnorr signatures for secp256k1 as specified in BIP-340:
+/// P. Wuille, J. Nick and T. Ruffing. Schnorr Signatures for secp256k1.
+/// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+///
+/// Public keys are x coordinates only, the point is the one with an even y coordinate. Signing
+/// negates the secret key and the nonce when needed so that the public key and R have even y.
+/// The Taproot tweak of BIP-341 is provided by XOnlyPublicKey::tap_tweak and
+/// KeyPair::tap_tweak.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub struct XOnlyPublicKey {
+    point: Secp256k1Point,
+}
+
+pub struct KeyPair {
+    // the secret key of the point with even y
+    sk: Secp256k1Scalar,
+    pub pk: XOnlyPublicKey,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Bip340Signature {
+    pub r_x: BigInt,
+    pub s: Secp256k1Scalar,
+}
+
+/// SHA256(SHA256(tag) || SHA256(tag) || x_1 || ... || x_n)
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.input(tag_hash);
+    hasher.input(tag_hash);
+    for d in data {
+        hasher.input(d);
+    }
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.result());
+    hash
+}
+
+impl XOnlyPublicKey {
+    /// returns the key of the point and whether the point had to be negated (odd y).
+    pub fn from_point(point: &Secp256k1Point) -> Result<(XOnlyPublicKey, bool), SignatureError> {
+        match point.y_coor() {
+            Some(y) if !point.is_zero() => {
+                if BigInt::is_even(&y) {
+                    Ok((XOnlyPublicKey { point: *point }, false))
+                } else {
+                    let negated = Secp256k1Point::zero().sub_point(&point.get_element());
+                    Ok((XOnlyPublicKey { point: negated }, true))
+                }
+            }
+            _ => Err(SignatureError),
+        }
+    }
+
+    /// lift_x of BIP-340, fails if x is not the x coordinate of a point.
+    pub fn from_bytes(bytes: &[u8]) -> Result<XOnlyPublicKey, SignatureError> {
+        if bytes.len() != 32 {
+            return Err(SignatureError);
+        }
+        Ok(XOnlyPublicKey {
+            point: lift_x(&BigInt::from(bytes))?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        to_bytes32(&self.point.x_coor().unwrap())
+    }
+
+    /// the point with even y
+    pub fn to_point(&self) -> Secp256k1Point {
+        self.point
+    }
+
+    /// The BIP-341 output key Q = P + t * G with t = hash_TapTweak(P || merkle_root), the merkle
+    /// root is omitted for outputs without scripts. Returns Q and the parity of its y coordinate
+    /// which spending a script path needs.
+    pub fn tap_tweak(
+        &self,
+        merkle_root: Option<&[u8]>,
+    ) -> Result<(XOnlyPublicKey, bool), SignatureError> {
+        let t = tap_tweak_scalar(self, merkle_root)?;
+        let q = self.point + Secp256k1Point::mul_base(&t);
+        XOnlyPublicKey::from_point(&q)
+    }
+}
+
+// deserialized keys are checked like decoded ones: the point must be lift_x of its x coordinate
+impl<'de> Deserialize<'de> for XOnlyPublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<XOnlyPublicKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct XOnlyPublicKeyFields {
+            point: Secp256k1Point,
+        }
+
+        let fields = XOnlyPublicKeyFields::deserialize(deserializer)?;
+        if fields.point.is_zero() {
+            return Err(de::Error::custom(SignatureError));
+        }
+        let x = fields.point.x_coor().unwrap();
+        let pk = XOnlyPublicKey::from_bytes(&to_bytes32(&x)).map_err(de::Error::custom)?;
+        if pk.point != fields.point {
+            return Err(de::Error::custom(SignatureError));
+        }
+        Ok(pk)
+    }
+}
+
+impl KeyPair {
+    pub fn from_secret(sk: Secp256k1Scalar) -> Result<KeyPair, SignatureError> {
+        if sk.to_big_int() == BigInt::zero() {
+            return Err(SignatureError);
+        }
+        let (pk, negated) = XOnlyPublicKey::from_point(&Secp256k1Point::mul_base(&sk))?;
+        let sk = if negated { negate(&sk) } else { sk };
+        Ok(KeyPair { sk, pk })
+    }
+
+    pub fn new_random() -> KeyPair {
+        KeyPair::from_secret(ECScalar::new_random()).unwrap()
+    }
+
+    /// the key pair of the output key of XOnlyPublicKey::tap_tweak
+    pub fn tap_tweak(&self, merkle_root: Option<&[u8]>) -> Result<KeyPair, SignatureError> {
+        let t = tap_tweak_scalar(&self.pk, merkle_root)?;
+        KeyPair::from_secret(self.sk + t)
+    }
+
+    /// signs with auxiliary randomness, which only protects against side channels: all zero
+    /// aux_rand still gives secure signatures. Fails if the nonce is zero, which only happens
+    /// with negligible probability.
+    pub fn sign(&self, msg: &[u8], aux_rand: &[u8; 32]) -> Result<Bip340Signature, SignatureError> {
+        let d = to_bytes32(&self.sk.to_big_int());
+        let p_x = self.pk.to_bytes();
+        let aux_hash = tagged_hash("BIP0340/aux", &[aux_rand]);
+        let t = d
+            .iter()
+            .zip(aux_hash.iter())
+            .map(|(d_i, a_i)| d_i ^ a_i)
+            .collect::<Vec<u8>>();
+        let nonce = tagged_hash("BIP0340/nonce", &[&t, &p_x, msg]);
+        let k: Secp256k1Scalar = ECScalar::from(&BigInt::from(&nonce[..]));
+        if k.to_big_int() == BigInt::zero() {
+            return Err(SignatureError);
+        }
+        let (r, negated) = XOnlyPublicKey::from_point(&Secp256k1Point::mul_base(&k)).unwrap();
+        let k = if negated { negate(&k) } else { k };
+        let r_x = r.point.x_coor().unwrap();
+
+        let e = challenge(&r_x, &p_x, msg);
+        Ok(Bip340Signature {
+            r_x,
+            s: k + e * self.sk,
+        })
+    }
+}
+
+impl Bip340Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&to_bytes32(&self.r_x));
+        bytes[32..].copy_from_slice(&to_bytes32(&self.s.to_big_int()));
+        bytes
+    }
+
+    /// fails if r is not below the field size or s is not below the group order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Bip340Signature, SignatureError> {
+        if bytes.len() != 64 {
+            return Err(SignatureError);
+        }
+        let r_x = BigInt::from(&bytes[..32]);
+        let s = BigInt::from(&bytes[32..]);
+        if r_x >= BigInt::from(&FIELD_PRIME[..]) || s >= Secp256k1Scalar::q() {
+            return Err(SignatureError);
+        }
+        Ok(Bip340Signature {
+            r_x,
+            s: ECScalar::from(&s),
+        })
+    }
+
+    pub fn verify(&self, msg: &[u8], pk: &XOnlyPublicKey) -> Result<(), SignatureError> {
+        // R = s * G - e * P must have even y and x coordinate r_x
+        let e = challenge(&self.r_x, &pk.to_bytes(), msg);
+        let r = Secp256k1Point::multi_scalar_mul(
+            &[Secp256k1Point::generator(), pk.point],
+            &[self.s, negate(&e)],
+        );
+        match (r.x_coor(), r.y_coor()) {
+            (Some(x), Some(y)) if !r.is_zero() && BigInt::is_even(&y) && x == self.r_x => Ok(()),
+            _ => Err(SignatureError),
+        }
+    }
+
+    /// Checks all the signatures at once: with random a_1 = 1, a_2, ..., a_n
+    /// (a_1 * s_1 + ... + a_n * s_n) * G = a_1 * R_1 + a_1 * e_1 * P_1 + ... + a_n * e_n * P_n
+    /// using a single multi scalar multiplication. Fails if any of the signatures is invalid.
+    pub fn batch_verify(
+        signatures: &[Bip340Signature],
+        msgs: &[&[u8]],
+        pks: &[XOnlyPublicKey],
+    ) -> Result<(), SignatureError> {
+        if signatures.len() != msgs.len() || signatures.len() != pks.len() {
+            return Err(SignatureError);
+        }
+        let mut points = vec![Secp256k1Point::generator()];
+        let mut scalars = vec![Secp256k1Scalar::zero()];
+        for (i, signature) in signatures.iter().enumerate() {
+            let a: Secp256k1Scalar = if i == 0 {
+                ECScalar::from(&BigInt::one())
+            } else {
+                ECScalar::new_random()
+            };
+            let r = lift_x(&signature.r_x)?;
+            let e = challenge(&signature.r_x, &pks[i].to_bytes(), msgs[i]);
+            scalars[0] = scalars[0] + negate(&(a * signature.s));
+            points.push(r);
+            scalars.push(a);
+            points.push(pks[i].point);
+            scalars.push(a * e);
+        }
+        if Secp256k1Point::multi_scalar_mul(&points, &scalars).is_zero() {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+}
+
+// deserialized signatures are range checked like decoded ones, s is a scalar already
+impl<'de> Deserialize<'de> for Bip340Signature {
+    fn deserialize<D>(deserializer: D) -> Result<Bip340Signature, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Bip340SignatureFields {
+            r_x: BigInt,
+            s: Secp256k1Scalar,
+        }
+
+        let fields = Bip340SignatureFields::deserialize(deserializer)?;
+        if fields.r_x < BigInt::zero() || fields.r_x >= BigInt::from(&FIELD_PRIME[..]) {
+            return Err(de::Error::custom(SignatureError));
+        }
+        Ok(Bip340Signature {
+            r_x: fields.r_x,
+            s: fields.s,
+        })
+    }
+}
+
+// hash_BIP0340/challenge(r_x || p_x || msg) mod n
+fn challenge(r_x: &BigInt, p_x: &[u8; 32], msg: &[u8]) -> Secp256k1Scalar {
+    let e = tagged_hash("BIP0340/challenge", &[&to_bytes32(r_x), p_x, msg]);
+    ECScalar::from(&BigInt::from(&e[..]))
+}
+
+fn tap_tweak_scalar(
+    pk: &XOnlyPublicKey,
+    merkle_root: Option<&[u8]>,
+) -> Result<Secp256k1Scalar, SignatureError> {
+    let t = tagged_hash("TapTweak", &[&pk.to_bytes(), merkle_root.unwrap_or(&[])]);
+    let t = BigInt::from(&t[..]);
+    if t >= Secp256k1Scalar::q() {
+        return Err(SignatureError);
+    }
+    Ok(ECScalar::from(&t))
+}
+
+// the point with x coordinate x and even y, x = 0 is not on the curve since 7 is not a square
+fn lift_x(x: &BigInt) -> Result<Secp256k1Point, SignatureError> {
+    if *x == BigInt::zero() || *x >= BigInt::from(&FIELD_PRIME[..]) {
+        return Err(SignatureError);
+    }
+    // from_bytes decodes up to 32 bytes as the x coordinate of a point with even y
+    let point = Secp256k1Point::from_bytes(&to_bytes32(x)).map_err(|_| SignatureError)?;
+    if point.is_zero() {
+        return Err(SignatureError);
+    }
+    Ok(point)
+}
+
+pub(crate) fn negate<S: ECScalar>(k: &S) -> S {
+    ECScalar::from(&BigInt::mod_sub(&BigInt::zero(), &k.to_big_int(), &S::q()))
+}
+
+// n must fit in 32 bytes: it is a scalar, a coordinate or the r_x of a signature, which is range
+// checked when decoded or deserialized
+pub(crate) fn to_bytes32(n: &BigInt) -> [u8; 32] {
+    let v = BigInt::to_vec(n);
+    let mut bytes = [0u8; 32];
+    bytes[32 - v.len()..].copy_from_slice(&v);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex32(s: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(s).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn test_sign_vectors() {
+        // test vectors 0 to 3 and 15 to 18 of BIP-340
+        let vectors: [(&str, &str, &str, &str, &str); 4] = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+                 25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+            ),
+            (
+                "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+                 8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+            ),
+            (
+                "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b14e5c9",
+                "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+                "c87aa53824b4d7ae2eb035a2b5bbbccc080e76cdc6d1692c4b0b62d798e6d906",
+                "7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c",
+                "5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1b\
+                 ab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7",
+            ),
+            (
+                "0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710",
+                "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec\
+                 97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3",
+            ),
+        ];
+        for (sk, pk, aux_rand, msg, signature) in vectors.iter() {
+            let keypair = KeyPair::from_secret(ECScalar::from(&BigInt::from_hex(sk))).unwrap();
+            assert_eq!(hex::encode(keypair.pk.to_bytes()), *pk);
+            let msg = hex::decode(msg).unwrap();
+            let sig = keypair.sign(&msg, &hex32(aux_rand)).unwrap();
+            assert_eq!(hex::encode(&sig.to_bytes()[..]), *signature);
+            assert!(sig.verify(&msg, &keypair.pk).is_ok());
+        }
+
+        // messages of other sizes
+        let keypair = KeyPair::from_secret(ECScalar::from(&BigInt::from_hex(
+            "0340034003400340034003400340034003400340034003400340034003400340",
+        )))
+        .unwrap();
+        let vectors: [(Vec<u8>, &str); 4] = [
+            (
+                vec![],
+                "71535db165ecd9fbbc046e5ffaea61186bb6ad436732fccc25291a55895464cf\
+                 6069ce26bf03466228f19a3a62db8a649f2d560fac652827d1af0574e427ab63",
+            ),
+            (
+                vec![0x11],
+                "08a20a0afef64124649232e0693c583ab1b9934ae63b4c3511f3ae1134c6a303\
+                 ea3173bfea6683bd101fa5aa5dbc1996fe7cacfc5a577d33ec14564cec2bacbf",
+            ),
+            (
+                (1..=17).collect(),
+                "5130f39a4059b43bc7cac09a19ece52b5d8699d1a71e3c52da9afdb6b50ac370\
+                 c4a482b77bf960f8681540e25b6771ece1e5a37fd80e5a51897c5566a97ea5a5",
+            ),
+            (
+                vec![0x99; 100],
+                "403b12b0d8555a344175ea7ec746566303321e5dbfa8be6f091635163eca79a8\
+                 585ed3e3170807e7c03b720fc54c7b23897fcba0e9d0b4a06894cfd249f22367",
+            ),
+        ];
+        for (msg, signature) in vectors.iter() {
+            let sig = keypair.sign(msg, &[0u8; 32]).unwrap();
+            assert_eq!(hex::encode(&sig.to_bytes()[..]), *signature);
+        }
+    }
+
+    #[test]
+    fn test_verify_vectors() {
+        // test vectors 4 to 14 of BIP-340
+        let msg = "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+        let pk = "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+        let vectors: [(&str, &str, &str, bool); 11] = [
+            (
+                "d69c3509bb99e412e68b0fe8544e72837dfa30746d8be2aa65975f29d22dc7b9",
+                "4df3c3f68fcc83b27e9d42c90431a72499f17875c81a599b566c9889b9696703",
+                "00000000000000000000003b78ce563f89a0ed9414f5aa28ad0d96d6795f9c63\
+                 76afb1548af603b3eb45c9f8207dee1060cb71c04e80f593060b07d28308d7f4",
+                true,
+            ),
+            // public key not on the curve
+            (
+                "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34",
+                msg,
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+            // R has odd y
+            (
+                pk,
+                msg,
+                "fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556\
+                 3cc27944640ac607cd107ae10923d9ef7a73c643e166be5ebeafa34b1ac553e2",
+                false,
+            ),
+            // negated message
+            (
+                pk,
+                msg,
+                "1fa62e331edbc21c394792d2ab1100a7b432b013df3f6ff4f99fcb33e0e1515f\
+                 28890b3edb6e7189b630448b515ce4f8622a954cfe545735aaea5134fccdb2bd",
+                false,
+            ),
+            // negated s
+            (
+                pk,
+                msg,
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 961764b3aa9b2ffcb6ef947b6887a226e8d7c93e00c5ed0c1834ff0d0c2e6da6",
+                false,
+            ),
+            // s * G - e * P is the point at infinity
+            (
+                pk,
+                msg,
+                "0000000000000000000000000000000000000000000000000000000000000000\
+                 123dda8328af9c23a94c1feecfd123ba4fb73476f0d594dcb65c6425bd186051",
+                false,
+            ),
+            (
+                pk,
+                msg,
+                "0000000000000000000000000000000000000000000000000000000000000001\
+                 7615fbaf5ae28864013c099742deadb4dba87f11ac6754f93780d5a1837cf197",
+                false,
+            ),
+            // r is not the x coordinate of a point
+            (
+                pk,
+                msg,
+                "4a298dacae57395a15d0795ddbfd1dcb564da82b0f269bc70a74f8220429ba1d\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+            // r is the field size
+            (
+                pk,
+                msg,
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+            // s is the group order
+            (
+                pk,
+                msg,
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                false,
+            ),
+            // the public key exceeds the field size
+            (
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+                msg,
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+        ];
+        for (pk, msg, signature, valid) in vectors.iter() {
+            let msg = hex::decode(msg).unwrap();
+            let verified = XOnlyPublicKey::from_bytes(&hex::decode(pk).unwrap())
+                .and_then(|pk| {
+                    let sig = Bip340Signature::from_bytes(&hex::decode(signature).unwrap())?;
+                    sig.verify(&msg, &pk)
+                })
+                .is_ok();
+            assert_eq!(verified, *valid, "{}", signature);
+        }
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let keypairs = (0..5).map(|_| KeyPair::new_random()).collect::<Vec<_>>();
+        let pks = keypairs.iter().map(|k| k.pk).collect::<Vec<_>>();
+        let msgs: Vec<&[u8]> = vec![b"0", b"1", b"2", b"3", b"4"];
+        let mut signatures = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .map(|(keypair, msg)| keypair.sign(msg, &[7u8; 32]).unwrap())
+            .collect::<Vec<_>>();
+        assert!(Bip340Signature::batch_verify(&signatures, &msgs, &pks).is_ok());
+        assert!(Bip340Signature::batch_verify(&[], &[], &[]).is_ok());
+        assert!(Bip340Signature::batch_verify(&signatures[1..], &msgs, &pks).is_err());
+
+        signatures.swap(1, 2);
+        assert!(Bip340Signature::batch_verify(&signatures, &msgs, &pks).is_err());
+        signatures.swap(1, 2);
+        let one: Secp256k1Scalar = ECScalar::from(&BigInt::one());
+        signatures[4].s = signatures[4].s + one;
+        assert!(Bip340Signature::batch_verify(&signatures, &msgs, &pks).is_err());
+
+        // r_x = 0 is not the x coordinate of a point
+        signatures[4].s = signatures[4].s + negate(&one);
+        signatures[3].r_x = BigInt::zero();
+        assert!(Bip340Signature::batch_verify(&signatures, &msgs, &pks).is_err());
+        assert!(signatures[3].verify(msgs[3], &pks[3]).is_err());
+    }
+
+    #[test]
+    fn test_zero_key() {
+        assert!(XOnlyPublicKey::from_bytes(&[0u8; 32]).is_err());
+        assert!(XOnlyPublicKey::from_point(&Secp256k1Point::zero()).is_err());
+
+        let zero = XOnlyPublicKey {
+            point: Secp256k1Point::zero(),
+        };
+        let encoded = serde_json::to_string(&zero).unwrap();
+        assert!(serde_json::from_str::<XOnlyPublicKey>(&encoded).is_err());
+
+        // the point with odd y is not a valid key either
+        let pk = KeyPair::new_random().pk;
+        let odd = XOnlyPublicKey {
+            point: Secp256k1Point::zero().sub_point(&pk.point.get_element()),
+        };
+        let encoded = serde_json::to_string(&odd).unwrap();
+        assert!(serde_json::from_str::<XOnlyPublicKey>(&encoded).is_err());
+        let encoded = serde_json::to_string(&pk).unwrap();
+        assert_eq!(
+            serde_json::from_str::<XOnlyPublicKey>(&encoded).unwrap(),
+            pk
+        );
+    }
+
+    #[test]
+    fn test_signature_range() {
+        let keypair = KeyPair::new_random();
+        let sig = keypair.sign(b"message", &[0u8; 32]).unwrap();
+        let encoded = serde_json::to_string(&sig).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Bip340Signature>(&encoded).unwrap(),
+            sig
+        );
+
+        // r_x must be below the field size, larger values do not fit in 32 bytes
+        let p = BigInt::from(&FIELD_PRIME[..]);
+        for r_x in [
+            p.clone(),
+            BigInt::one() << 300,
+            BigInt::zero() - BigInt::one(),
+        ]
+        .iter()
+        {
+            let invalid = Bip340Signature {
+                r_x: r_x.clone(),
+                s: sig.s,
+            };
+            let encoded = serde_json::to_string(&invalid).unwrap();
+            assert!(serde_json::from_str::<Bip340Signature>(&encoded).is_err());
+        }
+        assert!(Bip340Signature::from_bytes(&[0xff; 64]).is_err());
+    }
+
+    #[test]
+    fn test_tap_tweak() {
+        // BIP-341 wallet test vectors, key path only output
+        let internal = XOnlyPublicKey::from_bytes(
+            &hex::decode("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d")
+                .unwrap(),
+        )
+        .unwrap();
+        let (output, _) = internal.tap_tweak(None).unwrap();
+        assert_eq!(
+            hex::encode(output.to_bytes()),
+            "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"
+        );
+
+        // the tweaked key pair signs for the output key
+        let keypair = KeyPair::new_random();
+        let merkle_root = [5u8; 32];
+        let tweaked = keypair.tap_tweak(Some(&merkle_root)).unwrap();
+        let (output, _) = keypair.pk.tap_tweak(Some(&merkle_root)).unwrap();
+        assert_eq!(tweaked.pk, output);
+        let sig = tweaked.sign(b"spend", &[0u8; 32]).unwrap();
+        assert!(sig.verify(b"spend", &output).is_ok());
+        assert!(sig.verify(b"spend", &keypair.pk).is_err());
+    }
+}
@@ -0,0 +1,407 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use super::SignatureError;
+
+use crate::elliptic::curves::bls12_381::g1::{FieldScalar, G1Point};
+use crate::elliptic::curves::bls12_381::g2::G2Point;
+use crate::elliptic::curves::bls12_381::pairing::{Bls12_381, Gt};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+
+/// BLS signatures over BLS12-381 following the proof of possession scheme of
+/// draft-irtf-cfrg-bls-signature-05:
+/// D. Boneh, S. Gorbunov, R. Wahby, H. Wee, C. Wood and Z. Zhang. BLS Signatures.
+/// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05
+///
+/// A signature on a message is sk * H(msg) where H hashes to curve (RFC 9380). Signatures on the
+/// same message aggregate into a signature under the sum of the public keys, which is only safe
+/// once every signer proved possession of its secret key (otherwise a rogue key pk' = x * g - pk
+/// lets an attacker forge the aggregate). Aggregates of signatures on distinct messages are
+/// checked with a single multi pairing.
+///
+/// The variant picks the groups: MinPk has public keys in G1 and signatures in G2 (used by
+/// Ethereum), MinSig has 48 bytes signatures in G1 and public keys in G2.
+pub trait BlsVariant: Clone + Copy + PartialEq + Debug {
+    type PublicKey: ECPoint<Scalar = FieldScalar> + Copy + Debug + Serialize + DeserializeOwned;
+    type Signature: HashToCurve<Scalar = FieldScalar> + Copy + Debug + Serialize + DeserializeOwned;
+
+    /// the domain separation tag of signatures
+    const CIPHERSUITE_ID: &'static str;
+    /// the domain separation tag of proofs of possession
+    const POP_CIPHERSUITE_ID: &'static str;
+
+    /// computes e(pk_1, sig_1) + ... + e(pk_n, sig_n), putting each point in its group
+    fn multi_pairing(terms: &[(&Self::PublicKey, &Self::Signature)]) -> Gt;
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MinPk;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MinSig;
+
+impl BlsVariant for MinPk {
+    type PublicKey = G1Point;
+    type Signature = G2Point;
+
+    const CIPHERSUITE_ID: &'static str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_CIPHERSUITE_ID: &'static str = "BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn multi_pairing(terms: &[(&G1Point, &G2Point)]) -> Gt {
+        Bls12_381::multi_pairing(terms)
+    }
+}
+
+impl BlsVariant for MinSig {
+    type PublicKey = G2Point;
+    type Signature = G1Point;
+
+    const CIPHERSUITE_ID: &'static str = "BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_CIPHERSUITE_ID: &'static str = "BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn multi_pairing(terms: &[(&G2Point, &G1Point)]) -> Gt {
+        let terms = terms
+            .iter()
+            .map(|(pk, sig)| (*sig, *pk))
+            .collect::<Vec<_>>();
+        Bls12_381::multi_pairing(&terms)
+    }
+}
+
+pub struct KeyPair<V: BlsVariant> {
+    sk: FieldScalar,
+    pub pk: V::PublicKey,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BlsSignature<V: BlsVariant> {
+    pub sigma: V::Signature,
+}
+
+/// A signature on the public key itself, under a separate domain separation tag.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ProofOfPossession<V: BlsVariant> {
+    pub sigma: V::Signature,
+}
+
+impl<V: BlsVariant> KeyPair<V> {
+    pub fn from_secret(sk: FieldScalar) -> KeyPair<V> {
+        let pk = V::PublicKey::mul_base(&sk);
+        KeyPair { sk, pk }
+    }
+
+    pub fn new_random() -> KeyPair<V> {
+        KeyPair::from_secret(ECScalar::new_random())
+    }
+
+    /// KeyGen of the draft: derives the secret key from at least 32 bytes of keying material
+    /// with HKDF-SHA256.
+    pub fn keygen(ikm: &[u8], key_info: &[u8]) -> Result<KeyPair<V>, SignatureError> {
+        if ikm.len() < 32 {
+            return Err(SignatureError);
+        }
+        let mut ikm = ikm.to_vec();
+        ikm.push(0);
+        let mut info = key_info.to_vec();
+        info.extend_from_slice(&[0, 48]);
+
+        let mut salt = Sha256::digest(b"BLS-SIG-KEYGEN-SALT-").to_vec();
+        loop {
+            let prk = hmac_sha256(&salt, &[&ikm]);
+            let t1 = hmac_sha256(&prk, &[&info, &[1]]);
+            let t2 = hmac_sha256(&prk, &[&t1, &info, &[2]]);
+            let okm = [&t1[..], &t2[..16]].concat();
+            let sk: FieldScalar = ECScalar::from(&BigInt::from(&okm[..]));
+            if sk.to_big_int() != BigInt::zero() {
+                return Ok(KeyPair::from_secret(sk));
+            }
+            salt = Sha256::digest(&salt).to_vec();
+        }
+    }
+
+    pub fn sign(&self, msg: &[u8]) -> BlsSignature<V> {
+        let h = V::Signature::hash_to_curve(msg, V::CIPHERSUITE_ID.as_bytes());
+        BlsSignature { sigma: h * self.sk }
+    }
+
+    pub fn prove_possession(&self) -> ProofOfPossession<V> {
+        let h = V::Signature::hash_to_curve(
+            &self.pk.pk_to_key_slice(),
+            V::POP_CIPHERSUITE_ID.as_bytes(),
+        );
+        ProofOfPossession { sigma: h * self.sk }
+    }
+}
+
+impl<V: BlsVariant> BlsSignature<V> {
+    pub fn verify(&self, msg: &[u8], pk: &V::PublicKey) -> Result<(), SignatureError> {
+        self.aggregate_verify(&[msg], &[*pk])
+    }
+
+    /// sums the signatures, fails for an empty list.
+    pub fn aggregate(signatures: &[BlsSignature<V>]) -> Result<BlsSignature<V>, SignatureError> {
+        let (first, rest) = signatures.split_first().ok_or(SignatureError)?;
+        let sigma = rest
+            .iter()
+            .fold(first.sigma, |acc, signature| acc + signature.sigma);
+        Ok(BlsSignature { sigma })
+    }
+
+    /// verifies an aggregate of signatures on the same message. Each public key must come with
+    /// a verified ProofOfPossession.
+    pub fn fast_aggregate_verify(
+        &self,
+        msg: &[u8],
+        pks: &[V::PublicKey],
+    ) -> Result<(), SignatureError> {
+        let (first, rest) = pks.split_first().ok_or(SignatureError)?;
+        if !rest.iter().chain(Some(first)).all(key_validate) {
+            return Err(SignatureError);
+        }
+        let apk = rest.iter().fold(*first, |acc, pk| acc + *pk);
+        self.aggregate_verify(&[msg], &[apk])
+    }
+
+    /// verifies an aggregate of the signatures of msgs[i] under pks[i].
+    pub fn aggregate_verify(
+        &self,
+        msgs: &[&[u8]],
+        pks: &[V::PublicKey],
+    ) -> Result<(), SignatureError> {
+        if msgs.is_empty()
+            || msgs.len() != pks.len()
+            || !pks.iter().all(key_validate)
+            || !self.sigma.is_in_prime_order_subgroup()
+        {
+            return Err(SignatureError);
+        }
+        let hashes = msgs
+            .iter()
+            .map(|msg| V::Signature::hash_to_curve(msg, V::CIPHERSUITE_ID.as_bytes()))
+            .collect::<Vec<_>>();
+
+        // e(pk_1, H(msg_1)) + ... + e(pk_n, H(msg_n)) - e(g, sigma) = 0
+        let g = V::PublicKey::generator();
+        let minus_g = V::PublicKey::zero().sub_point(&g.get_element());
+        let mut terms = pks.iter().zip(hashes.iter()).collect::<Vec<_>>();
+        terms.push((&minus_g, &self.sigma));
+        if V::multi_pairing(&terms) == Gt::identity() {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+}
+
+impl<V: BlsVariant> ProofOfPossession<V> {
+    pub fn verify(&self, pk: &V::PublicKey) -> Result<(), SignatureError> {
+        if !key_validate(pk) || !self.sigma.is_in_prime_order_subgroup() {
+            return Err(SignatureError);
+        }
+        let h =
+            V::Signature::hash_to_curve(&pk.pk_to_key_slice(), V::POP_CIPHERSUITE_ID.as_bytes());
+        let g = V::PublicKey::generator();
+        let minus_g = V::PublicKey::zero().sub_point(&g.get_element());
+        if V::multi_pairing(&[(pk, &h), (&minus_g, &self.sigma)]) == Gt::identity() {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+}
+
+// KeyValidate of the draft
+fn key_validate<P: ECPoint>(pk: &P) -> bool {
+    !pk.is_zero() && pk.is_valid() && pk.is_in_prime_order_subgroup()
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> Vec<u8> {
+    let mut hmac = Hmac::<Sha256>::new_varkey(key).expect("HMAC accepts keys of any size");
+    for d in data {
+        hmac.input(d);
+    }
+    hmac.result().code().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::traits::Converter;
+
+    fn keypair_from_hex<V: BlsVariant>(sk: &str) -> KeyPair<V> {
+        KeyPair::from_secret(ECScalar::from(&BigInt::from_hex(sk)))
+    }
+
+    #[test]
+    fn test_sign_min_pk() {
+        // test vectors of the Ethereum consensus specs (min-pk with proof of possession)
+        let msg = [0u8; 32];
+        let vectors = [
+            (
+                "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+                "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+            ),
+            (
+                "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+                "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+            ),
+        ];
+        for (sk, expected) in vectors.iter() {
+            let keypair = keypair_from_hex::<MinPk>(sk);
+            let signature = keypair.sign(&msg);
+            assert_eq!(hex::encode(signature.sigma.pk_to_key_slice()), *expected);
+            assert!(signature.verify(&msg, &keypair.pk).is_ok());
+        }
+    }
+
+    fn test_sign_verify<V: BlsVariant>() {
+        let keypair = KeyPair::<V>::new_random();
+        let signature = keypair.sign(b"message");
+        assert!(signature.verify(b"message", &keypair.pk).is_ok());
+        assert!(signature.verify(b"other message", &keypair.pk).is_err());
+        let other = KeyPair::<V>::new_random();
+        assert!(signature.verify(b"message", &other.pk).is_err());
+        assert!(signature.verify(b"message", &V::PublicKey::zero()).is_err());
+
+        let s = serde_json::to_string(&signature).unwrap();
+        let des: BlsSignature<V> = serde_json::from_str(&s).unwrap();
+        assert_eq!(des, signature);
+    }
+
+    #[test]
+    fn test_sign_verify_min_pk() {
+        test_sign_verify::<MinPk>();
+    }
+
+    #[test]
+    fn test_sign_verify_min_sig() {
+        test_sign_verify::<MinSig>();
+    }
+
+    #[test]
+    fn test_keygen() {
+        let ikm = [7u8; 32];
+        let keypair = KeyPair::<MinPk>::keygen(&ikm, b"").unwrap();
+        assert_eq!(keypair.pk, KeyPair::<MinPk>::keygen(&ikm, b"").unwrap().pk);
+        assert_ne!(
+            keypair.pk,
+            KeyPair::<MinPk>::keygen(&ikm, b"info").unwrap().pk
+        );
+        assert_ne!(
+            keypair.pk,
+            KeyPair::<MinPk>::keygen(&[8u8; 32], b"").unwrap().pk
+        );
+        assert!(KeyPair::<MinPk>::keygen(&ikm[..31], b"").is_err());
+
+        // the secret key does not depend on the variant
+        let keypair = KeyPair::<MinSig>::keygen(&ikm, b"").unwrap();
+        assert_eq!(keypair.sk, KeyPair::<MinPk>::keygen(&ikm, b"").unwrap().sk);
+    }
+
+    fn test_fast_aggregate_verify<V: BlsVariant>() {
+        let keypairs = (0..4)
+            .map(|_| KeyPair::<V>::new_random())
+            .collect::<Vec<_>>();
+        let pks = keypairs
+            .iter()
+            .map(|keypair| keypair.pk)
+            .collect::<Vec<_>>();
+        for keypair in keypairs.iter() {
+            assert!(keypair.prove_possession().verify(&keypair.pk).is_ok());
+        }
+        let signatures = keypairs
+            .iter()
+            .map(|keypair| keypair.sign(b"message"))
+            .collect::<Vec<_>>();
+        let aggregate = BlsSignature::aggregate(&signatures).unwrap();
+        assert!(aggregate.fast_aggregate_verify(b"message", &pks).is_ok());
+        assert!(aggregate
+            .fast_aggregate_verify(b"other message", &pks)
+            .is_err());
+        assert!(aggregate
+            .fast_aggregate_verify(b"message", &pks[1..])
+            .is_err());
+        assert!(aggregate.fast_aggregate_verify(b"message", &[]).is_err());
+        assert!(BlsSignature::<V>::aggregate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify_min_pk() {
+        test_fast_aggregate_verify::<MinPk>();
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify_min_sig() {
+        test_fast_aggregate_verify::<MinSig>();
+    }
+
+    fn test_aggregate_verify<V: BlsVariant>() {
+        let keypairs = (0..3)
+            .map(|_| KeyPair::<V>::new_random())
+            .collect::<Vec<_>>();
+        let pks = keypairs
+            .iter()
+            .map(|keypair| keypair.pk)
+            .collect::<Vec<_>>();
+        let msgs: [&[u8]; 3] = [b"first", b"second", b"third"];
+        let signatures = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .map(|(keypair, msg)| keypair.sign(msg))
+            .collect::<Vec<_>>();
+        let aggregate = BlsSignature::aggregate(&signatures).unwrap();
+        assert!(aggregate.aggregate_verify(&msgs, &pks).is_ok());
+
+        let swapped: [&[u8]; 3] = [b"second", b"first", b"third"];
+        assert!(aggregate.aggregate_verify(&swapped, &pks).is_err());
+        assert!(aggregate.aggregate_verify(&msgs[..2], &pks[..2]).is_err());
+        assert!(aggregate.aggregate_verify(&msgs[..2], &pks).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_verify_min_pk() {
+        test_aggregate_verify::<MinPk>();
+    }
+
+    #[test]
+    fn test_aggregate_verify_min_sig() {
+        test_aggregate_verify::<MinSig>();
+    }
+
+    #[test]
+    fn test_rogue_key_attack() {
+        // the attacker publishes pk' = x * g - pk and signs alone for pk + pk'
+        let honest = KeyPair::<MinPk>::new_random();
+        let x: FieldScalar = ECScalar::new_random();
+        let rogue_pk = (G1Point::generator() * x).sub_point(&honest.pk.get_element());
+        let h = G2Point::hash_to_curve(b"message", MinPk::CIPHERSUITE_ID.as_bytes());
+        let forgery = BlsSignature::<MinPk> { sigma: h * x };
+        assert!(forgery
+            .fast_aggregate_verify(b"message", &[honest.pk, rogue_pk])
+            .is_ok());
+
+        // which is why the attacker cannot prove possession of the rogue key
+        let pop = ProofOfPossession::<MinPk> {
+            sigma: KeyPair::<MinPk>::from_secret(x).prove_possession().sigma,
+        };
+        assert!(pop.verify(&rogue_pk).is_err());
+        assert!(honest.prove_possession().verify(&rogue_pk).is_err());
+
+        // and a signature is not a proof of possession
+        let pop = ProofOfPossession::<MinPk> {
+            sigma: honest.sign(&honest.pk.pk_to_key_slice()).sigma,
+        };
+        assert!(pop.verify(&honest.pk).is_err());
+    }
+}
@@ -0,0 +1,580 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use super::SignatureError;
+
+use crate::arithmetic::traits::{Converter, Modulo, NumberTests};
+use crate::elliptic::curves::secp256_k1::{Secp256k1Point, Secp256k1Scalar};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// ECDSA (SEC 1 section 4.1) over any curve whose points have an x coordinate, with the
+/// deterministic nonces of RFC 6979:
+/// T. Pornin. Deterministic Usage of the Digital Signature Algorithm (DSA) and Elliptic Curve
+/// Digital Signature Algorithm (ECDSA). RFC 6979, 2013.
+///
+/// sign and verify hash the message with SHA-256, the _prehashed variants take the digest (e.g.
+/// double SHA-256 for Bitcoin or Keccak-256 for Ethereum). Signatures are always produced with
+/// s <= q / 2 ("low S"). verify accepts both s and q - s like SEC 1, protocols which forbid the
+/// high form (Bitcoin) should check is_low_s as well.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct EcdsaSignature<P: ECPoint> {
+    pub r: P::Scalar,
+    pub s: P::Scalar,
+}
+
+impl<P> EcdsaSignature<P>
+where
+    P: ECPoint + Clone,
+    P::Scalar: Clone,
+{
+    pub fn sign(sk: &P::Scalar, msg: &[u8]) -> EcdsaSignature<P> {
+        EcdsaSignature::sign_prehashed(sk, &Sha256::digest(msg))
+    }
+
+    pub fn sign_prehashed(sk: &P::Scalar, digest: &[u8]) -> EcdsaSignature<P> {
+        EcdsaSignature::sign_with_recovery_id(sk, digest).0
+    }
+
+    pub fn verify(&self, msg: &[u8], pk: &P) -> Result<(), SignatureError> {
+        self.verify_prehashed(&Sha256::digest(msg), pk)
+    }
+
+    pub fn verify_prehashed(&self, digest: &[u8], pk: &P) -> Result<(), SignatureError> {
+        let q = P::Scalar::q();
+        let r = self.r.to_big_int();
+        let s = self.s.to_big_int();
+        if r == BigInt::zero() || s == BigInt::zero() || pk.is_zero() || !pk.is_valid() {
+            return Err(SignatureError);
+        }
+        // R = (e / s) * G + (r / s) * pk
+        let e = bits_to_int(digest, &q);
+        let s_inv = BigInt::mod_inv(&s, &q);
+        let u1: P::Scalar = ECScalar::from(&BigInt::mod_mul(&e, &s_inv, &q));
+        let u2: P::Scalar = ECScalar::from(&BigInt::mod_mul(&r, &s_inv, &q));
+        let point = P::multi_scalar_mul(&[P::generator(), pk.clone()], &[u1, u2]);
+        match point.x_coor() {
+            Some(x) if !point.is_zero() && BigInt::modulus(&x, &q) == r => Ok(()),
+            _ => Err(SignatureError),
+        }
+    }
+
+    pub fn is_low_s(&self) -> bool {
+        let q = P::Scalar::q();
+        self.s.to_big_int() <= q / BigInt::from(2)
+    }
+
+    /// returns the signature with s replaced by q - s if s > q / 2, both verify.
+    pub fn normalize_s(&self) -> EcdsaSignature<P> {
+        if self.is_low_s() {
+            return self.clone();
+        }
+        let zero: P::Scalar = ECScalar::zero();
+        EcdsaSignature {
+            r: self.r.clone(),
+            s: zero.sub(&self.s.get_element()),
+        }
+    }
+
+    /// the ASN.1 DER encoding SEQUENCE { r INTEGER, s INTEGER }
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut body = der_integer(&self.r.to_big_int());
+        body.extend(der_integer(&self.s.to_big_int()));
+        let mut der = vec![0x30];
+        der.extend(der_length(body.len()));
+        der.extend(body);
+        der
+    }
+
+    /// strict DER decoding, alternative BER encodings are rejected.
+    pub fn from_der(bytes: &[u8]) -> Result<EcdsaSignature<P>, SignatureError> {
+        let (tag, body, rest) = der_read(bytes)?;
+        if tag != 0x30 || !rest.is_empty() {
+            return Err(SignatureError);
+        }
+        let (tag, r, body) = der_read(body)?;
+        if tag != 0x02 {
+            return Err(SignatureError);
+        }
+        let (tag, s, body) = der_read(body)?;
+        if tag != 0x02 || !body.is_empty() {
+            return Err(SignatureError);
+        }
+        EcdsaSignature::from_integers(&der_integer_value(r)?, &der_integer_value(s)?)
+    }
+
+    /// r || s, both big endian and padded to the byte length of q.
+    pub fn to_compact(&self) -> Vec<u8> {
+        let len = BigInt::to_vec(&P::Scalar::q()).len();
+        let mut compact = to_fixed_bytes(&self.r.to_big_int(), len);
+        compact.extend(to_fixed_bytes(&self.s.to_big_int(), len));
+        compact
+    }
+
+    pub fn from_compact(bytes: &[u8]) -> Result<EcdsaSignature<P>, SignatureError> {
+        let len = BigInt::to_vec(&P::Scalar::q()).len();
+        if bytes.len() != 2 * len {
+            return Err(SignatureError);
+        }
+        EcdsaSignature::from_integers(&BigInt::from(&bytes[..len]), &BigInt::from(&bytes[len..]))
+    }
+
+    // r and s must be in [1, q - 1]
+    fn from_integers(r: &BigInt, s: &BigInt) -> Result<EcdsaSignature<P>, SignatureError> {
+        let q = P::Scalar::q();
+        let in_range = |x: &BigInt| *x > BigInt::zero() && *x < q;
+        if !in_range(r) || !in_range(s) {
+            return Err(SignatureError);
+        }
+        Ok(EcdsaSignature {
+            r: ECScalar::from(r),
+            s: ECScalar::from(s),
+        })
+    }
+
+    // returns the low S signature and its recovery id: bit 0 is the parity of the y coordinate
+    // of R and bit 1 is set if the x coordinate of R is at least q.
+    fn sign_with_recovery_id(sk: &P::Scalar, digest: &[u8]) -> (EcdsaSignature<P>, u8) {
+        let q = P::Scalar::q();
+        let x = sk.to_big_int();
+        let e = bits_to_int(digest, &q);
+        let mut nonces = Rfc6979::new(&x, &BigInt::modulus(&e, &q), &q);
+        loop {
+            let k = nonces.next_nonce();
+            let k_fe: P::Scalar = ECScalar::from(&k);
+            let point = P::mul_base(&k_fe);
+            let (point_x, point_y) = match (point.x_coor(), point.y_coor()) {
+                (Some(point_x), Some(point_y)) => (point_x, point_y),
+                _ => continue,
+            };
+            let r = BigInt::modulus(&point_x, &q);
+            if r == BigInt::zero() {
+                continue;
+            }
+            // s = (e + r * x) / k, x and k are secret
+            let s = BigInt::mod_mul_sec(
+                &BigInt::mod_add(&e, &BigInt::mod_mul_sec(&r, &x, &q), &q),
+                &BigInt::mod_inv_sec(&k, &q),
+                &q,
+            );
+            if s == BigInt::zero() {
+                continue;
+            }
+            let mut recovery_id = if BigInt::is_even(&point_y) { 0 } else { 1 };
+            if point_x >= q {
+                recovery_id |= 2;
+            }
+            let signature = EcdsaSignature {
+                r: ECScalar::from(&r),
+                s: ECScalar::from(&s),
+            };
+            if signature.is_low_s() {
+                return (signature, recovery_id);
+            }
+            // negating s negates R
+            return (signature.normalize_s(), recovery_id ^ 1);
+        }
+    }
+}
+
+impl EcdsaSignature<Secp256k1Point> {
+    /// signs the digest and returns the recovery id (0 to 3) which lets recover find the public
+    /// key.
+    pub fn sign_recoverable(
+        sk: &Secp256k1Scalar,
+        digest: &[u8],
+    ) -> (EcdsaSignature<Secp256k1Point>, u8) {
+        EcdsaSignature::sign_with_recovery_id(sk, digest)
+    }
+
+    /// recovers the public key from a signature on the digest: pk = (s * R - e * G) / r
+    pub fn recover(
+        &self,
+        digest: &[u8],
+        recovery_id: u8,
+    ) -> Result<Secp256k1Point, SignatureError> {
+        let q = Secp256k1Scalar::q();
+        if recovery_id > 3
+            || self.r.to_big_int() == BigInt::zero()
+            || self.s.to_big_int() == BigInt::zero()
+        {
+            return Err(SignatureError);
+        }
+        let mut x = self.r.to_big_int();
+        if recovery_id & 2 != 0 {
+            x = &x + &q;
+        }
+        let x_bytes = BigInt::to_vec(&x);
+        if x_bytes.len() > 32 {
+            return Err(SignatureError);
+        }
+        // up to 32 bytes are decoded as x with an even y coordinate
+        let mut point = Secp256k1Point::from_bytes(&x_bytes).map_err(|_| SignatureError)?;
+        if recovery_id & 1 != 0 {
+            point = Secp256k1Point::zero().sub_point(&point.get_element());
+        }
+
+        let e = bits_to_int(digest, &q);
+        let r_inv = BigInt::mod_inv(&self.r.to_big_int(), &q);
+        let u1: Secp256k1Scalar = ECScalar::from(&BigInt::mod_sub(
+            &BigInt::zero(),
+            &BigInt::mod_mul(&e, &r_inv, &q),
+            &q,
+        ));
+        let u2: Secp256k1Scalar =
+            ECScalar::from(&BigInt::mod_mul(&self.s.to_big_int(), &r_inv, &q));
+        let pk = Secp256k1Point::multi_scalar_mul(&[Secp256k1Point::generator(), point], &[u1, u2]);
+        if pk.is_zero() {
+            return Err(SignatureError);
+        }
+        self.verify_prehashed(digest, &pk)?;
+        Ok(pk)
+    }
+}
+
+// The HMAC-SHA256 DRBG of RFC 6979 section 3.2, seeded with the secret key x and the reduced
+// hash h
+struct Rfc6979 {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    q: BigInt,
+    first: bool,
+}
+
+impl Rfc6979 {
+    fn new(x: &BigInt, h: &BigInt, q: &BigInt) -> Rfc6979 {
+        let len = BigInt::to_vec(q).len();
+        let seed = [to_fixed_bytes(x, len), to_fixed_bytes(h, len)].concat();
+        let mut k = vec![0u8; 32];
+        let mut v = vec![1u8; 32];
+        k = hmac_sha256(&k, &[&v, &[0], &seed]);
+        v = hmac_sha256(&k, &[&v]);
+        k = hmac_sha256(&k, &[&v, &[1], &seed]);
+        v = hmac_sha256(&k, &[&v]);
+        Rfc6979 {
+            k,
+            v,
+            q: q.clone(),
+            first: true,
+        }
+    }
+
+    // returns the next candidate in [1, q - 1]
+    fn next_nonce(&mut self) -> BigInt {
+        let len = BigInt::to_vec(&self.q).len();
+        loop {
+            if !self.first {
+                self.k = hmac_sha256(&self.k, &[&self.v, &[0]]);
+                self.v = hmac_sha256(&self.k, &[&self.v]);
+            }
+            self.first = false;
+            let mut t = Vec::new();
+            while t.len() < len {
+                self.v = hmac_sha256(&self.k, &[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let k = bits_to_int(&t[..len], &self.q);
+            if k > BigInt::zero() && k < self.q {
+                return k;
+            }
+        }
+    }
+}
+
+// bits2int of RFC 6979: the leftmost bit length of q bits of the digest
+fn bits_to_int(digest: &[u8], q: &BigInt) -> BigInt {
+    let e = BigInt::from(digest);
+    let digest_bits = digest.len() * 8;
+    let q_bits = q.bit_length();
+    if digest_bits > q_bits {
+        e >> (digest_bits - q_bits)
+    } else {
+        e
+    }
+}
+
+fn to_fixed_bytes(n: &BigInt, len: usize) -> Vec<u8> {
+    let bytes = BigInt::to_vec(n);
+    let mut padded = vec![0u8; len - bytes.len()];
+    padded.extend(bytes);
+    padded
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> Vec<u8> {
+    let mut hmac = Hmac::<Sha256>::new_varkey(key).expect("HMAC accepts keys of any size");
+    for d in data {
+        hmac.input(d);
+    }
+    hmac.result().code().to_vec()
+}
+
+fn der_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        vec![len as u8]
+    } else {
+        let bytes = BigInt::to_vec(&BigInt::from(len as u64));
+        let mut encoded = vec![0x80 | bytes.len() as u8];
+        encoded.extend(bytes);
+        encoded
+    }
+}
+
+// a positive INTEGER, with a leading zero byte if its top bit is set
+fn der_integer(n: &BigInt) -> Vec<u8> {
+    let mut value = BigInt::to_vec(n);
+    if value.is_empty() || value[0] & 0x80 != 0 {
+        value.insert(0, 0);
+    }
+    let mut encoded = vec![0x02];
+    encoded.extend(der_length(value.len()));
+    encoded.extend(value);
+    encoded
+}
+
+// reads a TLV and returns (tag, value, remaining bytes), only minimal lengths are accepted
+fn der_read(bytes: &[u8]) -> Result<(u8, &[u8], &[u8]), SignatureError> {
+    if bytes.len() < 2 {
+        return Err(SignatureError);
+    }
+    let tag = bytes[0];
+    let (len, header) = match bytes[1] {
+        len if len < 0x80 => (len as usize, 2),
+        0x81 => {
+            if bytes.len() < 3 || bytes[2] < 0x80 {
+                return Err(SignatureError);
+            }
+            (bytes[2] as usize, 3)
+        }
+        _ => return Err(SignatureError),
+    };
+    if bytes.len() < header + len {
+        return Err(SignatureError);
+    }
+    Ok((tag, &bytes[header..header + len], &bytes[header + len..]))
+}
+
+// the value of a positive, minimally encoded INTEGER
+fn der_integer_value(value: &[u8]) -> Result<BigInt, SignatureError> {
+    match value {
+        [] => Err(SignatureError),
+        [first, ..] if first & 0x80 != 0 => Err(SignatureError),
+        [0, second, ..] if second & 0x80 == 0 => Err(SignatureError),
+        _ => Ok(BigInt::from(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic::curves::p256::{Secp256r1Point, Secp256r1Scalar};
+
+    fn test_sign_verify<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone + PartialEq + std::fmt::Debug,
+    {
+        let sk: P::Scalar = ECScalar::new_random();
+        let pk = P::mul_base(&sk);
+        let signature = EcdsaSignature::<P>::sign(&sk, b"message");
+        assert!(signature.is_low_s());
+        assert!(signature.verify(b"message", &pk).is_ok());
+        assert!(signature.verify(b"other message", &pk).is_err());
+        assert!(signature.verify(b"message", &P::generator()).is_err());
+        // deterministic nonces
+        assert_eq!(EcdsaSignature::<P>::sign(&sk, b"message"), signature);
+
+        // the high S form verifies too
+        let zero: P::Scalar = ECScalar::zero();
+        let high_s = EcdsaSignature::<P> {
+            r: signature.r.clone(),
+            s: zero.sub(&signature.s.get_element()),
+        };
+        assert!(!high_s.is_low_s());
+        assert!(high_s.verify(b"message", &pk).is_ok());
+        assert_eq!(high_s.normalize_s(), signature);
+
+        let der = signature.to_der();
+        assert_eq!(EcdsaSignature::<P>::from_der(&der).unwrap(), signature);
+        let compact = signature.to_compact();
+        assert_eq!(compact.len(), 64);
+        assert_eq!(
+            EcdsaSignature::<P>::from_compact(&compact).unwrap(),
+            signature
+        );
+        assert!(EcdsaSignature::<P>::from_compact(&[0u8; 64]).is_err());
+        assert!(EcdsaSignature::<P>::from_compact(&[0xffu8; 64]).is_err());
+    }
+
+    #[test]
+    fn test_sign_verify_secp256k1() {
+        test_sign_verify::<Secp256k1Point>();
+    }
+
+    #[test]
+    fn test_sign_verify_p256() {
+        test_sign_verify::<Secp256r1Point>();
+    }
+
+    #[test]
+    fn test_rfc6979_p256() {
+        // RFC 6979 appendix A.2.5, with SHA-256
+        let sk: Secp256r1Scalar = ECScalar::from(&BigInt::from_hex(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ));
+        let pk = Secp256r1Point::mul_base(&sk);
+        assert_eq!(
+            pk.x_coor().unwrap().to_hex(),
+            "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+        );
+        let vectors = [
+            (
+                "sample",
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+                "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+            ),
+            (
+                "test",
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367",
+                "019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083",
+            ),
+        ];
+        for (msg, r, s) in vectors.iter() {
+            let expected = EcdsaSignature::<Secp256r1Point> {
+                r: ECScalar::from(&BigInt::from_hex(r)),
+                s: ECScalar::from(&BigInt::from_hex(s)),
+            };
+            let signature = EcdsaSignature::<Secp256r1Point>::sign(&sk, msg.as_bytes());
+            assert_eq!(signature, expected.normalize_s());
+            assert!(expected.verify(msg.as_bytes(), &pk).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rfc6979_secp256k1() {
+        let sk: Secp256k1Scalar = ECScalar::from(&BigInt::one());
+        let signature = EcdsaSignature::<Secp256k1Point>::sign(&sk, b"Satoshi Nakamoto");
+        assert_eq!(
+            hex::encode(signature.to_compact()),
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+             2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_crate_interoperability() {
+        let secp = secp256k1::Secp256k1::new();
+        for i in 0..10 {
+            let sk: Secp256k1Scalar = ECScalar::new_random();
+            let pk = Secp256k1Point::mul_base(&sk);
+            let digest = Sha256::digest(&[i as u8]);
+            let message = secp256k1::Message::from_slice(&digest).unwrap();
+
+            let signature = EcdsaSignature::<Secp256k1Point>::sign_prehashed(&sk, &digest);
+            let der = secp256k1::Signature::from_der(&signature.to_der()).unwrap();
+            assert!(secp.verify(&message, &der, &pk.get_element()).is_ok());
+
+            let theirs = secp.sign(&message, &sk.get_element());
+            let theirs =
+                EcdsaSignature::<Secp256k1Point>::from_der(&theirs.serialize_der()).unwrap();
+            assert!(theirs.verify_prehashed(&digest, &pk).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_recover() {
+        for i in 0..20 {
+            let sk: Secp256k1Scalar = ECScalar::new_random();
+            let pk = Secp256k1Point::mul_base(&sk);
+            let digest = Sha256::digest(&[i as u8]);
+            let (signature, recovery_id) =
+                EcdsaSignature::<Secp256k1Point>::sign_recoverable(&sk, &digest);
+            assert!(recovery_id < 2);
+            assert_eq!(signature.recover(&digest, recovery_id).unwrap(), pk);
+            let other = signature.recover(&digest, recovery_id ^ 1).unwrap();
+            assert_ne!(other, pk);
+            assert!(signature.recover(&digest, 4).is_err());
+
+            let zero_r = EcdsaSignature::<Secp256k1Point> {
+                r: ECScalar::zero(),
+                s: signature.s,
+            };
+            assert!(zero_r.recover(&digest, recovery_id).is_err());
+            let zero_s = EcdsaSignature::<Secp256k1Point> {
+                r: signature.r,
+                s: ECScalar::zero(),
+            };
+            assert!(zero_s.recover(&digest, recovery_id).is_err());
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofFile {
+        #[serde(rename = "testGroups")]
+        test_groups: Vec<WycheproofGroup>,
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofGroup {
+        #[serde(rename = "publicKey")]
+        public_key: WycheproofKey,
+        tests: Vec<WycheproofTest>,
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofKey {
+        wx: String,
+        wy: String,
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofTest {
+        #[serde(rename = "tcId")]
+        tc_id: usize,
+        msg: String,
+        sig: String,
+        result: String,
+    }
+
+    fn test_wycheproof<P>(json: &str)
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        let file: WycheproofFile = serde_json::from_str(json).unwrap();
+        for group in file.test_groups.iter() {
+            let pk = P::from_coor(
+                &BigInt::from_hex(&group.public_key.wx),
+                &BigInt::from_hex(&group.public_key.wy),
+            );
+            for test in group.tests.iter() {
+                let msg = hex::decode(&test.msg).unwrap();
+                let sig = hex::decode(&test.sig).unwrap();
+                let verified = EcdsaSignature::<P>::from_der(&sig)
+                    .and_then(|signature| signature.verify(&msg, &pk))
+                    .is_ok();
+                assert_eq!(verified, test.result == "valid", "test case {}", test.tc_id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wycheproof_secp256k1() {
+        test_wycheproof::<Secp256k1Point>(include_str!(
+            "../../../tests/data/wycheproof/ecdsa_secp256k1_sha256_test.json"
+        ));
+    }
+
+    #[test]
+    fn test_wycheproof_p256() {
+        test_wycheproof::<Secp256r1Point>(include_str!(
+            "../../../tests/data/wycheproof/ecdsa_secp256r1_sha256_test.json"
+        ));
+    }
+}
@@ -0,0 +1,576 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use super::SignatureError;
+
+use crate::arithmetic::traits::{Converter, Samplable};
+use crate::elliptic::curves::ed25519::{Ed25519Point, Ed25519Scalar};
+use crate::elliptic::curves::traits::*;
+use crate::BigInt;
+
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha512};
+
+/// Ed25519 signatures as specified in RFC 8032, with the Ed25519ctx and Ed25519ph variants:
+/// S. Josefsson and I. Liusvaara. Edwards-Curve Digital Signature Algorithm (EdDSA). RFC 8032,
+/// 2017.
+///
+/// Implementations disagree on which signatures are valid when the public key or R are not in
+/// the sub group of prime order or are not canonically encoded. VerificationMode selects the
+/// rules, S must be below the group order in all of them:
+/// - Cofactorless checks [S]B = R + [k]A and rejects non canonical encodings of A and R,
+/// - Cofactored checks [8][S]B = [8]R + [8][k]A as recommended by the RFC and rejects non
+///   canonical encodings of A and R,
+/// - Zip215 follows ZIP-215 (https://zips.z.cash/zip-0215): the cofactored equation, with any
+///   encoding of A and R that decodes to a point. Its results agree with batch verification.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VerificationMode {
+    Cofactorless,
+    Cofactored,
+    Zip215,
+}
+
+/// The public key keeps its encoding: the challenge hashes the bytes it was given, which differ
+/// from the encoding of the point when they are not canonical.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PublicKey {
+    bytes: [u8; 32],
+    point: Ed25519Point,
+}
+
+pub struct KeyPair {
+    // the clamped secret scalar and the prefix hashed into the nonces, both derived from the seed
+    s: Ed25519Scalar,
+    prefix: [u8; 32],
+    pub pk: PublicKey,
+}
+
+/// r is the encoding of the point R
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Ed25519Signature {
+    pub r: [u8; 32],
+    pub s: Ed25519Scalar,
+}
+
+impl PublicKey {
+    pub fn from_point(point: &Ed25519Point) -> PublicKey {
+        PublicKey {
+            bytes: encode(point),
+            point: *point,
+        }
+    }
+
+    /// accepts any encoding of a point, the verification mode decides whether non canonical ones
+    /// are valid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, SignatureError> {
+        if bytes.len() != 32 {
+            return Err(SignatureError);
+        }
+        let mut array = [0u8; 32];
+        array.copy_from_slice(bytes);
+        let point = Ed25519Point::decode_non_canonical(&array).map_err(|_| SignatureError)?;
+        Ok(PublicKey {
+            bytes: array,
+            point,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    pub fn to_point(&self) -> Ed25519Point {
+        self.point
+    }
+}
+
+impl KeyPair {
+    /// expands the 32 bytes secret key of the RFC: SHA-512(seed) is split into the clamped
+    /// scalar s and the prefix.
+    pub fn from_seed(seed: &[u8; 32]) -> KeyPair {
+        let h = Sha512::digest(seed);
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&h[..32]);
+        s_bytes[0] &= 248;
+        s_bytes[31] &= 127;
+        s_bytes[31] |= 64;
+        let s = scalar_from_bytes_le(&s_bytes);
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&h[32..]);
+        KeyPair {
+            s,
+            prefix,
+            pk: PublicKey::from_point(&Ed25519Point::mul_base(&s)),
+        }
+    }
+
+    pub fn new_random() -> KeyPair {
+        let mut seed = [0u8; 32];
+        thread_rng().fill(&mut seed[..]);
+        KeyPair::from_seed(&seed)
+    }
+
+    /// Ed25519
+    pub fn sign(&self, msg: &[u8]) -> Ed25519Signature {
+        self.sign_with_dom(&[], msg)
+    }
+
+    /// Ed25519ctx, the context must have 1 to 255 bytes.
+    pub fn sign_ctx(&self, msg: &[u8], context: &[u8]) -> Result<Ed25519Signature, SignatureError> {
+        if context.is_empty() {
+            return Err(SignatureError);
+        }
+        Ok(self.sign_with_dom(&dom2(0, context)?, msg))
+    }
+
+    /// Ed25519ph, signs the SHA-512 digest of the message. The context has at most 255 bytes.
+    pub fn sign_prehashed(
+        &self,
+        digest: &[u8; 64],
+        context: &[u8],
+    ) -> Result<Ed25519Signature, SignatureError> {
+        Ok(self.sign_with_dom(&dom2(1, context)?, digest))
+    }
+
+    fn sign_with_dom(&self, dom: &[u8], msg: &[u8]) -> Ed25519Signature {
+        let r = hash_to_scalar(&[dom, &self.prefix, msg]);
+        let big_r = encode(&Ed25519Point::mul_base(&r));
+        let k = hash_to_scalar(&[dom, &big_r, &self.pk.bytes, msg]);
+        Ed25519Signature {
+            r: big_r,
+            s: r + k * self.s,
+        }
+    }
+}
+
+impl Ed25519Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&scalar_to_bytes_le(&self.s));
+        bytes
+    }
+
+    /// fails if S is not below the group order
+    pub fn from_bytes(bytes: &[u8]) -> Result<Ed25519Signature, SignatureError> {
+        if bytes.len() != 64 {
+            return Err(SignatureError);
+        }
+        let mut s = bytes[32..].to_vec();
+        s.reverse();
+        let s = BigInt::from(&s[..]);
+        if s >= Ed25519Scalar::q() {
+            return Err(SignatureError);
+        }
+        let mut r = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        Ok(Ed25519Signature {
+            r,
+            s: ECScalar::from(&s),
+        })
+    }
+
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        pk: &PublicKey,
+        mode: VerificationMode,
+    ) -> Result<(), SignatureError> {
+        self.verify_with_dom(&[], msg, pk, mode)
+    }
+
+    pub fn verify_ctx(
+        &self,
+        msg: &[u8],
+        context: &[u8],
+        pk: &PublicKey,
+        mode: VerificationMode,
+    ) -> Result<(), SignatureError> {
+        if context.is_empty() {
+            return Err(SignatureError);
+        }
+        self.verify_with_dom(&dom2(0, context)?, msg, pk, mode)
+    }
+
+    pub fn verify_prehashed(
+        &self,
+        digest: &[u8; 64],
+        context: &[u8],
+        pk: &PublicKey,
+        mode: VerificationMode,
+    ) -> Result<(), SignatureError> {
+        self.verify_with_dom(&dom2(1, context)?, digest, pk, mode)
+    }
+
+    /// Checks Ed25519 signatures at once with random 128 bits z_i:
+    /// [8](-(z_1 * S_1 + ... + z_n * S_n)[B] + sum_i [z_i]R_i + [z_i * k_i]A_i) = 0
+    /// using a single multi scalar multiplication. This is the cofactored equation, so in
+    /// Cofactorless mode the signatures are verified one by one instead.
+    /// Only plain Ed25519 signatures are supported: the challenges are computed without dom2, so
+    /// Ed25519ctx and Ed25519ph signatures fail and must be checked with verify_ctx and
+    /// verify_prehashed.
+    pub fn batch_verify(
+        signatures: &[Ed25519Signature],
+        msgs: &[&[u8]],
+        pks: &[PublicKey],
+        mode: VerificationMode,
+    ) -> Result<(), SignatureError> {
+        if signatures.len() != msgs.len() || signatures.len() != pks.len() {
+            return Err(SignatureError);
+        }
+        if mode == VerificationMode::Cofactorless {
+            return signatures
+                .iter()
+                .zip(msgs.iter().zip(pks.iter()))
+                .try_for_each(|(signature, (msg, pk))| signature.verify(msg, pk, mode));
+        }
+        let mut points = vec![Ed25519Point::generator()];
+        let mut scalars = vec![Ed25519Scalar::zero()];
+        for (i, signature) in signatures.iter().enumerate() {
+            let r = decode(&signature.r, mode)?;
+            check_public_key(&pks[i], mode)?;
+            let k = hash_to_scalar(&[&signature.r, &pks[i].bytes, msgs[i]]);
+            let z: Ed25519Scalar = ECScalar::from(&BigInt::sample(128));
+            scalars[0] = scalars[0].sub(&(z * signature.s).get_element());
+            points.push(r);
+            scalars.push(z);
+            points.push(pks[i].point);
+            scalars.push(z * k);
+        }
+        let eight: Ed25519Scalar = ECScalar::from(&BigInt::from(8));
+        if (Ed25519Point::multi_scalar_mul(&points, &scalars) * eight).is_zero() {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+
+    fn verify_with_dom(
+        &self,
+        dom: &[u8],
+        msg: &[u8],
+        pk: &PublicKey,
+        mode: VerificationMode,
+    ) -> Result<(), SignatureError> {
+        let r = decode(&self.r, mode)?;
+        check_public_key(pk, mode)?;
+        let k = hash_to_scalar(&[dom, &self.r, &pk.bytes, msg]);
+        let s_b = Ed25519Point::mul_base(&self.s);
+        let k_a = pk.point * k;
+        let valid = match mode {
+            VerificationMode::Cofactorless => s_b == r + k_a,
+            VerificationMode::Cofactored | VerificationMode::Zip215 => {
+                let eight: Ed25519Scalar = ECScalar::from(&BigInt::from(8));
+                let diff = s_b
+                    .sub_point(&r.get_element())
+                    .sub_point(&k_a.get_element());
+                (diff * eight).is_zero()
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(SignatureError)
+        }
+    }
+}
+
+// dom2(F, C) = "SigEd25519 no Ed25519 collisions" || F || len(C) || C
+fn dom2(phflag: u8, context: &[u8]) -> Result<Vec<u8>, SignatureError> {
+    if context.len() > 255 {
+        return Err(SignatureError);
+    }
+    let mut dom = b"SigEd25519 no Ed25519 collisions".to_vec();
+    dom.push(phflag);
+    dom.push(context.len() as u8);
+    dom.extend_from_slice(context);
+    Ok(dom)
+}
+
+fn decode(bytes: &[u8; 32], mode: VerificationMode) -> Result<Ed25519Point, SignatureError> {
+    let point = Ed25519Point::decode_non_canonical(bytes).map_err(|_| SignatureError)?;
+    if mode != VerificationMode::Zip215 && encode(&point) != *bytes {
+        return Err(SignatureError);
+    }
+    Ok(point)
+}
+
+fn check_public_key(pk: &PublicKey, mode: VerificationMode) -> Result<(), SignatureError> {
+    if mode != VerificationMode::Zip215 && encode(&pk.point) != pk.bytes {
+        return Err(SignatureError);
+    }
+    Ok(())
+}
+
+fn encode(point: &Ed25519Point) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&point.pk_to_key_slice());
+    bytes
+}
+
+// SHA-512 of the concatenation, read as a little endian integer mod l
+fn hash_to_scalar(data: &[&[u8]]) -> Ed25519Scalar {
+    let mut hasher = Sha512::new();
+    for d in data {
+        hasher.input(d);
+    }
+    scalar_from_bytes_le(&hasher.result())
+}
+
+fn scalar_from_bytes_le(bytes: &[u8]) -> Ed25519Scalar {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    ECScalar::from(&BigInt::from(&bytes[..]))
+}
+
+fn scalar_to_bytes_le(s: &Ed25519Scalar) -> [u8; 32] {
+    let v = BigInt::to_vec(&s.to_big_int());
+    let mut bytes = [0u8; 32];
+    bytes[32 - v.len()..].copy_from_slice(&v);
+    bytes.reverse();
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ErrorKey;
+
+    const MODES: [VerificationMode; 3] = [
+        VerificationMode::Cofactorless,
+        VerificationMode::Cofactored,
+        VerificationMode::Zip215,
+    ];
+
+    fn seed(s: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(s).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn test_rfc8032_vectors() {
+        // RFC 8032 section 7.1, tests 1 to 3
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+        for (sk, pk, msg, signature) in vectors.iter() {
+            let keypair = KeyPair::from_seed(&seed(sk));
+            assert_eq!(hex::encode(keypair.pk.to_bytes()), *pk);
+            let msg = hex::decode(msg).unwrap();
+            let sig = keypair.sign(&msg);
+            assert_eq!(hex::encode(&sig.to_bytes()[..]), *signature);
+            let pk = PublicKey::from_bytes(&hex::decode(pk).unwrap()).unwrap();
+            for mode in MODES.iter() {
+                assert!(sig.verify(&msg, &pk, *mode).is_ok());
+                assert!(sig.verify(b"other message", &pk, *mode).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rfc8032_ctx_and_ph_vectors() {
+        // RFC 8032 section 7.2, context "foo"
+        let keypair = KeyPair::from_seed(&seed(
+            "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        ));
+        let msg = hex::decode("f726936d19c800494e3fdaff20b276a8").unwrap();
+        let sig = keypair.sign_ctx(&msg, b"foo").unwrap();
+        assert_eq!(
+            hex::encode(&sig.to_bytes()[..]),
+            "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d"
+        );
+        for mode in MODES.iter() {
+            assert!(sig.verify_ctx(&msg, b"foo", &keypair.pk, *mode).is_ok());
+            assert!(sig.verify_ctx(&msg, b"bar", &keypair.pk, *mode).is_err());
+            assert!(sig.verify(&msg, &keypair.pk, *mode).is_err());
+        }
+        assert!(keypair.sign_ctx(&msg, b"").is_err());
+        assert!(keypair.sign_ctx(&msg, &[0u8; 256]).is_err());
+
+        // RFC 8032 section 7.3
+        let keypair = KeyPair::from_seed(&seed(
+            "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+        ));
+        let mut digest = [0u8; 64];
+        digest.copy_from_slice(&Sha512::digest(b"abc"));
+        let sig = keypair.sign_prehashed(&digest, &[]).unwrap();
+        assert_eq!(
+            hex::encode(&sig.to_bytes()[..]),
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406"
+        );
+        for mode in MODES.iter() {
+            assert!(sig
+                .verify_prehashed(&digest, &[], &keypair.pk, *mode)
+                .is_ok());
+            assert!(sig.verify(&digest, &keypair.pk, *mode).is_err());
+        }
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let keypair = KeyPair::new_random();
+        let sig = keypair.sign(b"message");
+        let other = KeyPair::new_random();
+        for mode in MODES.iter() {
+            assert!(sig.verify(b"message", &keypair.pk, *mode).is_ok());
+            assert!(sig.verify(b"message", &other.pk, *mode).is_err());
+        }
+        assert_eq!(Ed25519Signature::from_bytes(&sig.to_bytes()).unwrap(), sig);
+
+        // S + l is rejected
+        let mut bytes = sig.to_bytes();
+        let s = sig.s.to_big_int() + Ed25519Scalar::q();
+        let mut s_bytes = BigInt::to_vec(&s);
+        s_bytes.reverse();
+        bytes[32..32 + s_bytes.len()].copy_from_slice(&s_bytes);
+        assert!(Ed25519Signature::from_bytes(&bytes).is_err());
+
+        let s = serde_json::to_string(&sig).unwrap();
+        let des: Ed25519Signature = serde_json::from_str(&s).unwrap();
+        assert_eq!(des, sig);
+    }
+
+    #[test]
+    fn test_zip215() -> Result<(), ErrorKey> {
+        let identity = encode(&Ed25519Point::zero());
+        let valid_for = |pk: &PublicKey, r: &[u8; 32], msg: &[u8]| {
+            let sig = Ed25519Signature {
+                r: *r,
+                s: Ed25519Scalar::zero(),
+            };
+            MODES
+                .iter()
+                .map(|mode| sig.verify(msg, pk, *mode).is_ok())
+                .collect::<Vec<bool>>()
+        };
+
+        // non canonical encodings of the identity: y = p + 1, and x = 0 with the sign bit set
+        let mut y_non_canonical = [0xffu8; 32];
+        y_non_canonical[0] = 0xee;
+        y_non_canonical[31] = 0x7f;
+        let mut sign_bit_set = identity;
+        sign_bit_set[31] |= 0x80;
+        for bytes in [y_non_canonical, sign_bit_set].iter() {
+            let pk = PublicKey::from_bytes(bytes).unwrap();
+            assert!(pk.to_point().is_zero());
+            assert_eq!(valid_for(&pk, &identity, b"msg"), [false, false, true]);
+            let pk = PublicKey::from_bytes(&identity).unwrap();
+            assert_eq!(valid_for(&pk, bytes, b"msg"), [false, false, true]);
+        }
+
+        // a public key of order 8: the cofactored equation holds for all messages, the
+        // cofactorless one only when k = 0 mod 8
+        let torsion = Ed25519Point::decode_non_canonical(&seed(
+            "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+        ))?;
+        let eight: Ed25519Scalar = ECScalar::from(&BigInt::from(8));
+        assert!(!torsion.is_zero() && (torsion * eight).is_zero());
+        let pk = PublicKey::from_point(&torsion);
+        let results = (0..16u8)
+            .map(|msg| valid_for(&pk, &identity, &[msg]))
+            .collect::<Vec<_>>();
+        assert!(results.iter().all(|r| r[1] && r[2]));
+        assert!(results.iter().any(|r| !r[0]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let keypairs = (0..5).map(|_| KeyPair::new_random()).collect::<Vec<_>>();
+        let pks = keypairs.iter().map(|k| k.pk).collect::<Vec<_>>();
+        let msgs: Vec<&[u8]> = vec![b"0", b"1", b"2", b"3", b"4"];
+        let mut signatures = keypairs
+            .iter()
+            .zip(msgs.iter())
+            .map(|(keypair, msg)| keypair.sign(msg))
+            .collect::<Vec<_>>();
+        for mode in MODES.iter() {
+            assert!(Ed25519Signature::batch_verify(&signatures, &msgs, &pks, *mode).is_ok());
+            assert!(Ed25519Signature::batch_verify(&[], &[], &[], *mode).is_ok());
+            assert!(Ed25519Signature::batch_verify(&signatures[1..], &msgs, &pks, *mode).is_err());
+        }
+
+        signatures.swap(1, 2);
+        for mode in MODES.iter() {
+            assert!(Ed25519Signature::batch_verify(&signatures, &msgs, &pks, *mode).is_err());
+        }
+        signatures.swap(1, 2);
+
+        // Ed25519ctx signatures are not plain Ed25519 signatures
+        signatures[3] = keypairs[3].sign_ctx(msgs[3], b"foo").unwrap();
+        for mode in MODES.iter() {
+            assert!(Ed25519Signature::batch_verify(&signatures, &msgs, &pks, *mode).is_err());
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofFile {
+        #[serde(rename = "testGroups")]
+        test_groups: Vec<WycheproofGroup>,
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofGroup {
+        #[serde(rename = "publicKey")]
+        public_key: WycheproofKey,
+        tests: Vec<WycheproofTest>,
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofKey {
+        pk: String,
+    }
+
+    #[derive(Deserialize)]
+    struct WycheproofTest {
+        #[serde(rename = "tcId")]
+        tc_id: usize,
+        msg: String,
+        sig: String,
+        result: String,
+    }
+
+    #[test]
+    fn test_wycheproof() {
+        let file: WycheproofFile = serde_json::from_str(include_str!(
+            "../../../tests/data/wycheproof/ed25519_test.json"
+        ))
+        .unwrap();
+        for group in file.test_groups.iter() {
+            let pk = PublicKey::from_bytes(&hex::decode(&group.public_key.pk).unwrap()).unwrap();
+            for test in group.tests.iter() {
+                let msg = hex::decode(&test.msg).unwrap();
+                let sig = hex::decode(&test.sig).unwrap();
+                for mode in MODES.iter() {
+                    let verified = Ed25519Signature::from_bytes(&sig)
+                        .and_then(|signature| signature.verify(&msg, &pk, *mode))
+                        .is_ok();
+                    assert_eq!(verified, test.result == "valid", "test case {}", test.tc_id);
+                }
+            }
+        }
+    }
+}
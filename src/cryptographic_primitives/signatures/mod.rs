@@ -0,0 +1,30 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use std::error::Error;
+use std::fmt;
+
+pub mod bip340;
+pub mod bls;
+pub mod ecdsa;
+pub mod eddsa;
+pub mod threshold_bls;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignatureError;
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SignatureError")
+    }
+}
+
+impl Error for SignatureError {
+    fn description(&self) -> &str {
+        "Error while signing or verifying"
+    }
+}
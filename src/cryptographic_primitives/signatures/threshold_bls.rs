@@ -0,0 +1,196 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use super::bls::{BlsSignature, BlsVariant, KeyPair};
+use super::SignatureError;
+
+use crate::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::elliptic::curves::bls12_381::g1::FieldScalar;
+use crate::elliptic::curves::traits::*;
+
+/// t-of-n BLS signatures. The secret key is shared with Feldman VSS in the group of the public
+/// keys, so the commitments of the VerifiableSS give the group public key (commitments[0]) and
+/// the public key of every share (get_point_commitment). Any t + 1 parties sign the message
+/// with their shares, and the partial signatures are combined by Lagrange interpolation in the
+/// exponent into the signature of the group secret key: an ordinary BLS signature which
+/// verifies under the group public key.
+///
+/// Parties are indexed from 0 like in VerifiableSS::reconstruct, party i holds the share f(i + 1).
+pub struct KeyShare<V: BlsVariant> {
+    pub index: usize,
+    pub vss_scheme: VerifiableSS<V::PublicKey>,
+    keypair: KeyPair<V>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PartialSignature<V: BlsVariant> {
+    pub index: usize,
+    pub sigma: V::Signature,
+}
+
+impl<V: BlsVariant> KeyShare<V> {
+    /// checks the share against the commitments of the dealer
+    pub fn new(
+        index: usize,
+        share: FieldScalar,
+        vss_scheme: VerifiableSS<V::PublicKey>,
+    ) -> Result<KeyShare<V>, SignatureError> {
+        if index >= vss_scheme.parameters.share_count {
+            return Err(SignatureError);
+        }
+        let keypair = KeyPair::from_secret(share);
+        vss_scheme
+            .validate_share_public(&keypair.pk, index + 1)
+            .map_err(|_| SignatureError)?;
+        Ok(KeyShare {
+            index,
+            vss_scheme,
+            keypair,
+        })
+    }
+
+    pub fn group_public_key(&self) -> V::PublicKey {
+        self.vss_scheme.commitments[0]
+    }
+
+    pub fn partial_sign(&self, msg: &[u8]) -> PartialSignature<V> {
+        PartialSignature {
+            index: self.index,
+            sigma: self.keypair.sign(msg).sigma,
+        }
+    }
+}
+
+impl<V: BlsVariant> PartialSignature<V> {
+    /// verifies the partial signature under the public key of the share of its signer
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        vss_scheme: &VerifiableSS<V::PublicKey>,
+    ) -> Result<(), SignatureError> {
+        if self.index >= vss_scheme.parameters.share_count {
+            return Err(SignatureError);
+        }
+        let pk = vss_scheme.get_point_commitment(self.index + 1);
+        BlsSignature::<V> { sigma: self.sigma }.verify(msg, &pk)
+    }
+
+    /// combines t + 1 or more partial signatures of distinct parties. The partial signatures
+    /// are not verified, a single invalid one makes the result invalid.
+    pub fn combine(
+        partial_signatures: &[PartialSignature<V>],
+        vss_scheme: &VerifiableSS<V::PublicKey>,
+    ) -> Result<BlsSignature<V>, SignatureError> {
+        let s = partial_signatures
+            .iter()
+            .map(|partial| partial.index)
+            .collect::<Vec<usize>>();
+        let mut sorted = s.clone();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != s.len()
+            || s.len() < vss_scheme.reconstruct_limit()
+            || s.iter().any(|i| *i >= vss_scheme.parameters.share_count)
+        {
+            return Err(SignatureError);
+        }
+
+        // sigma = sum_i lambda_i * sigma_i
+        let sigmas = partial_signatures
+            .iter()
+            .map(|partial| partial.sigma)
+            .collect::<Vec<V::Signature>>();
+        let lambdas = s
+            .iter()
+            .map(|i| vss_scheme.map_share_to_new_params(*i, &s))
+            .collect::<Vec<FieldScalar>>();
+        Ok(BlsSignature {
+            sigma: V::Signature::multi_scalar_mul(&sigmas, &lambdas),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptographic_primitives::signatures::bls::{MinPk, MinSig};
+    use crate::elliptic::curves::bls12_381::g1::G1Point;
+
+    fn test_threshold_signing<V: BlsVariant>() {
+        let secret: FieldScalar = ECScalar::new_random();
+        let (vss_scheme, shares) = VerifiableSS::<V::PublicKey>::share(2, 5, &secret);
+        let key_shares = shares
+            .into_iter()
+            .enumerate()
+            .map(|(i, share)| KeyShare::<V>::new(i, share, vss_scheme.clone()).unwrap())
+            .collect::<Vec<_>>();
+        let group_pk = key_shares[0].group_public_key();
+        assert_eq!(group_pk, KeyPair::<V>::from_secret(secret).pk);
+
+        let msg = b"message";
+        let partials = [0, 2, 3]
+            .iter()
+            .map(|i| key_shares[*i].partial_sign(msg))
+            .collect::<Vec<_>>();
+        for partial in partials.iter() {
+            assert!(partial.verify(msg, &vss_scheme).is_ok());
+            assert!(partial.verify(b"other message", &vss_scheme).is_err());
+        }
+
+        let signature = PartialSignature::combine(&partials, &vss_scheme).unwrap();
+        assert!(signature.verify(msg, &group_pk).is_ok());
+        // the combined signature is the one of the group secret key
+        assert_eq!(signature, KeyPair::<V>::from_secret(secret).sign(msg));
+
+        // any t + 1 parties give the same signature
+        let partials_all = key_shares
+            .iter()
+            .rev()
+            .map(|key_share| key_share.partial_sign(msg))
+            .collect::<Vec<_>>();
+        let signature_all = PartialSignature::combine(&partials_all, &vss_scheme).unwrap();
+        assert_eq!(signature_all, signature);
+
+        // t partial signatures or a repeated party are not enough
+        assert!(PartialSignature::combine(&partials[..2], &vss_scheme).is_err());
+        let repeated = [
+            partials[0].clone(),
+            partials[1].clone(),
+            partials[1].clone(),
+        ];
+        assert!(PartialSignature::combine(&repeated, &vss_scheme).is_err());
+    }
+
+    #[test]
+    fn test_threshold_signing_min_pk() {
+        test_threshold_signing::<MinPk>();
+    }
+
+    #[test]
+    fn test_threshold_signing_min_sig() {
+        test_threshold_signing::<MinSig>();
+    }
+
+    #[test]
+    fn test_invalid_shares_are_rejected() {
+        let secret: FieldScalar = ECScalar::new_random();
+        let (vss_scheme, shares) = VerifiableSS::<G1Point>::share(1, 3, &secret);
+        assert!(KeyShare::<MinPk>::new(0, shares[1], vss_scheme.clone()).is_err());
+        assert!(KeyShare::<MinPk>::new(3, shares[0], vss_scheme.clone()).is_err());
+
+        // a partial signature made with the wrong share does not verify and spoils the result
+        let key_share = KeyShare::<MinPk>::new(1, shares[1], vss_scheme.clone()).unwrap();
+        let mut forged = key_share.partial_sign(b"message");
+        forged.index = 0;
+        assert!(forged.verify(b"message", &vss_scheme).is_err());
+        let partials = [forged, key_share.partial_sign(b"message")];
+        let signature = PartialSignature::combine(&partials, &vss_scheme).unwrap();
+        assert!(signature
+            .verify(b"message", &vss_scheme.commitments[0])
+            .is_err());
+    }
+}
@@ -35,11 +35,9 @@ where
     P::Scalar: Clone,
 {
     pub fn first() -> (Party1FirstMessage<P>, EcKeyPair<P>) {
-        let base: P = ECPoint::generator();
-
         let secret_share: P::Scalar = ECScalar::new_random();
 
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
 
         let ec_key_pair = EcKeyPair {
             public_share: public_share.clone(),
@@ -51,8 +49,7 @@ where
     pub fn first_with_fixed_secret_share(
         secret_share: P::Scalar,
     ) -> (Party1FirstMessage<P>, EcKeyPair<P>) {
-        let base: P = ECPoint::generator();
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
 
         let ec_key_pair = EcKeyPair {
             public_share: public_share.clone(),
@@ -68,9 +65,8 @@ where
     P::Scalar: Clone,
 {
     pub fn first() -> (Party2FirstMessage<P>, EcKeyPair<P>) {
-        let base: P = ECPoint::generator();
         let secret_share: P::Scalar = ECScalar::new_random();
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
         let ec_key_pair = EcKeyPair {
             public_share: public_share.clone(),
             secret_share,
@@ -81,8 +77,7 @@ where
     pub fn first_with_fixed_secret_share(
         secret_share: P::Scalar,
     ) -> (Party2FirstMessage<P>, EcKeyPair<P>) {
-        let base: P = ECPoint::generator();
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
         let ec_key_pair = EcKeyPair {
             public_share: public_share.clone(),
             secret_share,
@@ -79,11 +79,9 @@ impl Party1FirstMessage {
         P: ECPoint + Clone,
         P::Scalar: Zeroize,
     {
-        let base: P = ECPoint::generator();
-
         let secret_share: P::Scalar = ECScalar::new_random();
 
-        let public_share = base.scalar_mul(&secret_share.get_element());
+        let public_share = P::mul_base(&secret_share);
 
         let d_log_proof = DLogProof::<P>::prove(&secret_share);
         // we use hash based commitment
@@ -126,8 +124,7 @@ impl Party1FirstMessage {
         P: ECPoint + Clone,
         P::Scalar: Zeroize + Clone,
     {
-        let base: P = ECPoint::generator();
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
 
         let d_log_proof = DLogProof::<P>::prove(&secret_share);
 
@@ -184,9 +181,8 @@ where
     P::Scalar: Zeroize + Clone,
 {
     pub fn create() -> (Party2FirstMessage<P>, EcKeyPair<P>) {
-        let base: P = ECPoint::generator();
         let secret_share: P::Scalar = ECScalar::new_random();
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
         let d_log_proof = DLogProof::prove(&secret_share);
         let ec_key_pair = EcKeyPair {
             public_share: public_share.clone(),
@@ -204,8 +200,7 @@ where
     pub fn create_with_fixed_secret_share(
         secret_share: P::Scalar,
     ) -> (Party2FirstMessage<P>, EcKeyPair<P>) {
-        let base: P = ECPoint::generator();
-        let public_share = base * secret_share.clone();
+        let public_share = P::mul_base(&secret_share);
         let d_log_proof = DLogProof::prove(&secret_share);
         let ec_key_pair = EcKeyPair {
             public_share: public_share.clone(),
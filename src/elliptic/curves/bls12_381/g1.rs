@@ -11,7 +11,10 @@ use std::str;
 
 pub const SECRET_KEY_SIZE: usize = 32;
 
-use super::traits::{ECPoint, ECScalar};
+use crate::elliptic::curves::fixed_base::FixedBaseTable;
+use crate::elliptic::curves::multi_scalar_mul::multi_scalar_mul;
+use crate::elliptic::curves::hash_to_curve::{hash_to_field, Isogeny, PrimeField, SimpleSwu};
+use crate::elliptic::curves::traits::{ECPoint, ECScalar, HashToCurve};
 use crate::arithmetic::traits::Converter;
 use crate::cryptographic_primitives::hashing::hash_sha512::HSha512;
 use crate::cryptographic_primitives::hashing::traits::Hash;
@@ -25,16 +28,18 @@ use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
 use std::fmt;
 use std::ops::{Add, Mul};
 
 pub type SK = Scalar;
-// We use G1 only
+// G2 lives in g2.rs
 pub type PK = G1Affine;
 
 use crate::arithmetic::traits::Samplable;
 use crate::BigInt;
 use crate::ErrorKey::{self};
+use rand::{CryptoRng, RngCore};
 
 use std::ptr;
 use std::sync::atomic;
@@ -62,6 +67,36 @@ pub struct G1Point {
 pub type GE = G1Point;
 pub type FE = FieldScalar;
 
+lazy_static::lazy_static! {
+    static ref BASE_POINT2: G1Point = {
+        // 48 bytes
+        let g: GE = ECPoint::generator();
+        let hash = HSha512::create_hash(&[&g.bytes_compressed_to_big_int()]);
+        let hash = HSha512::create_hash(&[&hash]);
+
+        let mut bytes = BigInt::to_vec(&hash);
+        bytes[47] = 151; //Fq must be canoncial + specific flags. This byte is the same as the one from the generator.
+
+        let h: GE = G1Point::decode_unchecked(&bytes[..]).unwrap();
+        let bp2_proj: G1Projective = h.ge.into();
+        let bp2_proj_in_g1 = bp2_proj.clear_cofactor();
+        G1Point {
+            purpose: "base point 2",
+            ge: bp2_proj_in_g1.into(),
+        }
+    };
+
+    static ref GENERATOR_TABLE: FixedBaseTable<G1Projective> =
+        FixedBaseTable::new(G1Projective::generator(), G1Projective::identity(), 255);
+
+    static ref BASE_POINT2_TABLE: FixedBaseTable<G1Projective> =
+        FixedBaseTable::new(
+            G1Projective::from(BASE_POINT2.ge),
+            G1Projective::identity(),
+            255,
+        );
+}
+
 impl Zeroize for FieldScalar {
     fn zeroize(&mut self) {
         unsafe { ptr::write_volatile(self, FE::zero()) };
@@ -70,11 +105,20 @@ impl Zeroize for FieldScalar {
     }
 }
 
+impl FieldScalar {
+    // the big endian encoding, for the fixed base tables
+    pub(crate) fn to_bytes_be(&self) -> [u8; 32] {
+        let mut bytes = SK::to_bytes(&self.fe);
+        bytes.reverse();
+        bytes
+    }
+}
+
 impl ECScalar for FieldScalar {
     type SecretKey = SK;
 
-    fn new_random() -> FieldScalar {
-        let rnd_bn = BigInt::sample_below(&FE::q());
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> FieldScalar {
+        let rnd_bn = BigInt::sample_below_with_rng(&FE::q(), rng);
         ECScalar::from(&rnd_bn)
     }
 
@@ -287,6 +331,30 @@ impl G1Point {
         // let zero_s = FieldScalar::zero();
         // Self::generator() * zero_s
     }
+
+    fn to_array_48(bytes: &[u8]) -> [u8; 48] {
+        let mut bytes_array_48 = [0u8; 48];
+        match bytes.len() {
+            0..=48 => {
+                (&mut bytes_array_48[48 - bytes.len()..]).copy_from_slice(bytes);
+            }
+            _ => {
+                bytes_array_48.copy_from_slice(&bytes[..48]);
+            }
+        }
+        bytes_array_48
+    }
+
+    // decodes a point on the curve without checking that it is in G1
+    fn decode_unchecked(bytes: &[u8]) -> Result<G1Point, ErrorKey> {
+        let ge: Option<G1Affine> =
+            G1Affine::from_compressed_unchecked(&G1Point::to_array_48(bytes)).into();
+        ge.map(|ge| G1Point {
+            purpose: "random",
+            ge,
+        })
+        .ok_or(ErrorKey::InvalidPublicKey)
+    }
 }
 
 impl ECPoint for G1Point {
@@ -295,21 +363,7 @@ impl ECPoint for G1Point {
     type Scalar = FieldScalar;
 
     fn base_point2() -> G1Point {
-        // 48 bytes
-        let g: GE = ECPoint::generator();
-        let hash = HSha512::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        let hash = HSha512::create_hash(&[&hash]);
-
-        let mut bytes = BigInt::to_vec(&hash);
-        bytes[47] = 151; //Fq must be canoncial + specific flags. This byte is the same as the one from the generator.
-
-        let h: GE = ECPoint::from_bytes(&bytes[..]).unwrap();
-        let bp2_proj: G1Projective = h.ge.into();
-        let bp2_proj_in_g1 = bp2_proj.clear_cofactor();
-        G1Point {
-            purpose: "base point 2",
-            ge: bp2_proj_in_g1.into(),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> G1Point {
@@ -319,6 +373,14 @@ impl ECPoint for G1Point {
         }
     }
 
+    fn zero() -> G1Point {
+        G1Point::identity()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.ge.is_identity().into()
+    }
+
     fn get_element(&self) -> PK {
         self.ge.clone()
     }
@@ -332,7 +394,7 @@ impl ECPoint for G1Point {
 
     fn y_coor(&self) -> Option<BigInt> {
         let bytes = G1Affine::to_uncompressed(&self.ge);
-        let y_coor = &bytes[48..98];
+        let y_coor = &bytes[48..96];
         let bn = BigInt::from(y_coor);
         Some(bn)
     }
@@ -343,22 +405,15 @@ impl ECPoint for G1Point {
         bn
     }
 
+    // from_bytes returns Ok only for the encoding of a point of G1 (the sub group of prime order)
     fn from_bytes(bytes: &[u8]) -> Result<G1Point, ErrorKey> {
-        let mut bytes_array_48 = [0u8; 48];
-        match bytes.len() {
-            0..=48 => {
-                (&mut bytes_array_48[48 - bytes.len()..]).copy_from_slice(bytes);
-            }
-            _ => {
-                bytes_array_48.copy_from_slice(&bytes[..48]);
-            }
-        }
-
-        let pk = G1Point {
+        let bytes_array_48 = G1Point::to_array_48(bytes);
+        let ge: Option<G1Affine> = G1Affine::from_compressed(&bytes_array_48).into();
+        ge.map(|ge| G1Point {
             purpose: "random",
-            ge: G1Affine::from_compressed_unchecked(&bytes_array_48).unwrap(),
-        };
-        return Ok(pk);
+            ge,
+        })
+        .ok_or(ErrorKey::InvalidPublicKey)
     }
 
     // in this case the opposite of from_bytes: takes compressed pk to 48 bytes.
@@ -397,9 +452,172 @@ impl ECPoint for G1Point {
         }
     }
 
-    fn from_coor(_x: &BigInt, _y: &BigInt) -> G1Point {
-        // TODO
-        unimplemented!();
+    fn from_coor(x: &BigInt, y: &BigInt) -> G1Point {
+        let mut bytes = [0u8; 96];
+        bytes[..48].copy_from_slice(&G1Point::to_array_48(&BigInt::to_vec(x)));
+        bytes[48..].copy_from_slice(&G1Point::to_array_48(&BigInt::to_vec(y)));
+        G1Point {
+            purpose: "from_coor",
+            ge: G1Affine::from_uncompressed(&bytes).unwrap(),
+        }
+    }
+
+    // runs Straus/Pippenger on projective points to avoid an inversion per addition
+    fn multi_scalar_mul(points: &[G1Point], scalars: &[FieldScalar]) -> G1Point {
+        let points = points
+            .iter()
+            .map(|p| G1Projective::from(p.ge))
+            .collect::<Vec<G1Projective>>();
+        let scalars = scalars.iter().map(|k| k.to_big_int()).collect::<Vec<BigInt>>();
+        let res = multi_scalar_mul(&points, &scalars).unwrap_or_else(G1Projective::identity);
+        G1Point {
+            purpose: "multi_scalar_mul",
+            ge: res.into(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.ge.is_on_curve().into()
+    }
+
+    fn is_in_prime_order_subgroup(&self) -> bool {
+        self.is_valid() && bool::from(self.ge.is_torsion_free())
+    }
+
+    fn mul_base(scalar: &FieldScalar) -> G1Point {
+        let res = GENERATOR_TABLE.mul(&scalar.to_bytes_be());
+        G1Point {
+            purpose: "mul_base",
+            ge: res.into(),
+        }
+    }
+
+    fn mul_base_point2(scalar: &FieldScalar) -> G1Point {
+        let res = BASE_POINT2_TABLE.mul(&scalar.to_bytes_be());
+        G1Point {
+            purpose: "mul_base",
+            ge: res.into(),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    // simplified SWU to the curve y^2 = x^3 + A' * x + B' followed by an 11-isogeny to E1,
+    // constants from RFC 9380 section 8.8.1 and appendix E.2
+    static ref SSWU: SimpleSwu<PrimeField> = {
+        let field = PrimeField::from_hex(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        );
+        let a = BigInt::from_hex(
+            "144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d",
+        );
+        let b = BigInt::from_hex(
+            "12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0",
+        );
+        let z = BigInt::from(11);
+        let isogeny = Isogeny::from_hex(
+            &[
+                "11a05f2b1e833340b809101dd99815856b303e88a2d7005ff2627b56cdb4e2c85610c2d5f2e62d6eaeac1662734649b7",
+                "17294ed3e943ab2f0588bab22147a81c7c17e75b2f6a8417f565e33c70d1e86b4838f2a6f318c356e834eef1b3cb83bb",
+                "d54005db97678ec1d1048c5d10a9a1bce032473295983e56878e501ec68e25c958c3e3d2a09729fe0179f9dac9edcb0",
+                "1778e7166fcc6db74e0609d307e55412d7f5e4656a8dbf25f1b33289f1b330835336e25ce3107193c5b388641d9b6861",
+                "e99726a3199f4436642b4b3e4118e5499db995a1257fb3f086eeb65982fac18985a286f301e77c451154ce9ac8895d9",
+                "1630c3250d7313ff01d1201bf7a74ab5db3cb17dd952799b9ed3ab9097e68f90a0870d2dcae73d19cd13c1c66f652983",
+                "d6ed6553fe44d296a3726c38ae652bfb11586264f0f8ce19008e218f9c86b2a8da25128c1052ecaddd7f225a139ed84",
+                "17b81e7701abdbe2e8743884d1117e53356de5ab275b4db1a682c62ef0f2753339b7c8f8c8f475af9ccb5618e3f0c88e",
+                "80d3cf1f9a78fc47b90b33563be990dc43b756ce79f5574a2c596c928c5d1de4fa295f296b74e956d71986a8497e317",
+                "169b1f8e1bcfa7c42e0c37515d138f22dd2ecb803a0c5c99676314baf4bb1b7fa3190b2edc0327797f241067be390c9e",
+                "10321da079ce07e272d8ec09d2565b0dfa7dccdde6787f96d50af36003b14866f69b771f8c285decca67df3f1605fb7b",
+                "6e08c248e260e70bd1e962381edee3d31d79d7e22c837bc23c0bf1bc24c6b68c24b1b80b64d391fa9c8ba2e8ba2d229",
+            ],
+            &[
+                "8ca8d548cff19ae18b2e62f4bd3fa6f01d5ef4ba35b48ba9c9588617fc8ac62b558d681be343df8993cf9fa40d21b1c",
+                "12561a5deb559c4348b4711298e536367041e8ca0cf0800c0126c2588c48bf5713daa8846cb026e9e5c8276ec82b3bff",
+                "b2962fe57a3225e8137e629bff2991f6f89416f5a718cd1fca64e00b11aceacd6a3d0967c94fedcfcc239ba5cb83e19",
+                "3425581a58ae2fec83aafef7c40eb545b08243f16b1655154cca8abc28d6fd04976d5243eecf5c4130de8938dc62cd8",
+                "13a8e162022914a80a6f1d5f43e7a07dffdfc759a12062bb8d6b44e833b306da9bd29ba81f35781d539d395b3532a21e",
+                "e7355f8e4e667b955390f7f0506c6e9395735e9ce9cad4d0a43bcef24b8982f7400d24bc4228f11c02df9a29f6304a5",
+                "772caacf16936190f3e0c63e0596721570f5799af53a1894e2e073062aede9cea73b3538f0de06cec2574496ee84a3a",
+                "14a7ac2a9d64a8b230b3f5b074cf01996e7f63c21bca68a81996e1cdf9822c580fa5b9489d11e2d311f7d99bbdcc5a5e",
+                "a10ecf6ada54f825e920b3dafc7a3cce07f8d1d7161366b74100da67f39883503826692abba43704776ec3a79a1d641",
+                "95fc13ab9e92ad4476d6e3eb3a56680f682b4ee96f7d03776df533978f31c1593174e4b4b7865002d6384d168ecdd0a",
+                "1",
+            ],
+            &[
+                "90d97c81ba24ee0259d1f094980dcfa11ad138e48a869522b52af6c956543d3cd0c7aee9b3ba3c2be9845719707bb33",
+                "134996a104ee5811d51036d776fb46831223e96c254f383d0f906343eb67ad34d6c56711962fa8bfe097e75a2e41c696",
+                "cc786baa966e66f4a384c86a3b49942552e2d658a31ce2c344be4b91400da7d26d521628b00523b8dfe240c72de1f6",
+                "1f86376e8981c217898751ad8746757d42aa7b90eeb791c09e4a3ec03251cf9de405aba9ec61deca6355c77b0e5f4cb",
+                "8cc03fdefe0ff135caf4fe2a21529c4195536fbe3ce50b879833fd221351adc2ee7f8dc099040a841b6daecf2e8fedb",
+                "16603fca40634b6a2211e11db8f0a6a074a7d0d4afadb7bd76505c3d3ad5544e203f6326c95a807299b23ab13633a5f0",
+                "4ab0b9bcfac1bbcb2c977d027796b3ce75bb8ca2be184cb5231413c4d634f3747a87ac2460f415ec961f8855fe9d6f2",
+                "987c8d5333ab86fde9926bd2ca6c674170a05bfe3bdd81ffd038da6c26c842642f64550fedfe935a15e4ca31870fb29",
+                "9fc4018bd96684be88c9e221e4da1bb8f3abd16679dc26c1e8b6e6a1f20cabe69d65201c78607a360370e577bdba587",
+                "e1bba7a1186bdb5223abde7ada14a23c42a0ca7915af6fe06985e7ed1e4d43b9b3f7055dd4eba6f2bafaaebca731c30",
+                "19713e47937cd1be0dfd0b8f1d43fb93cd2fcbcb6caf493fd1183e416389e61031bf3a5cce3fbafce813711ad011c132",
+                "18b46a908f36f6deb918c143fed2edcc523559b8aaf0c2462e6bfe7f911f643249d9cdf41b44d606ce07c8a4d0074d8e",
+                "b182cac101b9399d155096004f53f447aa7b12a3426b08ec02710e807b4633f06c851c1919211f20d4c04f00b971ef8",
+                "245a394ad1eca9b72fc00ae7be315dc757b3b080d4c158013e6632d3c40659cc6cf90ad1c232a6442d9d3f5db980133",
+                "5c129645e44cf1102a159f748c4a3fc5e673d81d7e86568d9ab0f5d396a7ce46ba1049b6579afb7866b1e715475224b",
+                "15e6be4e990f03ce4ea50b3b42df2eb5cb181d8f84965a3957add4fa95af01b2b665027efec01c7704b456be69c8b604",
+            ],
+            &[
+                "16112c4c3a9c98b252181140fad0eae9601a6de578980be6eec3232b5be72e7a07f3688ef60c206d01479253b03663c1",
+                "1962d75c2381201e1a0cbd6c43c348b885c84ff731c4d59ca4a10356f453e01f78a4260763529e3532f6102c2e49a03d",
+                "58df3306640da276faaae7d6e8eb15778c4855551ae7f310c35a5dd279cd2eca6757cd636f96f891e2538b53dbf67f2",
+                "16b7d288798e5395f20d23bf89edb4d1d115c5dbddbcd30e123da489e726af41727364f2c28297ada8d26d98445f5416",
+                "be0e079545f43e4b00cc912f8228ddcc6d19c9f0f69bbb0542eda0fc9dec916a20b15dc0fd2ededda39142311a5001d",
+                "8d9e5297186db2d9fb266eaac783182b70152c65550d881c5ecd87b6f0f5a6449f38db9dfa9cce202c6477faaf9b7ac",
+                "166007c08a99db2fc3ba8734ace9824b5eecfdfa8d0cf8ef5dd365bc400a0051d5fa9c01a58b1fb93d1a1399126a775c",
+                "16a3ef08be3ea7ea03bcddfabba6ff6ee5a4375efa1f4fd7feb34fd206357132b920f5b00801dee460ee415a15812ed9",
+                "1866c8ed336c61231a1be54fd1d74cc4f9fb0ce4c6af5920abc5750c4bf39b4852cfe2f7bb9248836b233d9d55535d4a",
+                "167a55cda70a6e1cea820597d94a84903216f763e13d87bb5308592e7ea7d4fbc7385ea3d529b35e346ef48bb8913f55",
+                "4d2f259eea405bd48f010a01ad2911d9c6dd039bb61a6290e591b36e636a5c871a5c29f4f83060400f8b49cba8f6aa8",
+                "accbb67481d033ff5852c1e48c50c477f94ff8aefce42d28c0f9a88cea7913516f968986f7ebbea9684b529e2561092",
+                "ad6b9514c767fe3c3613144b45f1496543346d98adf02267d5ceef9a00d9b8693000763e3b90ac11e99b138573345cc",
+                "2660400eb2e4f3b628bdd0d53cd76f2bf565b94e72927c1cb748df27942480e420517bd8714cc80d1fadc1326ed06f7",
+                "e0fa1d816ddc03e6b24255e0d7819c171c40f65e273b853324efcd6356caa205ca2f570f13497804415473a1d634b8f",
+                "1",
+            ],
+        );
+        SimpleSwu::new(field, a, b, z).with_isogeny(isogeny)
+    };
+}
+
+impl G1Point {
+    // returns the point before clearing the cofactor
+    fn map_to_curve(u: &BigInt) -> G1Projective {
+        let (x, y) = match SSWU.map_to_curve(u) {
+            Some(point) => point,
+            None => return G1Projective::identity(),
+        };
+        let mut bytes = [0u8; 96];
+        for (coor, buffer) in [x, y].iter().zip(bytes.chunks_mut(48)) {
+            let coor_vec = BigInt::to_vec(coor);
+            buffer[48 - coor_vec.len()..].copy_from_slice(&coor_vec);
+        }
+        G1Projective::from(G1Affine::from_uncompressed_unchecked(&bytes).unwrap())
+    }
+}
+
+impl HashToCurve for G1Point {
+    const SUITE_ID: &'static str = "BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, 2, SSWU.field.p(), 64);
+        let q = G1Point::map_to_curve(&u[0]) + G1Point::map_to_curve(&u[1]);
+        G1Point {
+            purpose: "hash_to_curve",
+            ge: q.clear_cofactor().into(),
+        }
+    }
+
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> G1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, 1, SSWU.field.p(), 64);
+        G1Point {
+            purpose: "encode_to_curve",
+            ge: G1Point::map_to_curve(&u[0]).clear_cofactor().into(),
+        }
     }
 }
 
@@ -494,7 +712,7 @@ impl<'de> Visitor<'de> for JubjubPointVisitor {
             .ok_or_else(|| panic!("deserialization failed"))?;
         let bytes_bn = BigInt::from_hex(bytes_str);
         let bytes = BigInt::to_vec(&bytes_bn);
-        Ok(G1Point::from_bytes(&bytes[..]).expect("error deserializing point"))
+        G1Point::from_bytes(&bytes[..]).map_err(|_| de::Error::custom("invalid G1Point"))
     }
 
     fn visit_map<E: MapAccess<'de>>(self, mut map: E) -> Result<G1Point, E::Error> {
@@ -512,16 +730,19 @@ impl<'de> Visitor<'de> for JubjubPointVisitor {
         let bytes_bn = BigInt::from_hex(&bytes_str);
         let bytes = BigInt::to_vec(&bytes_bn);
 
-        Ok(G1Point::from_bytes(&bytes[..]).expect("error deserializing point"))
+        G1Point::from_bytes(&bytes[..]).map_err(|_| de::Error::custom("invalid G1Point"))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{FieldScalar, G1Point};
-    use crate::arithmetic::traits::Modulo;
+    use crate::arithmetic::traits::{Converter, Modulo};
+    use crate::cryptographic_primitives::hashing::hash_sha512::HSha512;
+    use crate::cryptographic_primitives::hashing::traits::Hash;
     use crate::elliptic::curves::traits::ECPoint;
     use crate::elliptic::curves::traits::ECScalar;
+    use crate::elliptic::curves::traits::HashToCurve;
     use crate::BigInt;
     use bincode;
     use serde_json;
@@ -563,6 +784,35 @@ mod tests {
         assert_eq!(des_pk, pk * &eight);
     }
 
+    #[test]
+    fn test_from_bytes_rejects_points_outside_g1() {
+        // base_point2 before clearing the cofactor: a point on the curve which is not in G1
+        let g = GE::generator();
+        let hash = HSha512::create_hash(&[&g.bytes_compressed_to_big_int()]);
+        let hash = HSha512::create_hash(&[&hash]);
+        let mut bytes = BigInt::to_vec(&hash);
+        bytes[47] = 151;
+        let p = G1Point::decode_unchecked(&bytes[..]).unwrap();
+        assert!(p.is_valid());
+        assert!(!p.is_in_prime_order_subgroup());
+        assert!(G1Point::from_bytes(&p.pk_to_key_slice()).is_err());
+
+        let identity = GE::identity();
+        assert!(identity.is_in_prime_order_subgroup());
+        assert!(G1Point::from_bytes(&identity.pk_to_key_slice()).unwrap().is_zero());
+
+        assert!(g.is_in_prime_order_subgroup());
+        assert_eq!(G1Point::from_bytes(&g.pk_to_key_slice()).unwrap(), g);
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let a: FE = ECScalar::new_random();
+        let p = GE::generator() * a;
+        let q = GE::from_coor(&p.x_coor().unwrap(), &p.y_coor().unwrap());
+        assert_eq!(p, q);
+    }
+
     #[test]
     fn test_from_mpz() {
         let rand_scalar: FE = ECScalar::new_random();
@@ -693,4 +943,44 @@ mod tests {
         let new_i = &i + &s;
         assert_eq!(new_i,s);
     }
+
+    // RFC 9380 appendices J.9.1 and J.9.2
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1\
+                 08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+            ),
+            (
+                b"abc",
+                "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903\
+                 0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+            ),
+        ];
+        for (msg, expected) in vectors.iter() {
+            let p = G1Point::hash_to_curve(msg, dst);
+            assert_eq!(hex::encode(&p.ge.to_uncompressed()[..]), *expected);
+        }
+
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_NU_";
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "184bb665c37ff561a89ec2122dd343f20e0f4cbcaec84e3c3052ea81d1834e192c426074b02ed3dca4e7676ce4ce48ba\
+                 04407b8d35af4dacc809927071fc0405218f1401a6d15af775810e4e460064bcc9468beeba82fdc751be70476c888bf3",
+            ),
+            (
+                b"abc",
+                "009769f3ab59bfd551d53a5f846b9984c59b97d6842b20a2c565baa167945e3d026a3755b6345df8ec7e6acb6868ae6d\
+                 1532c00cf61aa3d0ce3e5aa20c3b531a2abd2c770a790a2613818303c6b830ffc0ecf6c357af3317b9575c567f11cd2c",
+            ),
+        ];
+        for (msg, expected) in vectors.iter() {
+            let p = G1Point::encode_to_curve(msg, dst);
+            assert_eq!(hex::encode(&p.ge.to_uncompressed()[..]), *expected);
+        }
+    }
 }
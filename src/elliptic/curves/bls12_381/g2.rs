@@ -0,0 +1,648 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// G2 of BLS12-381: the points of the sextic twist over Fp2 in the sub group of prime order q.
+// It shares its scalar field with G1, see g1.rs for FieldScalar.
+use std::fmt::Debug;
+
+use super::g1::FieldScalar;
+use crate::arithmetic::traits::Converter;
+use crate::cryptographic_primitives::hashing::hash_sha512::HSha512;
+use crate::cryptographic_primitives::hashing::traits::Hash;
+use crate::elliptic::curves::fixed_base::FixedBaseTable;
+use crate::elliptic::curves::hash_to_curve::{
+    hash_to_field, Isogeny, PrimeField, QuadraticField, SimpleSwu,
+};
+use crate::elliptic::curves::multi_scalar_mul::multi_scalar_mul;
+use crate::elliptic::curves::traits::{ECPoint, ECScalar, HashToCurve};
+
+use bls12_381::G2Affine;
+use bls12_381::G2Projective;
+use bls12_381::Scalar;
+
+use serde::de;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::ser::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+pub type SK = Scalar;
+pub type PK = G2Affine;
+
+use crate::BigInt;
+use crate::ErrorKey::{self};
+
+use std::ptr;
+use std::sync::atomic;
+use zeroize::Zeroize;
+
+#[cfg(feature = "merkle")]
+use crypto::digest::Digest;
+#[cfg(feature = "merkle")]
+use crypto::sha3::Sha3;
+#[cfg(feature = "merkle")]
+use merkle::Hashable;
+
+const COMPRESSED_SIZE: usize = 96;
+
+#[derive(Clone, Copy)]
+pub struct G2Point {
+    purpose: &'static str,
+    ge: PK,
+}
+
+pub type GE = G2Point;
+pub type FE = FieldScalar;
+
+lazy_static::lazy_static! {
+    static ref BASE_POINT2: G2Point = {
+        let h = G2Point::base_point2_candidate();
+        let bp2_proj: G2Projective = h.ge.into();
+        G2Point {
+            purpose: "base point 2",
+            ge: bp2_proj.clear_cofactor().into(),
+        }
+    };
+
+    static ref GENERATOR_TABLE: FixedBaseTable<G2Projective> =
+        FixedBaseTable::new(G2Projective::generator(), G2Projective::identity(), 255);
+
+    static ref BASE_POINT2_TABLE: FixedBaseTable<G2Projective> =
+        FixedBaseTable::new(
+            G2Projective::from(BASE_POINT2.ge),
+            G2Projective::identity(),
+            255,
+        );
+
+    // simplified SWU to the curve y^2 = x^3 + A' * x + B' followed by a 3-isogeny to E2,
+    // constants from RFC 9380 section 8.8.2 and appendix E.3
+    static ref SSWU: SimpleSwu<QuadraticField> = {
+        let field = QuadraticField::new(PrimeField::from_hex(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        ));
+        let a = (BigInt::zero(), BigInt::from(240));
+        let b = (BigInt::from(1012), BigInt::from(1012));
+        let z = (
+            field.base().p() - BigInt::from(2),
+            field.base().p() - BigInt::one(),
+        );
+        let isogeny = Isogeny::from_hex_pairs(
+            &[
+                (
+                    "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+                    "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+                ),
+                (
+                    "0",
+                    "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a",
+                ),
+                (
+                    "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e",
+                    "8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d",
+                ),
+                (
+                    "171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1",
+                    "0",
+                ),
+            ],
+            &[
+                ("0", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63"),
+                ("c", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f"),
+                ("1", "0"),
+            ],
+            &[
+                (
+                    "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+                    "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+                ),
+                (
+                    "0",
+                    "5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be",
+                ),
+                (
+                    "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c",
+                    "8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f",
+                ),
+                (
+                    "124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10",
+                    "0",
+                ),
+            ],
+            &[
+                ("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb"),
+                ("0", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3"),
+                ("12", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99"),
+                ("1", "0"),
+            ],
+        );
+        SimpleSwu::new(field, a, b, z).with_isogeny(isogeny)
+    };
+}
+
+impl Debug for G2Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Point {{ purpose: {:?}, bytes: {:?} }}",
+            self.purpose,
+            self.bytes_compressed_to_big_int().to_str_radix(16)
+        )
+    }
+}
+
+impl PartialEq for G2Point {
+    fn eq(&self, other: &G2Point) -> bool {
+        self.get_element() == other.get_element()
+    }
+}
+
+impl Zeroize for G2Point {
+    fn zeroize(&mut self) {
+        unsafe { ptr::write_volatile(self, GE::generator()) };
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl G2Point {
+    pub fn identity() -> Self {
+        G2Point {
+            purpose: "identity",
+            ge: PK::identity(),
+        }
+    }
+
+    fn to_array_96(bytes: &[u8]) -> [u8; COMPRESSED_SIZE] {
+        let mut bytes_array_96 = [0u8; COMPRESSED_SIZE];
+        match bytes.len() {
+            0..=COMPRESSED_SIZE => {
+                (&mut bytes_array_96[COMPRESSED_SIZE - bytes.len()..]).copy_from_slice(bytes);
+            }
+            _ => {
+                bytes_array_96.copy_from_slice(&bytes[..COMPRESSED_SIZE]);
+            }
+        }
+        bytes_array_96
+    }
+
+    // base_point2 before clearing the cofactor: x = c1 || c0 is taken from a chain of SHA512
+    // hashes starting at the generator, until it is the x coordinate of a point of the twist.
+    fn base_point2_candidate() -> G2Point {
+        let g: GE = ECPoint::generator();
+        let mut hash = HSha512::create_hash(&[&g.bytes_compressed_to_big_int()]);
+        loop {
+            let next_hash = HSha512::create_hash(&[&hash]);
+            let mut bytes = Vec::with_capacity(COMPRESSED_SIZE);
+            for h in [&hash, &next_hash].iter() {
+                let h_vec = BigInt::to_vec(h);
+                let mut h_bytes = vec![0u8; 64 - h_vec.len()];
+                h_bytes.extend_from_slice(&h_vec);
+                bytes.extend_from_slice(&h_bytes[..48]);
+            }
+            // c1 and c0 must be smaller than p, which starts with 0x1a, and the compression flag
+            // must be set
+            bytes[0] &= 0x0f;
+            bytes[48] &= 0x0f;
+            bytes[0] |= 0x80;
+            if let Ok(h) = G2Point::decode_unchecked(&bytes) {
+                return h;
+            }
+            hash = next_hash;
+        }
+    }
+
+    // decodes a point on the twist without checking that it is in G2
+    fn decode_unchecked(bytes: &[u8]) -> Result<G2Point, ErrorKey> {
+        let ge: Option<G2Affine> =
+            G2Affine::from_compressed_unchecked(&G2Point::to_array_96(bytes)).into();
+        ge.map(|ge| G2Point {
+            purpose: "random",
+            ge,
+        })
+        .ok_or(ErrorKey::InvalidPublicKey)
+    }
+}
+
+impl ECPoint for G2Point {
+    type SecretKey = SK;
+    type PublicKey = PK;
+    type Scalar = FieldScalar;
+
+    fn base_point2() -> G2Point {
+        *BASE_POINT2
+    }
+
+    fn generator() -> G2Point {
+        G2Point {
+            purpose: "base_fe",
+            ge: G2Affine::generator(),
+        }
+    }
+
+    fn zero() -> G2Point {
+        G2Point::identity()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.ge.is_identity().into()
+    }
+
+    fn get_element(&self) -> PK {
+        self.ge
+    }
+
+    // the coordinates are elements of Fp2 = Fp[u], returned as c1 * 2^384 + c0
+    fn x_coor(&self) -> Option<BigInt> {
+        let bytes = G2Affine::to_uncompressed(&self.ge);
+        Some(BigInt::from(&bytes[0..96]))
+    }
+
+    fn y_coor(&self) -> Option<BigInt> {
+        let bytes = G2Affine::to_uncompressed(&self.ge);
+        Some(BigInt::from(&bytes[96..192]))
+    }
+
+    fn bytes_compressed_to_big_int(&self) -> BigInt {
+        let bytes = self.ge.to_compressed();
+        BigInt::from(&bytes[..])
+    }
+
+    // from_bytes returns Ok only for the encoding of a point of G2 (the sub group of prime order)
+    fn from_bytes(bytes: &[u8]) -> Result<G2Point, ErrorKey> {
+        let bytes_array_96 = G2Point::to_array_96(bytes);
+        let ge: Option<G2Affine> = G2Affine::from_compressed(&bytes_array_96).into();
+        ge.map(|ge| G2Point {
+            purpose: "random",
+            ge,
+        })
+        .ok_or(ErrorKey::InvalidPublicKey)
+    }
+
+    // the compressed encoding of the point, 96 bytes
+    fn pk_to_key_slice(&self) -> Vec<u8> {
+        self.ge.to_compressed().to_vec()
+    }
+
+    fn scalar_mul(&self, fe: &SK) -> G2Point {
+        let res = &self.ge * fe;
+        G2Point {
+            purpose: "scalar_point_mul",
+            ge: res.into(),
+        }
+    }
+
+    fn add_point(&self, other: &PK) -> G2Point {
+        let ge_proj: G2Projective = self.ge.into();
+        let res = other + &ge_proj;
+        G2Point {
+            purpose: "combine",
+            ge: res.into(),
+        }
+    }
+
+    fn sub_point(&self, other: &PK) -> G2Point {
+        let ge_proj: G2Projective = self.ge.into();
+        let res = &ge_proj - other;
+        G2Point {
+            purpose: "sub",
+            ge: res.into(),
+        }
+    }
+
+    fn from_coor(x: &BigInt, y: &BigInt) -> G2Point {
+        let mut bytes = [0u8; 192];
+        bytes[..96].copy_from_slice(&G2Point::to_array_96(&BigInt::to_vec(x)));
+        bytes[96..].copy_from_slice(&G2Point::to_array_96(&BigInt::to_vec(y)));
+        G2Point {
+            purpose: "from_coor",
+            ge: G2Affine::from_uncompressed(&bytes).unwrap(),
+        }
+    }
+
+    // runs Straus/Pippenger on projective points to avoid an inversion per addition
+    fn multi_scalar_mul(points: &[G2Point], scalars: &[FieldScalar]) -> G2Point {
+        let points = points
+            .iter()
+            .map(|p| G2Projective::from(p.ge))
+            .collect::<Vec<G2Projective>>();
+        let scalars = scalars
+            .iter()
+            .map(|k| k.to_big_int())
+            .collect::<Vec<BigInt>>();
+        let res = multi_scalar_mul(&points, &scalars).unwrap_or_else(G2Projective::identity);
+        G2Point {
+            purpose: "multi_scalar_mul",
+            ge: res.into(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.ge.is_on_curve().into()
+    }
+
+    fn is_in_prime_order_subgroup(&self) -> bool {
+        self.is_valid() && bool::from(self.ge.is_torsion_free())
+    }
+
+    fn mul_base(scalar: &FieldScalar) -> G2Point {
+        let res = GENERATOR_TABLE.mul(&scalar.to_bytes_be());
+        G2Point {
+            purpose: "mul_base",
+            ge: res.into(),
+        }
+    }
+
+    fn mul_base_point2(scalar: &FieldScalar) -> G2Point {
+        let res = BASE_POINT2_TABLE.mul(&scalar.to_bytes_be());
+        G2Point {
+            purpose: "mul_base",
+            ge: res.into(),
+        }
+    }
+}
+
+impl G2Point {
+    // returns the point before clearing the cofactor
+    fn map_to_curve(u: &(BigInt, BigInt)) -> G2Projective {
+        let (x, y) = match SSWU.map_to_curve(u) {
+            Some(point) => point,
+            None => return G2Projective::identity(),
+        };
+        // the uncompressed encoding is x.c1 || x.c0 || y.c1 || y.c0
+        let mut bytes = [0u8; 192];
+        let coors = [x.1, x.0, y.1, y.0];
+        for (coor, buffer) in coors.iter().zip(bytes.chunks_mut(48)) {
+            let coor_vec = BigInt::to_vec(coor);
+            buffer[48 - coor_vec.len()..].copy_from_slice(&coor_vec);
+        }
+        G2Projective::from(G2Affine::from_uncompressed_unchecked(&bytes).unwrap())
+    }
+
+    fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Vec<(BigInt, BigInt)> {
+        let u = hash_to_field::<Sha256>(msg, dst, 2 * count, SSWU.field.base().p(), 64);
+        u.chunks(2).map(|c| (c[0].clone(), c[1].clone())).collect()
+    }
+}
+
+impl HashToCurve for G2Point {
+    const SUITE_ID: &'static str = "BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G2Point {
+        let u = G2Point::hash_to_field(msg, dst, 2);
+        let q = G2Point::map_to_curve(&u[0]) + G2Point::map_to_curve(&u[1]);
+        G2Point {
+            purpose: "hash_to_curve",
+            ge: q.clear_cofactor().into(),
+        }
+    }
+
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> G2Point {
+        let u = G2Point::hash_to_field(msg, dst, 1);
+        G2Point {
+            purpose: "encode_to_curve",
+            ge: G2Point::map_to_curve(&u[0]).clear_cofactor().into(),
+        }
+    }
+}
+
+impl Mul<FieldScalar> for G2Point {
+    type Output = G2Point;
+    fn mul(self, other: FieldScalar) -> G2Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for G2Point {
+    type Output = G2Point;
+    fn mul(self, other: &'o FieldScalar) -> G2Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for &'o G2Point {
+    type Output = G2Point;
+    fn mul(self, other: &'o FieldScalar) -> G2Point {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl Add<G2Point> for G2Point {
+    type Output = G2Point;
+    fn add(self, other: G2Point) -> G2Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o G2Point> for G2Point {
+    type Output = G2Point;
+    fn add(self, other: &'o G2Point) -> G2Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o G2Point> for &'o G2Point {
+    type Output = G2Point;
+    fn add(self, other: &'o G2Point) -> G2Point {
+        self.add_point(&other.get_element())
+    }
+}
+
+#[cfg(feature = "merkle")]
+impl Hashable for G2Point {
+    fn update_context(&self, context: &mut Sha3) {
+        let bytes: Vec<u8> = self.pk_to_key_slice();
+        context.input(&bytes[..]);
+    }
+}
+
+impl Serialize for G2Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.pk_to_key_slice();
+        let bytes_as_bn = BigInt::from(&bytes[..]);
+        let mut state = serializer.serialize_struct("G2Point", 1)?;
+        state.serialize_field("bytes_str", &bytes_as_bn.to_hex())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for G2Point {
+    fn deserialize<D>(deserializer: D) -> Result<G2Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["bytes_str"];
+        deserializer.deserialize_struct("G2Point", FIELDS, G2PointVisitor)
+    }
+}
+
+struct G2PointVisitor;
+
+impl<'de> Visitor<'de> for G2PointVisitor {
+    type Value = G2Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("G2Point")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<G2Point, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let bytes_str = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &"a single element"))?;
+        let bytes_bn = BigInt::from_hex(bytes_str);
+        let bytes = BigInt::to_vec(&bytes_bn);
+        G2Point::from_bytes(&bytes[..]).map_err(|_| de::Error::custom("invalid G2Point"))
+    }
+
+    fn visit_map<E: MapAccess<'de>>(self, mut map: E) -> Result<G2Point, E::Error> {
+        let mut bytes_str: String = "".to_string();
+
+        while let Some(key) = map.next_key::<&'de str>()? {
+            let v = map.next_value::<&'de str>()?;
+            match key {
+                "bytes_str" => {
+                    bytes_str = String::from(v);
+                }
+                _ => return Err(de::Error::unknown_field(key, &["bytes_str"])),
+            }
+        }
+        let bytes_bn = BigInt::from_hex(&bytes_str);
+        let bytes = BigInt::to_vec(&bytes_bn);
+
+        G2Point::from_bytes(&bytes[..]).map_err(|_| de::Error::custom("invalid G2Point"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::G2Point;
+    use crate::elliptic::curves::bls12_381::g1::FieldScalar;
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar, HashToCurve};
+
+    type GE = G2Point;
+    type FE = FieldScalar;
+
+    #[test]
+    fn test_serdes_pk() {
+        let pk = GE::generator();
+        let s = serde_json::to_string(&pk).expect("Failed in serialization");
+        let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_pk, pk);
+
+        let pk = GE::base_point2();
+        let bin = bincode::serialize(&pk).unwrap();
+        let des_pk: GE = bincode::deserialize(bin.as_slice()).unwrap();
+        assert_eq!(des_pk, pk);
+    }
+
+    #[test]
+    fn test_base_point2() {
+        let base_point2 = GE::base_point2();
+        assert!(base_point2.is_in_prime_order_subgroup());
+        assert!(!base_point2.is_zero());
+        assert!(base_point2 != GE::generator());
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_points_outside_g2() {
+        // base_point2 before clearing the cofactor: a point of the twist which is not in G2
+        let p = G2Point::base_point2_candidate();
+        assert!(p.is_valid());
+        assert!(!p.is_in_prime_order_subgroup());
+        assert!(G2Point::from_bytes(&p.pk_to_key_slice()).is_err());
+
+        let identity = GE::identity();
+        assert!(identity.is_in_prime_order_subgroup());
+        assert!(G2Point::from_bytes(&identity.pk_to_key_slice())
+            .unwrap()
+            .is_zero());
+
+        let g = GE::generator();
+        assert!(g.is_in_prime_order_subgroup());
+        assert_eq!(G2Point::from_bytes(&g.pk_to_key_slice()).unwrap(), g);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        let g = GE::generator();
+
+        let a_plus_b = a.clone() + b.clone();
+        assert_eq!(&g * &a_plus_b, (&g * &a) + (&g * &b));
+        assert_eq!(GE::mul_base(&a), &g * &a);
+        assert_eq!(GE::mul_base_point2(&a), &GE::base_point2() * &a);
+        assert!((&g * &a).sub_point(&(&g * &a).get_element()).is_zero());
+        assert_eq!(
+            GE::multi_scalar_mul(&[g, GE::base_point2()], &[a.clone(), b.clone()]),
+            (&g * &a) + (&GE::base_point2() * &b)
+        );
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let a: FE = ECScalar::new_random();
+        let p = GE::generator() * a;
+        let q = GE::from_coor(&p.x_coor().unwrap(), &p.y_coor().unwrap());
+        assert_eq!(p, q);
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d\
+                 0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a\
+                 12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6\
+                 0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+            ),
+            (
+                b"abc",
+                "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8\
+                 02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6\
+                 00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16\
+                 1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+            ),
+        ];
+        for (msg, expected) in vectors.iter() {
+            let p = G2Point::hash_to_curve(msg, dst);
+            assert_eq!(hex::encode(&p.ge.to_uncompressed()[..]), *expected);
+        }
+
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_NU_";
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "126b855e9e69b1f691f816e48ac6977664d24d99f8724868a184186469ddfd4617367e94527d4b74fc86413483afb35b\
+                 00e7f4568a82b4b7dc1f14c6aaa055edf51502319c723c4dc2688c7fe5944c213f510328082396515734b6612c4e7bb7\
+                 1498aadcf7ae2b345243e281ae076df6de84455d766ab6fcdaad71fab60abb2e8b980a440043cd305db09d283c895e3d\
+                 0caead0fd7b6176c01436833c79d305c78be307da5f6af6c133c47311def6ff1e0babf57a0fb5539fce7ee12407b0a42",
+            ),
+            (
+                b"abc",
+                "0296238ea82c6d4adb3c838ee3cb2346049c90b96d602d7bb1b469b905c9228be25c627bffee872def773d5b2a2eb57d\
+                 108ed59fd9fae381abfd1d6bce2fd2fa220990f0f837fa30e0f27914ed6e1454db0d1ee957b219f61da6ff8be0d6441f\
+                 153606c417e59fb331b7ae6bce4fbf7c5190c33ce9402b5ebe2b70e44fca614f3f1382a3625ed5493843d0b0a652fc3f\
+                 033f90f6057aadacae7963b0a0b379dd46750c1c94a6357c99b65f63b79e321ff50fe3053330911c56b6ceea08fee656",
+            ),
+        ];
+        for (msg, expected) in vectors.iter() {
+            let p = G2Point::encode_to_curve(msg, dst);
+            assert_eq!(hex::encode(&p.ge.to_uncompressed()[..]), *expected);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// BLS12-381: the groups G1 (g1.rs) and G2 (g2.rs) and the optimal Ate pairing from G1 x G2 to
+// Gt (pairing.rs). G1 is the default group of the curve: its types are re-exported here.
+pub mod g1;
+pub mod g2;
+pub mod pairing;
+
+pub use self::g1::*;
@@ -0,0 +1,221 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// The optimal Ate pairing of BLS12-381 and its target group Gt, the sub group of order q of the
+// multiplicative group of Fp12. Like the bls12_381 crate we write Gt additively.
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::g1::{FieldScalar, G1Point};
+use super::g2::G2Point;
+use crate::elliptic::curves::traits::{ECPoint, ECScalar, Pairing};
+
+use bls12_381::{multi_miller_loop, G2Prepared};
+
+#[derive(Clone, Copy)]
+pub struct Gt {
+    purpose: &'static str,
+    value: bls12_381::Gt,
+}
+
+impl Gt {
+    pub fn identity() -> Gt {
+        Gt {
+            purpose: "identity",
+            value: bls12_381::Gt::identity(),
+        }
+    }
+
+    /// e(g1, g2) for the generators of G1 and G2, it generates Gt.
+    pub fn generator() -> Gt {
+        Bls12_381::pairing(&G1Point::generator(), &G2Point::generator())
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.value == bls12_381::Gt::identity()
+    }
+
+    pub fn scalar_mul(&self, fe: &FieldScalar) -> Gt {
+        Gt {
+            purpose: "scalar_mul",
+            value: self.value * fe.get_element(),
+        }
+    }
+}
+
+impl fmt::Debug for Gt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Gt {{ purpose: {:?}, value: {:?} }}",
+            self.purpose, self.value
+        )
+    }
+}
+
+impl PartialEq for Gt {
+    fn eq(&self, other: &Gt) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Add<Gt> for Gt {
+    type Output = Gt;
+    fn add(self, other: Gt) -> Gt {
+        Gt {
+            purpose: "add",
+            value: self.value + other.value,
+        }
+    }
+}
+
+impl<'o> Add<&'o Gt> for Gt {
+    type Output = Gt;
+    fn add(self, other: &'o Gt) -> Gt {
+        self + *other
+    }
+}
+
+impl Sub<Gt> for Gt {
+    type Output = Gt;
+    fn sub(self, other: Gt) -> Gt {
+        Gt {
+            purpose: "sub",
+            value: self.value - other.value,
+        }
+    }
+}
+
+impl<'o> Sub<&'o Gt> for Gt {
+    type Output = Gt;
+    fn sub(self, other: &'o Gt) -> Gt {
+        self - *other
+    }
+}
+
+impl Neg for Gt {
+    type Output = Gt;
+    fn neg(self) -> Gt {
+        Gt {
+            purpose: "neg",
+            value: -self.value,
+        }
+    }
+}
+
+impl Mul<FieldScalar> for Gt {
+    type Output = Gt;
+    fn mul(self, other: FieldScalar) -> Gt {
+        self.scalar_mul(&other)
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for Gt {
+    type Output = Gt;
+    fn mul(self, other: &'o FieldScalar) -> Gt {
+        self.scalar_mul(other)
+    }
+}
+
+impl<'o> Mul<&'o FieldScalar> for &'o Gt {
+    type Output = Gt;
+    fn mul(self, other: &'o FieldScalar) -> Gt {
+        self.scalar_mul(other)
+    }
+}
+
+/// The pairing engine of BLS12-381.
+#[derive(Clone, Copy, Debug)]
+pub struct Bls12_381;
+
+impl Pairing for Bls12_381 {
+    type G1 = G1Point;
+    type G2 = G2Point;
+    type Gt = Gt;
+
+    fn pairing(p: &G1Point, q: &G2Point) -> Gt {
+        Gt {
+            purpose: "pairing",
+            value: bls12_381::pairing(&p.get_element(), &q.get_element()),
+        }
+    }
+
+    fn multi_pairing(terms: &[(&G1Point, &G2Point)]) -> Gt {
+        let terms = terms
+            .iter()
+            .map(|(p, q)| (p.get_element(), G2Prepared::from(q.get_element())))
+            .collect::<Vec<_>>();
+        let terms_ref = terms.iter().map(|(p, q)| (p, q)).collect::<Vec<_>>();
+        Gt {
+            purpose: "multi_pairing",
+            value: multi_miller_loop(&terms_ref).final_exponentiation(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bls12_381, Gt};
+    use crate::elliptic::curves::bls12_381::g1::{FieldScalar, G1Point};
+    use crate::elliptic::curves::bls12_381::g2::G2Point;
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar, Pairing};
+
+    #[test]
+    fn test_bilinearity() {
+        let a: FieldScalar = ECScalar::new_random();
+        let b: FieldScalar = ECScalar::new_random();
+        let p = G1Point::generator();
+        let q = G2Point::generator();
+
+        let e_ab = Bls12_381::pairing(&(p * a), &(q * b));
+        assert_eq!(e_ab, Gt::generator() * (a * b));
+        assert_eq!(Bls12_381::pairing(&(p * (a * b)), &q), e_ab);
+        assert_eq!(Bls12_381::pairing(&p, &(q * (a * b))), e_ab);
+
+        let p2 = G1Point::base_point2();
+        assert_eq!(
+            Bls12_381::pairing(&(p + p2), &q),
+            Bls12_381::pairing(&p, &q) + Bls12_381::pairing(&p2, &q)
+        );
+    }
+
+    #[test]
+    fn test_non_degeneracy() {
+        assert!(!Gt::generator().is_identity());
+        assert!(Bls12_381::pairing(&G1Point::zero(), &G2Point::generator()).is_identity());
+        assert!(Bls12_381::pairing(&G1Point::generator(), &G2Point::zero()).is_identity());
+
+        let q_minus_one: FieldScalar = ECScalar::from(&(FieldScalar::q() - 1));
+        assert_eq!(
+            Gt::generator() * q_minus_one + Gt::generator(),
+            Gt::identity()
+        );
+        assert_eq!(-Gt::generator(), Gt::generator() * q_minus_one);
+    }
+
+    #[test]
+    fn test_multi_pairing() {
+        let a: FieldScalar = ECScalar::new_random();
+        let b: FieldScalar = ECScalar::new_random();
+        let p1 = G1Point::generator() * a;
+        let p2 = G1Point::base_point2();
+        let q1 = G2Point::generator();
+        let q2 = G2Point::base_point2() * b;
+
+        let expected = Bls12_381::pairing(&p1, &q1) + Bls12_381::pairing(&p2, &q2);
+        assert_eq!(
+            Bls12_381::multi_pairing(&[(&p1, &q1), (&p2, &q2)]),
+            expected
+        );
+        assert!(Bls12_381::multi_pairing(&[]).is_identity());
+
+        // e(a * P, Q) - e(P, a * Q) = 0, the check behind BLS signature verification
+        let minus_p = G1Point::zero().sub_point(&G1Point::generator().get_element());
+        let q = G2Point::generator() * a;
+        assert!(Bls12_381::multi_pairing(&[(&p1, &q1), (&minus_p, &q)]).is_identity());
+    }
+}
@@ -6,22 +6,22 @@
     License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
 */
 
-use super::traits::{ECPoint, ECScalar};
+use super::hash_to_curve::expand_message_xmd;
+use super::traits::{ECPoint, ECScalar, HashToCurve};
 use crate::arithmetic::traits::Converter;
-use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
-use crate::cryptographic_primitives::hashing::traits::Hash;
 use crate::BigInt;
 use crate::ErrorKey::{self, InvalidPublicKey};
 use curve25519_dalek::constants::BASEPOINT_ORDER;
-use curve25519_dalek::constants::RISTRETTO_BASEPOINT_COMPRESSED;
-use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::constants::{RISTRETTO_BASEPOINT_COMPRESSED, RISTRETTO_BASEPOINT_TABLE};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoBasepointTable, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar;
-use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
 use serde::de;
 use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
+use sha2::Sha512;
 use std::fmt;
 use std::ops::{Add, Mul, Sub};
 use std::str;
@@ -40,7 +40,7 @@ use crypto::digest::Digest;
 use crypto::sha3::Sha3;
 #[cfg(feature = "merkle")]
 use merkle::Hashable;
-use curve25519_dalek::traits::Identity;
+use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
 
 pub type SK = Scalar;
 pub type PK = CompressedRistretto;
@@ -60,6 +60,23 @@ pub struct RistrettoCurvPoint {
 pub type GE = RistrettoCurvPoint;
 pub type FE = RistrettoScalar;
 
+lazy_static::lazy_static! {
+    /* base_point2 is the first NUMS generator seeded with the encoded generator, i.e. the hash
+    of G || I2OSP(0, 4) with DST "CURV-V01-CS01-NUMS-with-ristretto255_XMD:SHA-512_R255MAP_RO_".
+    See test_base_point2 */
+    static ref BASE_POINT2: RistrettoCurvPoint = {
+        let g: GE = ECPoint::generator();
+        let h = RistrettoCurvPoint::nums_generators(&g.pk_to_key_slice(), 1)[0];
+        RistrettoCurvPoint {
+            purpose: "random",
+            ge: h.get_element(),
+        }
+    };
+
+    static ref BASE_POINT2_TABLE: RistrettoBasepointTable =
+        RistrettoBasepointTable::create(&BASE_POINT2.ge.decompress().unwrap());
+}
+
 impl Zeroize for RistrettoScalar {
     fn zeroize(&mut self) {
         unsafe { ptr::write_volatile(self, FE::zero()) };
@@ -77,10 +94,10 @@ impl From<&BigInt> for RistrettoScalar {
 impl ECScalar for RistrettoScalar {
     type SecretKey = SK;
 
-    fn new_random() -> RistrettoScalar {
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> RistrettoScalar {
         RistrettoScalar {
             purpose: "random",
-            fe: SK::random(&mut thread_rng()),
+            fe: SK::random(rng),
         }
     }
 
@@ -277,14 +294,7 @@ impl ECPoint for RistrettoCurvPoint {
     type Scalar = RistrettoScalar;
 
     fn base_point2() -> RistrettoCurvPoint {
-        let g: GE = ECPoint::generator();
-        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        let bytes = BigInt::to_vec(&hash);
-        let h: GE = ECPoint::from_bytes(&bytes[..]).unwrap();
-        RistrettoCurvPoint {
-            purpose: "random",
-            ge: h.get_element(),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> RistrettoCurvPoint {
@@ -294,6 +304,14 @@ impl ECPoint for RistrettoCurvPoint {
         }
     }
 
+    fn zero() -> RistrettoCurvPoint {
+        RistrettoCurvPoint::identity()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.ge == PK::identity()
+    }
+
     fn get_element(&self) -> PK {
         self.ge
     }
@@ -390,6 +408,58 @@ impl ECPoint for RistrettoCurvPoint {
     fn from_coor(_x: &BigInt, _y: &BigInt) -> RistrettoCurvPoint {
         unimplemented!();
     }
+
+    // dalek picks Straus or Pippenger depending on the size of the input
+    fn multi_scalar_mul(
+        points: &[RistrettoCurvPoint],
+        scalars: &[RistrettoScalar],
+    ) -> RistrettoCurvPoint {
+        let points = points.iter().map(|p| p.ge.decompress().unwrap());
+        let scalars = scalars.iter().map(|k| k.fe);
+        RistrettoCurvPoint {
+            purpose: "multi_scalar_mul",
+            ge: RistrettoPoint::vartime_multiscalar_mul(scalars, points).compress(),
+        }
+    }
+
+    // the ristretto group has prime order, every point that decodes is in the subgroup
+    fn is_valid(&self) -> bool {
+        self.ge.decompress().is_some()
+    }
+
+    fn mul_base(scalar: &RistrettoScalar) -> RistrettoCurvPoint {
+        RistrettoCurvPoint {
+            purpose: "mul_base",
+            ge: (&RISTRETTO_BASEPOINT_TABLE * &scalar.fe).compress(),
+        }
+    }
+
+    fn mul_base_point2(scalar: &RistrettoScalar) -> RistrettoCurvPoint {
+        RistrettoCurvPoint {
+            purpose: "mul_base",
+            ge: (&*BASE_POINT2_TABLE * &scalar.fe).compress(),
+        }
+    }
+}
+
+// RFC 9380 only defines a random oracle suite for ristretto255: hash_to_curve and
+// encode_to_curve are the same map.
+impl HashToCurve for RistrettoCurvPoint {
+    const SUITE_ID: &'static str = "ristretto255_XMD:SHA-512_R255MAP_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> RistrettoCurvPoint {
+        let uniform_bytes = expand_message_xmd::<Sha512>(msg, dst, 64);
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&uniform_bytes);
+        RistrettoCurvPoint {
+            purpose: "hash_to_curve",
+            ge: RistrettoPoint::from_uniform_bytes(&bytes).compress(),
+        }
+    }
+
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> RistrettoCurvPoint {
+        RistrettoCurvPoint::hash_to_curve(msg, dst)
+    }
 }
 
 impl Mul<RistrettoScalar> for RistrettoCurvPoint {
@@ -497,7 +567,8 @@ impl<'de> Visitor<'de> for RistrettoCurvPointVisitor {
             .ok_or_else(|| panic!("deserialization failed"))?;
         let bytes_bn = BigInt::from_hex(bytes_str);
         let bytes = BigInt::to_vec(&bytes_bn);
-        Ok(RistrettoCurvPoint::from_bytes(&bytes[..]).expect("error deserializing point"))
+        RistrettoCurvPoint::from_bytes(&bytes[..])
+            .map_err(|_| de::Error::custom("invalid RistrettoCurvPoint"))
     }
 
     fn visit_map<E: MapAccess<'de>>(self, mut map: E) -> Result<RistrettoCurvPoint, E::Error> {
@@ -515,7 +586,8 @@ impl<'de> Visitor<'de> for RistrettoCurvPointVisitor {
         let bytes_bn = BigInt::from_hex(&bytes_str);
         let bytes = BigInt::to_vec(&bytes_bn);
 
-        Ok(RistrettoCurvPoint::from_bytes(&bytes[..]).expect("error deserializing point"))
+        RistrettoCurvPoint::from_bytes(&bytes[..])
+            .map_err(|_| de::Error::custom("invalid RistrettoCurvPoint"))
     }
 }
 
@@ -526,6 +598,7 @@ mod tests {
     use crate::arithmetic::traits::Modulo;
     use crate::elliptic::curves::traits::ECPoint;
     use crate::elliptic::curves::traits::ECScalar;
+    use crate::elliptic::curves::traits::HashToCurve;
     use crate::BigInt;
     use serde_json;
     use std::borrow::Borrow;
@@ -671,4 +744,41 @@ mod tests {
         let c = &a * &b;
         assert_eq!(c, c_s.to_big_int())
     }
+
+    #[test]
+    fn test_base_point2() {
+        // base_point2 is hash_to_curve of the generator, so its discrete log is unknown
+        let g: GE = ECPoint::generator();
+        let seed = [&g.pk_to_key_slice()[..], &[0u8; 4]].concat();
+        let dst = b"CURV-V01-CS01-NUMS-with-ristretto255_XMD:SHA-512_R255MAP_RO_";
+        assert_eq!(GE::base_point2(), GE::hash_to_curve(&seed, dst));
+        assert!(!GE::base_point2().is_zero());
+        assert_ne!(GE::base_point2(), g);
+    }
+
+    // RFC 9497 appendix A.1.1: the blinded elements of the ristretto255 OPRF vectors are
+    // blind * hash_to_curve(input)
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"HashToGroup-OPRFV1-\x00-ristretto255-SHA512";
+        let mut blind_bytes =
+            hex::decode("64d37aed22a27f5191de1c1d69fadb899d8862b58eb4220029e036ec4c1f6706").unwrap();
+        blind_bytes.reverse();
+        let blind: FE = ECScalar::from(&BigInt::from(&blind_bytes[..]));
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                &[0x00],
+                "609a0ae68c15a3cf6903766461307e5c8bb2f95e7e6550e1ffa2dc99e412803c",
+            ),
+            (
+                &[0x5a; 17],
+                "da27ef466870f5f15296299850aa088629945a17d1f5b7f5ff043f76b3c06418",
+            ),
+        ];
+        for (input, blinded_element) in vectors.iter() {
+            let p = GE::hash_to_curve(input, dst) * blind;
+            assert_eq!(hex::encode(p.pk_to_key_slice()), *blinded_element);
+            assert_eq!(GE::encode_to_curve(input, dst), GE::hash_to_curve(input, dst));
+        }
+    }
 }
@@ -11,7 +11,8 @@
 use std::fmt::Debug;
 use std::str;
 pub const TWO_TIMES_SECRET_KEY_SIZE: usize = 64;
-use super::traits::{ECPoint, ECScalar};
+use super::hash_to_curve::{hash_to_field, map_to_curve_elligator2, Field, PrimeField};
+use super::traits::{ECPoint, ECScalar, HashToCurve};
 use crate::arithmetic::traits::Converter;
 use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::cryptographic_primitives::hashing::traits::Hash;
@@ -20,6 +21,7 @@ use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
+use sha2::Sha512;
 use std::fmt;
 use std::ops::{Add, Mul};
 pub type SK = Fe;
@@ -34,6 +36,7 @@ use crypto::sha3::Sha3;
 use cryptoxide::curve25519::*;
 #[cfg(feature = "merkle")]
 use merkle::Hashable;
+use rand::{CryptoRng, RngCore};
 use std::ptr;
 use std::sync::atomic;
 use zeroize::Zeroize;
@@ -51,6 +54,37 @@ pub struct Ed25519Point {
 pub type GE = Ed25519Point;
 pub type FE = Ed25519Scalar;
 
+// encoding of the identity point (0, 1)
+const IDENTITY_BYTES: [u8; 32] = [
+    1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+lazy_static::lazy_static! {
+    static ref GENERATOR: Ed25519Point = {
+        let vec_1: [u8; 32];
+        vec_1 = [
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ];
+        Ed25519Point {
+            purpose: "base_fe",
+            ge: ge_scalarmult_base(&vec_1[..]),
+        }
+    };
+
+    static ref BASE_POINT2: Ed25519Point = {
+        let g: GE = ECPoint::generator();
+        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
+        let hash = HSha256::create_hash(&[&hash]);
+        let bytes = BigInt::to_vec(&hash);
+        let h: GE = Ed25519Point::decode_clear_cofactor(&bytes[..]).unwrap();
+        Ed25519Point {
+            purpose: "random",
+            ge: h.get_element(),
+        }
+    };
+}
+
 impl Zeroize for Ed25519Scalar {
     fn zeroize(&mut self) {
         unsafe { ptr::write_volatile(self, FE::zero()) };
@@ -63,8 +97,8 @@ impl ECScalar for Ed25519Scalar {
 
     // we chose to multiply by 8 (co-factor) all group elements to work in the prime order sub group.
     // each random fe is having its 3 first bits zeroed
-    fn new_random() -> Ed25519Scalar {
-        let rnd_bn = BigInt::sample_below(&FE::q());
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Ed25519Scalar {
+        let rnd_bn = BigInt::sample_below_with_rng(&FE::q(), rng);
         let rnd_bn_mul_8 = BigInt::mod_mul(&rnd_bn, &BigInt::from(8), &FE::q());
         ECScalar::from(&rnd_bn_mul_8)
     }
@@ -109,11 +143,7 @@ impl ECScalar for Ed25519Scalar {
     }
 
     fn q() -> BigInt {
-        let q_bytes_array: [u8; 32];
-        q_bytes_array = [
-            237, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
-        ];
+        let q_bytes_array = Ed25519Scalar::q_bytes();
         let l_fe = SK::from_bytes(&q_bytes_array);
         let l_fe = Ed25519Scalar {
             purpose: "q",
@@ -184,6 +214,16 @@ impl PartialEq for Ed25519Scalar {
     }
 }
 
+impl Ed25519Scalar {
+    // little endian encoding of the order of the prime order sub group
+    fn q_bytes() -> [u8; 32] {
+        [
+            237, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+        ]
+    }
+}
+
 impl Mul<Ed25519Scalar> for Ed25519Scalar {
     type Output = Ed25519Scalar;
     fn mul(self, other: Ed25519Scalar) -> Ed25519Scalar {
@@ -276,29 +316,24 @@ impl ECPoint for Ed25519Point {
     type Scalar = Ed25519Scalar;
 
     fn base_point2() -> Ed25519Point {
-        let g: GE = ECPoint::generator();
-        let hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        let hash = HSha256::create_hash(&[&hash]);
-        let bytes = BigInt::to_vec(&hash);
-        let h: GE = ECPoint::from_bytes(&bytes[..]).unwrap();
-        Ed25519Point {
-            purpose: "random",
-            ge: h.get_element(),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> Ed25519Point {
-        let vec_1: [u8; 32];
-        vec_1 = [
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 0,
-        ];
+        *GENERATOR
+    }
+
+    fn zero() -> Ed25519Point {
         Ed25519Point {
-            purpose: "base_fe",
-            ge: ge_scalarmult_base(&vec_1[..]),
+            purpose: "zero",
+            ge: ge_scalarmult_base(&[0u8; 32]),
         }
     }
 
+    fn is_zero(&self) -> bool {
+        self.ge.to_bytes() == IDENTITY_BYTES
+    }
+
     fn get_element(&self) -> PK {
         self.ge
     }
@@ -321,66 +356,16 @@ impl ECPoint for Ed25519Point {
         BigInt::from(self.ge.to_bytes()[0..self.ge.to_bytes().len()].as_ref())
     }
 
-    // from_bytes will return Ok only if the bytes encode a valid point.
-    // since valid point are not necessarily in the sub group of prime order this needs to be checked
-    // as well such that Ok will be returned only for valid point of the sub group prime order.
-    // currently we change the encoded point by multiply by 8 to make sure it is in the sub group of prime order.
-    // This is because for our use cases so far it doesn't matter and multiply by 8 is faster than testing for a point
-    // in the sub group prime order
+    // from_bytes will return Ok only if the bytes are the canonical encoding of a point of the
+    // sub group of prime order (this includes the identity). Points of small order and points
+    // with a small order component are rejected: they could be used by a malicious counterparty
+    // to leak information about our secrets.
     fn from_bytes(bytes: &[u8]) -> Result<Ed25519Point, ErrorKey> {
-        let bytes_vec = bytes.to_vec();
-        let mut bytes_array_32 = [0u8; 32];
-        let byte_len = bytes_vec.len();
-        match byte_len {
-            0..=32 => {
-                let mut template = vec![0; 32 - byte_len];
-                template.extend_from_slice(&bytes);
-                let bytes_vec = template;
-                let bytes_slice = &bytes_vec[0..32];
-                bytes_array_32.copy_from_slice(&bytes_slice);
-                let ge_from_bytes = PK::from_bytes_negate_vartime(&bytes_array_32);
-                match ge_from_bytes {
-                    Some(_x) => {
-                        let ge_bytes = ge_from_bytes.unwrap().to_bytes();
-                        let ge_from_bytes = PK::from_bytes_negate_vartime(&ge_bytes[..]);
-                        match ge_from_bytes {
-                            Some(y) => {
-                                let eight: FE = ECScalar::from(&BigInt::from(8));
-                                let new_point = Ed25519Point {
-                                    purpose: "random",
-                                    ge: y,
-                                };
-                                Ok(new_point * eight)
-                            }
-                            None => Err(InvalidPublicKey),
-                        }
-                    }
-                    None => Err(InvalidPublicKey),
-                }
-            }
-            _ => {
-                let bytes_slice = &bytes_vec[0..32];
-                bytes_array_32.copy_from_slice(&bytes_slice);
-                let ge_from_bytes = PK::from_bytes_negate_vartime(bytes);
-                match ge_from_bytes {
-                    Some(_x) => {
-                        let ge_bytes = ge_from_bytes.unwrap().to_bytes();
-                        let ge_from_bytes = PK::from_bytes_negate_vartime(&ge_bytes[..]);
-                        match ge_from_bytes {
-                            Some(y) => {
-                                let eight: FE = ECScalar::from(&BigInt::from(8));
-                                let new_point = Ed25519Point {
-                                    purpose: "random",
-                                    ge: y,
-                                };
-                                Ok(new_point * eight)
-                            }
-                            None => Err(InvalidPublicKey),
-                        }
-                    }
-                    None => Err(InvalidPublicKey),
-                }
-            }
+        let point = Ed25519Point::decode(bytes)?;
+        if point.is_in_prime_order_subgroup() {
+            Ok(point)
+        } else {
+            Err(InvalidPublicKey)
         }
     }
 
@@ -433,6 +418,122 @@ impl ECPoint for Ed25519Point {
     fn from_coor(_x: &BigInt, _y: &BigInt) -> Ed25519Point {
         unimplemented!();
     }
+
+    fn is_valid(&self) -> bool {
+        PK::from_bytes_negate_vartime(&self.ge.to_bytes()).is_some()
+    }
+
+    // checks that q * P is the identity
+    fn is_in_prime_order_subgroup(&self) -> bool {
+        let q_bytes = Ed25519Scalar::q_bytes();
+        let zero = [0u8; 32];
+        let q_point = GeP2::double_scalarmult_vartime(&q_bytes[..], self.ge, &zero[..]);
+        self.is_valid() && q_point.to_bytes() == IDENTITY_BYTES
+    }
+
+    // cryptoxide comes with a precomputed table for the generator. We don't build one for
+    // base_point2: cryptoxide doesn't expose the point representations needed to add points
+    // without a decompression, so a generic table would be slower than scalar_mul.
+    fn mul_base(scalar: &Ed25519Scalar) -> Ed25519Point {
+        Ed25519Point {
+            purpose: "mul_base",
+            ge: ge_scalarmult_base(&scalar.fe.to_bytes()[..]),
+        }
+    }
+}
+
+impl Ed25519Point {
+    // decodes any point on the curve, rejecting non canonical encodings
+    fn decode(bytes: &[u8]) -> Result<Ed25519Point, ErrorKey> {
+        let mut bytes_array_32 = [0u8; 32];
+        match bytes.len() {
+            0..=32 => {
+                bytes_array_32[32 - bytes.len()..].copy_from_slice(bytes);
+            }
+            _ => {
+                bytes_array_32.copy_from_slice(&bytes[0..32]);
+            }
+        }
+        // from_bytes_negate_vartime returns -P, decoding twice gives back P
+        let minus_ge = PK::from_bytes_negate_vartime(&bytes_array_32).ok_or(InvalidPublicKey)?;
+        let ge = PK::from_bytes_negate_vartime(&minus_ge.to_bytes()).ok_or(InvalidPublicKey)?;
+        if ge.to_bytes() != bytes_array_32 {
+            return Err(InvalidPublicKey);
+        }
+        Ok(Ed25519Point {
+            purpose: "random",
+            ge,
+        })
+    }
+
+    // decodes any point on the curve like ZIP-215: y may be non canonically encoded (y >= p) and
+    // the sign bit may be set for x = 0. Ed25519 signatures have to accept such encodings.
+    pub(crate) fn decode_non_canonical(bytes: &[u8; 32]) -> Result<Ed25519Point, ErrorKey> {
+        let minus_ge = PK::from_bytes_negate_vartime(bytes).ok_or(InvalidPublicKey)?;
+        let ge = PK::from_bytes_negate_vartime(&minus_ge.to_bytes()).ok_or(InvalidPublicKey)?;
+        Ok(Ed25519Point {
+            purpose: "random",
+            ge,
+        })
+    }
+
+    // decodes a point and multiplies it by the cofactor to move it to the sub group of prime order
+    fn decode_clear_cofactor(bytes: &[u8]) -> Result<Ed25519Point, ErrorKey> {
+        let eight: FE = ECScalar::from(&BigInt::from(8));
+        Ed25519Point::decode(bytes).map(|point| point * eight)
+    }
+}
+
+lazy_static::lazy_static! {
+    // GF(2^255 - 19)
+    static ref FIELD: PrimeField =
+        PrimeField::from_hex("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+
+    // sqrt(-486664) with sgn0 = 0, used by the rational map from curve25519 to edwards25519
+    static ref SQRT_MINUS_486664: BigInt = {
+        let c1 = FIELD.sqrt(&FIELD.neg(&BigInt::from(486_664))).unwrap();
+        if FIELD.sgn0(&c1) { FIELD.neg(&c1) } else { c1 }
+    };
+}
+
+impl Ed25519Point {
+    // Elligator 2 to curve25519 followed by the rational map to edwards25519 (RFC 9380 appendix
+    // D.1), the cofactor is cleared while decoding the point
+    fn map_to_curve(u: &BigInt) -> Ed25519Point {
+        let f = &*FIELD;
+        let (s, t) = map_to_curve_elligator2(f, &BigInt::from(486_662), &BigInt::from(2), u);
+        let s_plus_one = f.add(&s, &BigInt::one());
+        let (x, y) = if f.is_zero(&t) || f.is_zero(&s_plus_one) {
+            (BigInt::zero(), BigInt::one())
+        } else {
+            let x = f.div(&f.mul(&SQRT_MINUS_486664, &s), &t);
+            let y = f.div(&f.sub(&s, &BigInt::one()), &s_plus_one);
+            (x, y)
+        };
+
+        // little endian y, the most significant bit holds the sign of x
+        let mut bytes = BigInt::to_vec(&y);
+        bytes.reverse();
+        bytes.resize(32, 0);
+        if f.sgn0(&x) {
+            bytes[31] |= 1 << 7;
+        }
+        Ed25519Point::decode_clear_cofactor(&bytes).unwrap()
+    }
+}
+
+impl HashToCurve for Ed25519Point {
+    const SUITE_ID: &'static str = "edwards25519_XMD:SHA-512_ELL2_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Ed25519Point {
+        let u = hash_to_field::<Sha512>(msg, dst, 2, FIELD.p(), 48);
+        Ed25519Point::map_to_curve(&u[0]) + Ed25519Point::map_to_curve(&u[1])
+    }
+
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> Ed25519Point {
+        let u = hash_to_field::<Sha512>(msg, dst, 1, FIELD.p(), 48);
+        Ed25519Point::map_to_curve(&u[0])
+    }
 }
 
 impl Mul<Ed25519Scalar> for Ed25519Point {
@@ -527,7 +628,7 @@ impl<'de> Visitor<'de> for Ed25519PointVisitor {
             .ok_or_else(|| panic!("deserialization failed"))?);
         let bytes_bn = BigInt::from_hex(bytes_str);
         let bytes = BigInt::to_vec(&bytes_bn);
-        Ok(Ed25519Point::from_bytes(&bytes[..]).expect("error deserializing point"))
+        Ed25519Point::from_bytes(&bytes[..]).map_err(|_| de::Error::custom("invalid Ed25519Point"))
     }
 
     fn visit_map<E: MapAccess<'de>>(self, mut map: E) -> Result<Ed25519Point, E::Error> {
@@ -546,7 +647,7 @@ impl<'de> Visitor<'de> for Ed25519PointVisitor {
         let bytes_bn = BigInt::from_hex(&bytes_str);
         let bytes = BigInt::to_vec(&bytes_bn);
 
-        Ok(Ed25519Point::from_bytes(&bytes[..]).expect("error deserializing point"))
+        Ed25519Point::from_bytes(&bytes[..]).map_err(|_| de::Error::custom("invalid Ed25519Point"))
     }
 }
 
@@ -598,10 +699,11 @@ pub fn expmod(b: &BigInt, e: &BigInt, m: &BigInt) -> BigInt {
 
 #[cfg(test)]
 mod tests {
-    use super::{Ed25519Point, Ed25519Scalar};
+    use super::{Ed25519Point, Ed25519Scalar, IDENTITY_BYTES};
     use crate::arithmetic::traits::{Converter, Modulo};
     use crate::elliptic::curves::traits::ECPoint;
     use crate::elliptic::curves::traits::ECScalar;
+    use crate::elliptic::curves::traits::HashToCurve;
     use crate::BigInt;
     use serde_json;
 
@@ -613,27 +715,24 @@ mod tests {
         let mut pk = GE::generator();
         let mut s = serde_json::to_string(&pk).expect("Failed in serialization");
         let mut des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
-        let eight = ECScalar::from(&BigInt::from(8));
-        assert_eq!(des_pk, pk * &eight);
+        assert_eq!(des_pk, pk);
 
         pk = GE::base_point2();
         s = serde_json::to_string(&pk).expect("Failed in serialization");
         des_pk = serde_json::from_str(&s).expect("Failed in deserialization");
-        assert_eq!(des_pk, pk * &eight);
+        assert_eq!(des_pk, pk);
 
         // deserialize serialization of bytes_str < 64 hex
         s = "{\"bytes_str\":\"2c42d43e1a277e8f3d7d5aacde519c80b913341e425b624d867f790d1578e0\"}"
             .to_string();
         des_pk = serde_json::from_str(&s).expect("Failed in deserialization");
-        let eight_inverse: FE = eight.invert();
-        let des_pk_mul = des_pk * &eight_inverse;
         assert_eq!(
-            des_pk_mul.bytes_compressed_to_big_int().to_hex(),
+            des_pk.bytes_compressed_to_big_int().to_hex(),
             "2c42d43e1a277e8f3d7d5aacde519c80b913341e425b624d867f790d1578e0"
         );
 
         // serialize with padding
-        let ser_pk = serde_json::to_string(&des_pk_mul).expect("Failed in serialization");
+        let ser_pk = serde_json::to_string(&des_pk).expect("Failed in serialization");
         assert_eq!(
             &ser_pk,
             "{\"bytes_str\":\"002c42d43e1a277e8f3d7d5aacde519c80b913341e425b624d867f790d1578e0\"}"
@@ -641,7 +740,7 @@ mod tests {
 
         // deserialize a padded serialization
         let des_pk2: GE = serde_json::from_str(&ser_pk).expect("Failed in deserialization");
-        assert_eq!(des_pk_mul, des_pk2 * &eight_inverse);
+        assert_eq!(des_pk, des_pk2);
     }
 
     #[test]
@@ -649,8 +748,7 @@ mod tests {
         let pk = GE::generator();
         let encoded = bincode::serialize(&pk).unwrap();
         let decoded: Ed25519Point = bincode::deserialize(encoded.as_slice()).unwrap();
-        let eight = ECScalar::from(&BigInt::from(8));
-        assert_eq!(pk * &eight, decoded);
+        assert_eq!(pk, decoded);
     }
 
     #[test]
@@ -661,8 +759,20 @@ mod tests {
         // we make sure that the string encodes invalid point:
         let s: String = s.replace("5866", "5867");
         let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
-        let eight = ECScalar::from(&BigInt::from(8));
-        assert_eq!(des_pk, pk * &eight);
+        assert_eq!(des_pk, pk);
+    }
+
+    #[test]
+    fn test_serdes_torsion_pk() {
+        // a point of order 2 added to the generator
+        let g: GE = GE::generator();
+        let torsion = torsion_point();
+        let pk = g.add_point(&torsion.get_element());
+        assert!(pk.is_valid());
+        assert!(!pk.is_in_prime_order_subgroup());
+        let s = serde_json::to_string(&pk).expect("Failed in serialization");
+        let des_pk: Result<GE, _> = serde_json::from_str(&s);
+        assert!(des_pk.is_err());
     }
 
     #[test]
@@ -754,12 +864,14 @@ mod tests {
     }
     #[test]
     fn test_from_bytes_2() {
+        // these bytes encode a point on the curve which is not in the sub group of prime order
         let test_vec = [
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5,
             6,
         ];
+        assert!(Ed25519Point::decode(&test_vec).is_ok());
         let result = Ed25519Point::from_bytes(&test_vec);
-        assert!(result.is_ok())
+        assert!(result.is_err())
     }
     #[test]
     fn test_from_bytes_3() {
@@ -767,8 +879,45 @@ mod tests {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 1, 2, 3, 4, 5, 6,
         ];
+        assert!(Ed25519Point::decode(&test_vec).is_ok());
         let result = Ed25519Point::from_bytes(&test_vec);
-        assert!(result.is_ok())
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn test_from_bytes_small_order() {
+        // a point of order 2 is rejected, the identity is not
+        assert!(Ed25519Point::from_bytes(&IDENTITY_BYTES).unwrap().is_zero());
+        let torsion = torsion_point();
+        assert!(!torsion.is_in_prime_order_subgroup());
+        assert!(Ed25519Point::from_bytes(&torsion.get_element().to_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_from_bytes_non_canonical() {
+        // y = p + 1 is a non canonical encoding of the identity
+        let mut bytes = [0xffu8; 32];
+        bytes[0] = 0xee;
+        bytes[31] = 0x7f;
+        assert!(Ed25519Point::decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_is_in_prime_order_subgroup() {
+        let g: GE = GE::generator();
+        assert!(g.is_valid());
+        assert!(g.is_in_prime_order_subgroup());
+        let a: FE = ECScalar::new_random();
+        assert!((g * a).is_in_prime_order_subgroup());
+        assert!(GE::base_point2().is_in_prime_order_subgroup());
+    }
+
+    // (0, -1), the point of order 2
+    fn torsion_point() -> GE {
+        let mut bytes = [0xffu8; 32];
+        bytes[0] = 0xec;
+        bytes[31] = 0x7f;
+        Ed25519Point::decode(&bytes).unwrap()
     }
 
     #[test]
@@ -793,9 +942,8 @@ mod tests {
         let test_ge = g * &test_fe;
         let test_ge_bytes = test_ge.get_element().to_bytes();
         let test_ge2: GE = ECPoint::from_bytes(&test_ge_bytes[..]).unwrap();
-        let eight: FE = ECScalar::from(&BigInt::from(8));
 
-        assert_eq!(test_ge2, test_ge * eight);
+        assert_eq!(test_ge2, test_ge);
     }
 
     #[test]
@@ -813,4 +961,54 @@ mod tests {
             "15112221349535400772501151409588531511454012693041857206046113283949847762202"
         );
     }
+
+    // encoding of the point with the given big endian affine coordinates
+    fn encode_coordinates(x: &str, y: &str) -> Vec<u8> {
+        let mut bytes = hex::decode(y).unwrap();
+        bytes.reverse();
+        if hex::decode(x).unwrap()[31] & 1 == 1 {
+            bytes[31] |= 1 << 7;
+        }
+        bytes
+    }
+
+    // RFC 9380 appendices J.5.1 and J.5.2
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+        let vectors: [(&[u8], &str, &str); 2] = [
+            (
+                b"",
+                "3c3da6925a3c3c268448dcabb47ccde5439559d9599646a8260e47b1e4822fc6",
+                "09a6c8561a0b22bef63124c588ce4c62ea83a3c899763af26d795302e115dc21",
+            ),
+            (
+                b"abc",
+                "608040b42285cc0d72cbb3985c6b04c935370c7361f4b7fbdb1ae7f8c1a8ecad",
+                "1a8395b88338f22e435bbd301183e7f20a5f9de643f11882fb237f88268a5531",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let p = GE::hash_to_curve(msg, dst);
+            assert_eq!(p.pk_to_key_slice(), encode_coordinates(x, y));
+        }
+
+        let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_NU_";
+        let vectors: [(&[u8], &str, &str); 2] = [
+            (
+                b"",
+                "1ff2b70ecf862799e11b7ae744e3489aa058ce805dd323a936375a84695e76da",
+                "222e314d04a4d5725e9f2aff9fb2a6b69ef375a1214eb19021ceab2d687f0f9b",
+            ),
+            (
+                b"abc",
+                "5f13cc69c891d86927eb37bd4afc6672360007c63f68a33ab423a3aa040fd2a8",
+                "67732d50f9a26f73111dd1ed5dba225614e538599db58ba30aaea1f5c827fa42",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let p = GE::encode_to_curve(msg, dst);
+            assert_eq!(p.pk_to_key_slice(), encode_coordinates(x, y));
+        }
+    }
 }
@@ -0,0 +1,125 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// Precomputed tables for multiplying a fixed base point (the generator, base_point2) by
+// many different scalars. For a window of w bits the table holds j * 2^(w * i) * B for every
+// window i and digit j (including j = 0, the identity), so k * B is a sum of one table entry per
+// window of k and no doublings are needed. Curves build their tables lazily (see lazy_static) on
+// first use.
+//
+// The scalars are secret (keys, nonces, blindings), so mul is constant time: the digits are
+// read from the fixed width encoding of the scalar, every window adds an entry even for a zero
+// digit, and every entry of a row is read and selected with ConditionallySelectable. The point
+// addition must be complete (it must handle the identity and doubling without branching).
+
+use std::ops::Add;
+
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+const WINDOW: usize = 4;
+
+pub struct FixedBaseTable<G> {
+    // table[i][j] = j * 2^(WINDOW * i) * base
+    table: Vec<Vec<G>>,
+    identity: G,
+}
+
+impl<G> FixedBaseTable<G>
+where
+    G: Copy + Add<Output = G> + ConditionallySelectable,
+{
+    /// builds the table for scalars of up to `bits` bits
+    pub fn new(base: G, identity: G, bits: usize) -> FixedBaseTable<G> {
+        let windows = bits.div_ceil(WINDOW);
+        let mut table: Vec<Vec<G>> = Vec::with_capacity(windows);
+        let mut window_base = base;
+        for _ in 0..windows {
+            let mut row = vec![identity, window_base];
+            for j in 2..=(1 << WINDOW) {
+                let next = row[j - 1] + window_base;
+                row.push(next);
+            }
+            // the last entry is 2^WINDOW times the window base, which is the base of the next window
+            window_base = row.pop().unwrap();
+            table.push(row);
+        }
+        FixedBaseTable { table, identity }
+    }
+
+    /// returns k * base where k is given by its big endian bytes
+    pub fn mul(&self, k: &[u8]) -> G {
+        assert!(
+            k.len() * 8 <= self.table.len() * WINDOW,
+            "scalar is too large for the table"
+        );
+        // WINDOW = 4: two digits per byte, least significant first
+        let digits = k
+            .iter()
+            .rev()
+            .flat_map(|byte| vec![byte & 0x0f, byte >> 4])
+            .collect::<Vec<u8>>();
+        digits
+            .iter()
+            .zip(self.table.iter())
+            .fold(self.identity, |acc, (d, row)| {
+                let mut entry = self.identity;
+                for (j, point) in row.iter().enumerate() {
+                    entry.conditional_assign(point, d.ct_eq(&(j as u8)));
+                }
+                acc + entry
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixedBaseTable;
+    use crate::elliptic::curves::traits::*;
+    use crate::test_for_all_curves;
+    use crate::BigInt;
+
+    test_for_all_curves!(test_mul_base);
+    fn test_mul_base<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone,
+    {
+        let g: P = ECPoint::generator();
+        let h: P = ECPoint::base_point2();
+        for _ in 0..10 {
+            let k: P::Scalar = ECScalar::new_random();
+            assert_eq!(P::mul_base(&k), g.clone() * k.clone());
+            assert_eq!(P::mul_base_point2(&k), h.clone() * k.clone());
+        }
+        let one: P::Scalar = ECScalar::from(&BigInt::one());
+        assert_eq!(P::mul_base(&one), g);
+        assert_eq!(P::mul_base_point2(&one), h);
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        use p256::{ProjectivePoint, Scalar};
+
+        let table = FixedBaseTable::new(
+            ProjectivePoint::generator(),
+            ProjectivePoint::identity(),
+            256,
+        );
+        let mut scalars = vec![[0u8; 32], [0xffu8; 32]];
+        scalars[1][0] = 0x7f;
+        let mut small = [0u8; 32];
+        small[31] = 17;
+        scalars.push(small);
+        let random: [u8; 32] = rand::random();
+        scalars.push(random);
+        for k in scalars.iter() {
+            let expected = ProjectivePoint::generator() * Scalar::from_bytes_reduced(&(*k).into());
+            assert_eq!(table.mul(k), expected);
+        }
+        assert_eq!(table.mul(&[0u8; 32]), ProjectivePoint::identity());
+    }
+}
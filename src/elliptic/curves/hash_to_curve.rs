@@ -0,0 +1,534 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// Building blocks of hashing to elliptic curves as specified in RFC 9380
+// (https://www.rfc-editor.org/rfc/rfc9380.html): expand_message_xmd, hash_to_field and the
+// simplified SWU, isogeny and Elligator 2 maps. The curves put them together in their
+// implementation of HashToCurve (see traits.rs) with their own constants.
+//
+// Field arithmetic is done on BigInt, it is neither fast nor constant time.
+
+use digest::generic_array::typenum::Unsigned;
+use digest::{BlockInput, Digest};
+
+use crate::arithmetic::traits::{Converter, Modulo, NumberTests};
+use crate::BigInt;
+
+const OVERSIZE_DST_SALT: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// expand_message_xmd (RFC 9380 section 5.3.1): expands msg to len_in_bytes uniformly random
+/// bytes, using the hash function D and the domain separation tag dst.
+pub fn expand_message_xmd<D>(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8>
+where
+    D: Digest + BlockInput,
+{
+    let b_in_bytes = D::OutputSize::to_usize();
+    let s_in_bytes = D::BlockSize::to_usize();
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    assert!(
+        ell <= 255 && len_in_bytes <= 65535,
+        "expand_message_xmd: requested too many bytes"
+    );
+
+    // DST_prime = DST || I2OSP(len(DST), 1), a DST longer than 255 bytes is hashed first
+    let mut dst_prime = if dst.len() > 255 {
+        D::new()
+            .chain(OVERSIZE_DST_SALT)
+            .chain(dst)
+            .result()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = D::new()
+        .chain(vec![0u8; s_in_bytes])
+        .chain(msg)
+        .chain([(len_in_bytes >> 8) as u8, len_in_bytes as u8, 0u8])
+        .chain(&dst_prime)
+        .result();
+    let mut b_i = D::new().chain(&b_0).chain([1u8]).chain(&dst_prime).result();
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=ell {
+        let b_0_xor_b_i: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = D::new()
+            .chain(b_0_xor_b_i)
+            .chain([i as u8])
+            .chain(&dst_prime)
+            .result();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// hash_to_field (RFC 9380 section 5.2) for a prime field GF(p): returns count elements, each one
+/// computed from l bytes of expand_message_xmd.
+pub fn hash_to_field<D>(msg: &[u8], dst: &[u8], count: usize, p: &BigInt, l: usize) -> Vec<BigInt>
+where
+    D: Digest + BlockInput,
+{
+    let uniform_bytes = expand_message_xmd::<D>(msg, dst, count * l);
+    uniform_bytes
+        .chunks(l)
+        .map(|tv| BigInt::mod_add(&BigInt::from(tv), &BigInt::zero(), p))
+        .collect()
+}
+
+/// The field operations needed by the maps to curves.
+pub trait Field {
+    type Element: Clone + PartialEq;
+
+    fn zero(&self) -> Self::Element;
+    fn one(&self) -> Self::Element;
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn neg(&self, a: &Self::Element) -> Self::Element;
+    /// inv0 of RFC 9380: the inverse of a, or 0 for a = 0
+    fn inv0(&self, a: &Self::Element) -> Self::Element;
+    fn is_zero(&self, a: &Self::Element) -> bool;
+    fn is_square(&self, a: &Self::Element) -> bool;
+    /// any square root of a, the maps fix the sign themselves using sgn0
+    fn sqrt(&self, a: &Self::Element) -> Option<Self::Element>;
+    /// sgn0 of RFC 9380 section 4.1
+    fn sgn0(&self, a: &Self::Element) -> bool;
+
+    fn square(&self, a: &Self::Element) -> Self::Element {
+        self.mul(a, a)
+    }
+
+    fn div(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.mul(a, &self.inv0(b))
+    }
+
+    /// evaluates the polynomial with the given coefficients (lowest degree first) at x
+    fn eval(&self, coefficients: &[Self::Element], x: &Self::Element) -> Self::Element {
+        coefficients
+            .iter()
+            .rev()
+            .fold(self.zero(), |acc, c| self.add(&self.mul(&acc, x), c))
+    }
+}
+
+/// GF(p) for p = 3 mod 4 or p = 5 mod 8, which covers all the curves we hash to.
+pub struct PrimeField {
+    p: BigInt,
+}
+
+impl PrimeField {
+    pub fn new(p: BigInt) -> PrimeField {
+        PrimeField { p }
+    }
+
+    pub fn from_hex(p: &str) -> PrimeField {
+        PrimeField::new(BigInt::from_hex(p))
+    }
+
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    fn reduce(&self, a: &BigInt) -> BigInt {
+        BigInt::mod_add(a, &BigInt::zero(), &self.p)
+    }
+}
+
+impl Field for PrimeField {
+    type Element = BigInt;
+
+    fn zero(&self) -> BigInt {
+        BigInt::zero()
+    }
+
+    fn one(&self) -> BigInt {
+        BigInt::one()
+    }
+
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::mod_add(a, b, &self.p)
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::mod_sub(a, b, &self.p)
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::mod_mul(a, b, &self.p)
+    }
+
+    fn neg(&self, a: &BigInt) -> BigInt {
+        BigInt::mod_sub(&BigInt::zero(), a, &self.p)
+    }
+
+    fn inv0(&self, a: &BigInt) -> BigInt {
+        BigInt::mod_pow(a, &(&self.p - BigInt::from(2)), &self.p)
+    }
+
+    fn is_zero(&self, a: &BigInt) -> bool {
+        self.reduce(a) == BigInt::zero()
+    }
+
+    // Euler's criterion, 0 is a square
+    fn is_square(&self, a: &BigInt) -> bool {
+        let exp = (&self.p - BigInt::one()) / BigInt::from(2);
+        BigInt::mod_pow(a, &exp, &self.p) != &self.p - BigInt::one()
+    }
+
+    fn sqrt(&self, a: &BigInt) -> Option<BigInt> {
+        let p = &self.p;
+        let a = self.reduce(a);
+        let candidate = if p % BigInt::from(4) == BigInt::from(3) {
+            BigInt::mod_pow(&a, &((p + BigInt::one()) / BigInt::from(4)), p)
+        } else {
+            // p = 5 mod 8, multiply by sqrt(-1) = 2^((p - 1) / 4) if needed
+            let candidate = BigInt::mod_pow(&a, &((p + BigInt::from(3)) / BigInt::from(8)), p);
+            if self.square(&candidate) == a {
+                candidate
+            } else {
+                let exp = (p - BigInt::one()) / BigInt::from(4);
+                self.mul(&candidate, &BigInt::mod_pow(&BigInt::from(2), &exp, p))
+            }
+        };
+        if self.square(&candidate) == a {
+            Some(candidate)
+        } else {
+            None
+        }
+    }
+
+    fn sgn0(&self, a: &BigInt) -> bool {
+        !BigInt::is_even(&self.reduce(a))
+    }
+}
+
+/// GF(p^2) = GF(p)[u] / (u^2 + 1) for p = 3 mod 4, elements are (c0, c1) = c0 + c1 * u.
+pub struct QuadraticField {
+    base: PrimeField,
+}
+
+impl QuadraticField {
+    pub fn new(base: PrimeField) -> QuadraticField {
+        QuadraticField { base }
+    }
+
+    pub fn base(&self) -> &PrimeField {
+        &self.base
+    }
+
+    fn norm(&self, a: &(BigInt, BigInt)) -> BigInt {
+        let f = &self.base;
+        f.add(&f.square(&a.0), &f.square(&a.1))
+    }
+}
+
+impl Field for QuadraticField {
+    type Element = (BigInt, BigInt);
+
+    fn zero(&self) -> (BigInt, BigInt) {
+        (BigInt::zero(), BigInt::zero())
+    }
+
+    fn one(&self) -> (BigInt, BigInt) {
+        (BigInt::one(), BigInt::zero())
+    }
+
+    fn add(&self, a: &(BigInt, BigInt), b: &(BigInt, BigInt)) -> (BigInt, BigInt) {
+        (self.base.add(&a.0, &b.0), self.base.add(&a.1, &b.1))
+    }
+
+    fn sub(&self, a: &(BigInt, BigInt), b: &(BigInt, BigInt)) -> (BigInt, BigInt) {
+        (self.base.sub(&a.0, &b.0), self.base.sub(&a.1, &b.1))
+    }
+
+    fn mul(&self, a: &(BigInt, BigInt), b: &(BigInt, BigInt)) -> (BigInt, BigInt) {
+        let f = &self.base;
+        (
+            f.sub(&f.mul(&a.0, &b.0), &f.mul(&a.1, &b.1)),
+            f.add(&f.mul(&a.0, &b.1), &f.mul(&a.1, &b.0)),
+        )
+    }
+
+    fn neg(&self, a: &(BigInt, BigInt)) -> (BigInt, BigInt) {
+        (self.base.neg(&a.0), self.base.neg(&a.1))
+    }
+
+    // (c0 - c1 * u) / (c0^2 + c1^2)
+    fn inv0(&self, a: &(BigInt, BigInt)) -> (BigInt, BigInt) {
+        let f = &self.base;
+        let norm_inv = f.inv0(&self.norm(a));
+        (f.mul(&a.0, &norm_inv), f.neg(&f.mul(&a.1, &norm_inv)))
+    }
+
+    fn is_zero(&self, a: &(BigInt, BigInt)) -> bool {
+        self.base.is_zero(&a.0) && self.base.is_zero(&a.1)
+    }
+
+    // a is a square of GF(p^2) if and only if its norm is a square of GF(p)
+    fn is_square(&self, a: &(BigInt, BigInt)) -> bool {
+        self.base.is_square(&self.norm(a))
+    }
+
+    // with n = sqrt(c0^2 + c1^2), x0 = sqrt((c0 + n) / 2) or sqrt((c0 - n) / 2) and
+    // x1 = c1 / (2 * x0)
+    fn sqrt(&self, a: &(BigInt, BigInt)) -> Option<(BigInt, BigInt)> {
+        let f = &self.base;
+        if f.is_zero(&a.1) {
+            return match f.sqrt(&a.0) {
+                Some(x0) => Some((x0, BigInt::zero())),
+                None => f.sqrt(&f.neg(&a.0)).map(|x1| (BigInt::zero(), x1)),
+            };
+        }
+        let n = f.sqrt(&self.norm(a))?;
+        let two_inv = f.inv0(&BigInt::from(2));
+        let delta = f.mul(&f.add(&a.0, &n), &two_inv);
+        let x0 = match f.sqrt(&delta) {
+            Some(x0) => x0,
+            None => f.sqrt(&f.mul(&f.sub(&a.0, &n), &two_inv))?,
+        };
+        let x1 = f.div(&a.1, &f.add(&x0, &x0));
+        let root = (x0, x1);
+        if self.square(&root) == (f.reduce(&a.0), f.reduce(&a.1)) {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    fn sgn0(&self, a: &(BigInt, BigInt)) -> bool {
+        let f = &self.base;
+        f.sgn0(&a.0) || (f.is_zero(&a.0) && f.sgn0(&a.1))
+    }
+}
+
+/// The simplified SWU map (RFC 9380 section 6.6.2) to y^2 = x^3 + A * x + B, optionally followed by
+/// an isogeny for curves with A * B = 0 (section 6.6.3).
+pub struct SimpleSwu<F: Field> {
+    pub field: F,
+    a: F::Element,
+    b: F::Element,
+    z: F::Element,
+    isogeny: Option<Isogeny<F>>,
+}
+
+impl<F: Field> SimpleSwu<F> {
+    pub fn new(field: F, a: F::Element, b: F::Element, z: F::Element) -> SimpleSwu<F> {
+        SimpleSwu {
+            field,
+            a,
+            b,
+            z,
+            isogeny: None,
+        }
+    }
+
+    pub fn with_isogeny(mut self, isogeny: Isogeny<F>) -> SimpleSwu<F> {
+        self.isogeny = Some(isogeny);
+        self
+    }
+
+    /// returns the affine coordinates of the point u is mapped to, None for the point at infinity
+    /// (which only the isogeny can output).
+    pub fn map_to_curve(&self, u: &F::Element) -> Option<(F::Element, F::Element)> {
+        let f = &self.field;
+        let (a, b, z) = (&self.a, &self.b, &self.z);
+        let g = |x: &F::Element| f.add(&f.mul(&f.add(&f.square(x), a), x), b);
+
+        let z_u2 = f.mul(z, &f.square(u));
+        let tv1 = f.inv0(&f.add(&f.square(&z_u2), &z_u2));
+        let x1 = if f.is_zero(&tv1) {
+            f.div(b, &f.mul(z, a))
+        } else {
+            f.mul(&f.div(&f.neg(b), a), &f.add(&f.one(), &tv1))
+        };
+        let gx1 = g(&x1);
+        let (x, y) = if f.is_square(&gx1) {
+            let y = f.sqrt(&gx1).unwrap();
+            (x1, y)
+        } else {
+            let x2 = f.mul(&z_u2, &x1);
+            let y = f.sqrt(&g(&x2)).unwrap();
+            (x2, y)
+        };
+        let y = if f.sgn0(u) != f.sgn0(&y) {
+            f.neg(&y)
+        } else {
+            y
+        };
+
+        match self.isogeny {
+            Some(ref isogeny) => isogeny.map(f, &x, &y),
+            None => Some((x, y)),
+        }
+    }
+}
+
+/// A rational map (x, y) -> (x_num(x) / x_den(x), y * y_num(x) / y_den(x)), the coefficients of
+/// the polynomials are given lowest degree first.
+pub struct Isogeny<F: Field> {
+    x_num: Vec<F::Element>,
+    x_den: Vec<F::Element>,
+    y_num: Vec<F::Element>,
+    y_den: Vec<F::Element>,
+}
+
+impl<F: Field> Isogeny<F> {
+    pub fn new(
+        x_num: Vec<F::Element>,
+        x_den: Vec<F::Element>,
+        y_num: Vec<F::Element>,
+        y_den: Vec<F::Element>,
+    ) -> Isogeny<F> {
+        Isogeny {
+            x_num,
+            x_den,
+            y_num,
+            y_den,
+        }
+    }
+
+    fn map(&self, f: &F, x: &F::Element, y: &F::Element) -> Option<(F::Element, F::Element)> {
+        let x_den = f.eval(&self.x_den, x);
+        let y_den = f.eval(&self.y_den, x);
+        if f.is_zero(&x_den) || f.is_zero(&y_den) {
+            return None;
+        }
+        let x_out = f.div(&f.eval(&self.x_num, x), &x_den);
+        let y_out = f.mul(y, &f.div(&f.eval(&self.y_num, x), &y_den));
+        Some((x_out, y_out))
+    }
+}
+
+impl Isogeny<PrimeField> {
+    pub fn from_hex(
+        x_num: &[&str],
+        x_den: &[&str],
+        y_num: &[&str],
+        y_den: &[&str],
+    ) -> Isogeny<PrimeField> {
+        let parse = |coefficients: &[&str]| {
+            coefficients
+                .iter()
+                .map(|c| BigInt::from_hex(c))
+                .collect::<Vec<BigInt>>()
+        };
+        Isogeny::new(parse(x_num), parse(x_den), parse(y_num), parse(y_den))
+    }
+}
+
+impl Isogeny<QuadraticField> {
+    pub fn from_hex_pairs(
+        x_num: &[(&str, &str)],
+        x_den: &[(&str, &str)],
+        y_num: &[(&str, &str)],
+        y_den: &[(&str, &str)],
+    ) -> Isogeny<QuadraticField> {
+        let parse = |coefficients: &[(&str, &str)]| {
+            coefficients
+                .iter()
+                .map(|(c0, c1)| (BigInt::from_hex(c0), BigInt::from_hex(c1)))
+                .collect::<Vec<(BigInt, BigInt)>>()
+        };
+        Isogeny::new(parse(x_num), parse(x_den), parse(y_num), parse(y_den))
+    }
+}
+
+/// The Elligator 2 map (RFC 9380 section 6.7.1) to the Montgomery curve y^2 = x^3 + J * x^2 + x
+/// (that is K = 1) with the non square z.
+pub fn map_to_curve_elligator2(
+    f: &PrimeField,
+    j: &BigInt,
+    z: &BigInt,
+    u: &BigInt,
+) -> (BigInt, BigInt) {
+    let g = |x: &BigInt| f.mul(&f.add(&f.mul(&f.add(x, j), x), &BigInt::one()), x);
+
+    let tv1 = f.inv0(&f.add(&BigInt::one(), &f.mul(z, &f.square(u))));
+    let x1 = if f.is_zero(&tv1) {
+        f.neg(j)
+    } else {
+        f.mul(&f.neg(j), &tv1)
+    };
+    let gx1 = g(&x1);
+    let (x, y, sign) = if f.is_square(&gx1) {
+        (x1, f.sqrt(&gx1).unwrap(), true)
+    } else {
+        let x2 = f.sub(&f.neg(&x1), j);
+        let y = f.sqrt(&g(&x2)).unwrap();
+        (x2, y, false)
+    };
+    let y = if f.sgn0(&y) != sign { f.neg(&y) } else { y };
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_message_xmd;
+    use crate::elliptic::curves::traits::HashToCurve;
+    use crate::test_for_all_elliptic_curves;
+    use sha2::{Sha256, Sha512};
+
+    // RFC 9380 appendix K.1 and K.3
+    #[test]
+    fn test_expand_message_xmd() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha256>(b"", dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha256>(b"abc", dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha256>(b"", dst, 0x80)),
+            "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+        );
+
+        let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha512>(b"abc", dst, 0x20)),
+            "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha512>(b"", dst, 0x80)),
+            "41b037d1734a5f8df225dd8c7de38f851efdb45c372887be655212d07251b921b052b62eaed99b46f72f2ef4cc96bfaf254ebbbec091e1a3b9e4fb5e5b619d2e0c5414800a1d882b62bb5cd1778f098b8eb6cb399d5d9d18f5d5842cf5d13d7eb00a7cff859b605da678b318bd0e65ebff70bec88c753b159a805d2c89c55961"
+        );
+    }
+
+    // RFC 9380 appendix K.2, a DST longer than 255 bytes
+    #[test]
+    fn test_expand_message_xmd_long_dst() {
+        let mut dst = b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-".to_vec();
+        dst.extend(vec![b'1'; 208]);
+        assert_eq!(
+            hex::encode(expand_message_xmd::<Sha256>(b"", &dst, 0x20)),
+            "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3"
+        );
+    }
+
+    test_for_all_elliptic_curves!(test_nums_generators);
+    fn test_nums_generators<P>()
+    where
+        P: HashToCurve + std::fmt::Debug,
+    {
+        let generators = P::nums_generators(b"test", 4);
+        assert_eq!(generators.len(), 4);
+        for (i, h) in generators.iter().enumerate() {
+            assert!(!h.is_zero());
+            assert!(h.is_in_prime_order_subgroup());
+            assert!(*h != P::generator());
+            for h_j in &generators[i + 1..] {
+                assert!(h != h_j);
+            }
+        }
+        // deterministic and a prefix of a longer derivation
+        assert_eq!(P::nums_generators(b"test", 2)[..], generators[..2]);
+        assert!(P::nums_generators(b"test2", 1)[0] != generators[0]);
+    }
+}
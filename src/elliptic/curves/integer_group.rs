@@ -1,14 +1,17 @@
 use crate::arithmetic::traits::{Converter, Modulo, Samplable};
 use crate::elliptic::curves::traits::{ECPoint, ECScalar};
 use crate::{BigInt, ErrorKey};
+use rand::{CryptoRng, RngCore};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub};
 use std::ptr;
 use std::sync::atomic;
 use zeroize::Zeroize;
 use std::borrow::Borrow;
+use serde::{de, Deserialize, Deserializer};
 
 #[derive(Clone, Debug,Serialize, Deserialize)]
 pub struct Zqg {
+    #[serde(deserialize_with = "deserialize_element")]
     g: BigInt,
 }
 
@@ -59,9 +62,9 @@ impl From<&BigInt> for Zqf {
 impl ECScalar for Zqf {
     type SecretKey = Zqf;
 
-    fn new_random() -> Self {
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
         Zqf {
-            f: BigInt::sample_below(&FE::q()),
+            f: BigInt::sample_below_with_rng(&FE::q(), rng),
         }
     }
 
@@ -199,6 +202,14 @@ impl ECPoint for Zqg {
         Zqg { g: g.clone() }
     }
 
+    fn zero() -> Self {
+        Zqg::identity()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.g == BigInt::zero()
+    }
+
     fn get_element(&self) -> Self::PublicKey {
         self.clone()
     }
@@ -216,9 +227,14 @@ impl ECPoint for Zqg {
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self, ErrorKey> {
-        Ok(Zqg {
+        let point = Zqg {
             g: BigInt::from(bytes),
-        })
+        };
+        if point.is_valid() {
+            Ok(point)
+        } else {
+            Err(ErrorKey::InvalidPublicKey)
+        }
     }
 
     fn pk_to_key_slice(&self) -> Vec<u8> {
@@ -253,6 +269,24 @@ impl ECPoint for Zqg {
     fn from_coor(x: &BigInt, y: &BigInt) -> Self {
         unimplemented!()
     }
+
+    // elements are integers in [0, Q), the group has prime order Q
+    fn is_valid(&self) -> bool {
+        self.g >= BigInt::zero() && self.g < *Q
+    }
+}
+
+// elements received from counterparties must be reduced modulo Q
+fn deserialize_element<'de, D>(deserializer: D) -> Result<BigInt, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let element = BigInt::deserialize(deserializer)?;
+    if element >= BigInt::zero() && element < *Q {
+        Ok(element)
+    } else {
+        Err(de::Error::custom("element is not in the group"))
+    }
 }
 
 impl Mul<Zqf> for Zqg {
@@ -358,6 +392,21 @@ mod tests {
         //zqg2.unwrap().eq(&zqg);
     }
 
+    #[test]
+    fn test_zqg_from_bytes_out_of_range() {
+        let q_bytes = BigInt::to_vec(&Q);
+        assert!(Zqg::from_bytes(&q_bytes).is_err());
+        let q_minus_one = BigInt::to_vec(&(Q.clone() - BigInt::one()));
+        assert!(Zqg::from_bytes(&q_minus_one).is_ok());
+
+        let s = serde_json::to_string(&Zqg { g: Q.clone() }).unwrap();
+        let des: Result<Zqg, _> = serde_json::from_str(&s);
+        assert!(des.is_err());
+        let s = serde_json::to_string(&Zqg::generator()).unwrap();
+        let des: Zqg = serde_json::from_str(&s).unwrap();
+        assert_eq!(des, Zqg::generator());
+    }
+
     #[test]
     fn test_zqg_base2() {
         //println!("{:?}",Zqg::base_point2().g);
@@ -7,3 +7,6 @@ pub mod rsa_group;
 pub mod secp256_k1;
 pub mod traits;
 pub mod integer_group;
+pub mod fixed_base;
+pub mod multi_scalar_mul;
+pub mod hash_to_curve;
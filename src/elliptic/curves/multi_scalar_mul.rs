@@ -0,0 +1,222 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: <https://github.com/KZen-networks/curv/blob/master/LICENSE>
+*/
+
+// Multi-scalar multiplication: computes sum(k_i * P_i) for many points at once.
+// The algorithms are written against any group type with an addition so that curves can run
+// them on their native (e.g. projective) representation. The point at infinity is modelled
+// by `None` since the group type is only required to have an addition.
+//
+// Straus: https://www.jstor.org/stable/2310929 (interleaved windows, one table per point)
+// Pippenger: bucket method as described in https://eprint.iacr.org/2012/549.pdf section 4
+
+use std::ops::Add;
+
+use crate::arithmetic::traits::BitManipulation;
+use crate::BigInt;
+
+// below this number of points Straus is faster than Pippenger
+pub const PIPPENGER_THRESHOLD: usize = 32;
+const STRAUS_WINDOW: usize = 4;
+
+pub fn multi_scalar_mul<G>(points: &[G], scalars: &[BigInt]) -> Option<G>
+where
+    G: Clone + Add<Output = G>,
+{
+    assert_eq!(points.len(), scalars.len());
+    if points.len() < PIPPENGER_THRESHOLD {
+        straus(points, scalars)
+    } else {
+        pippenger(points, scalars)
+    }
+}
+
+pub fn straus<G>(points: &[G], scalars: &[BigInt]) -> Option<G>
+where
+    G: Clone + Add<Output = G>,
+{
+    let w = STRAUS_WINDOW;
+    let windows = num_windows(scalars, w);
+    let digits: Vec<Vec<usize>> = scalars.iter().map(|k| to_digits(k, w, windows)).collect();
+
+    // tables[i][j] = (j + 1) * P_i
+    let tables: Vec<Vec<G>> = points
+        .iter()
+        .map(|p| {
+            let mut table = vec![p.clone()];
+            for j in 1..(1 << w) - 1 {
+                let next = table[j - 1].clone() + p.clone();
+                table.push(next);
+            }
+            table
+        })
+        .collect();
+
+    let mut acc: Option<G> = None;
+    for window in (0..windows).rev() {
+        acc = double_n(acc, w);
+        for (table, digits) in tables.iter().zip(digits.iter()) {
+            let d = digits[window];
+            if d != 0 {
+                acc = add(acc, &table[d - 1]);
+            }
+        }
+    }
+    acc
+}
+
+pub fn pippenger<G>(points: &[G], scalars: &[BigInt]) -> Option<G>
+where
+    G: Clone + Add<Output = G>,
+{
+    let c = pippenger_window(points.len());
+    let windows = num_windows(scalars, c);
+    let digits: Vec<Vec<usize>> = scalars.iter().map(|k| to_digits(k, c, windows)).collect();
+
+    let mut acc: Option<G> = None;
+    for window in (0..windows).rev() {
+        acc = double_n(acc, c);
+
+        let mut buckets: Vec<Option<G>> = vec![None; (1 << c) - 1];
+        for (p, digits) in points.iter().zip(digits.iter()) {
+            let d = digits[window];
+            if d != 0 {
+                buckets[d - 1] = add(buckets[d - 1].take(), p);
+            }
+        }
+
+        // sum_j (j + 1) * buckets[j] computed with running sums
+        let mut running_sum: Option<G> = None;
+        let mut window_sum: Option<G> = None;
+        for bucket in buckets.into_iter().rev() {
+            if let Some(b) = bucket {
+                running_sum = add(running_sum, &b);
+            }
+            if let Some(s) = &running_sum {
+                window_sum = add(window_sum, s);
+            }
+        }
+        if let Some(s) = window_sum {
+            acc = add(acc, &s);
+        }
+    }
+    acc
+}
+
+// window size ~ log2(n), which balances the bucket aggregation against the additions
+fn pippenger_window(n: usize) -> usize {
+    let bits = (0usize.leading_zeros() - n.leading_zeros()) as usize;
+    if bits < 5 {
+        4
+    } else {
+        bits - 1
+    }
+}
+
+fn num_windows(scalars: &[BigInt], w: usize) -> usize {
+    let max_bits = scalars.iter().map(|k| k.bit_length()).max().unwrap_or(0);
+    max_bits.div_ceil(w)
+}
+
+// little endian base 2^w digits of k
+pub(crate) fn to_digits(k: &BigInt, w: usize, windows: usize) -> Vec<usize> {
+    assert!(*k >= BigInt::zero(), "scalars must be non negative");
+    (0..windows)
+        .map(|window| {
+            (0..w).fold(0, |digit, bit| {
+                if k.test_bit(window * w + bit) {
+                    digit | (1 << bit)
+                } else {
+                    digit
+                }
+            })
+        })
+        .collect()
+}
+
+fn add<G>(acc: Option<G>, p: &G) -> Option<G>
+where
+    G: Clone + Add<Output = G>,
+{
+    match acc {
+        None => Some(p.clone()),
+        Some(a) => Some(a + p.clone()),
+    }
+}
+
+fn double_n<G>(acc: Option<G>, n: usize) -> Option<G>
+where
+    G: Clone + Add<Output = G>,
+{
+    acc.map(|mut a| {
+        for _ in 0..n {
+            a = a.clone() + a;
+        }
+        a
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pippenger, straus};
+    use crate::elliptic::curves::traits::*;
+    use crate::test_for_all_curves;
+    use crate::BigInt;
+
+    fn naive<P>(points: &[P], scalars: &[P::Scalar]) -> P
+    where
+        P: ECPoint + Clone,
+        P::Scalar: Clone,
+    {
+        points
+            .iter()
+            .zip(scalars.iter())
+            .fold(P::zero(), |acc, (p, k)| acc + p.clone() * k.clone())
+    }
+
+    fn random_input<P>(n: usize) -> (Vec<P>, Vec<P::Scalar>)
+    where
+        P: ECPoint + Clone,
+    {
+        let g: P = ECPoint::generator();
+        let points = (0..n)
+            .map(|_| g.clone() * P::Scalar::new_random())
+            .collect::<Vec<P>>();
+        let scalars = (0..n).map(|_| P::Scalar::new_random()).collect();
+        (points, scalars)
+    }
+
+    test_for_all_curves!(test_multi_scalar_mul);
+    fn test_multi_scalar_mul<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone,
+    {
+        for n in &[1, 2, 5, 40] {
+            let (points, scalars) = random_input::<P>(*n);
+            assert_eq!(
+                P::multi_scalar_mul(&points, &scalars),
+                naive(&points, &scalars)
+            );
+        }
+    }
+
+    test_for_all_curves!(test_straus_and_pippenger_agree);
+    fn test_straus_and_pippenger_agree<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+        P::Scalar: Clone,
+    {
+        let (points, scalars) = random_input::<P>(10);
+        let scalars_bn = scalars
+            .iter()
+            .map(|k| k.to_big_int())
+            .collect::<Vec<BigInt>>();
+        let expected = naive(&points, &scalars);
+        assert_eq!(straus(&points, &scalars_bn).unwrap(), expected);
+        assert_eq!(pippenger(&points, &scalars_bn).unwrap(), expected);
+    }
+}
@@ -1,6 +1,9 @@
 // NIST P-256 elliptic curve utility functions.
 
-use super::traits::{ECPoint, ECScalar};
+use super::fixed_base::FixedBaseTable;
+use super::hash_to_curve::{hash_to_field, Field, PrimeField, SimpleSwu};
+use super::multi_scalar_mul::multi_scalar_mul;
+use super::traits::{ECPoint, ECScalar, HashToCurve};
 use crate::arithmetic::traits::{Converter, Modulo};
 use crate::BigInt;
 use crate::ErrorKey;
@@ -9,11 +12,12 @@ use generic_array::GenericArray;
 use p256::ecdsa::VerifyKey;
 use p256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use p256::{AffinePoint, EncodedPoint, ProjectivePoint, Scalar};
-use rand::{thread_rng, Rng};
+use rand::{CryptoRng, RngCore};
 use serde::de;
 use serde::de::Visitor;
 use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
 use std::ops::{Add, Mul, Sub};
 use std::sync::atomic;
 use std::{fmt, ptr};
@@ -28,25 +32,42 @@ pub struct Secp256r1Scalar {
     fe: SK,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug)]
 pub struct Secp256r1Point {
     purpose: &'static str,
-    ge: PK,
+    // None is the point at infinity, which a VerifyKey cannot represent
+    ge: Option<PK>,
 }
 pub type GE = Secp256r1Point;
 pub type FE = Secp256r1Scalar;
 
-/* X coordinate of a point of unknown discrete logarithm.
-Computed using a deterministic algorithm with the generator as input.
-See test_base_point2 */
-const BASE_POINT2_X: [u8; 32] = [
-    0x70, 0xf7, 0x2b, 0xba, 0xc4, 0x0e, 0x8a, 0x59, 0x4c, 0x91, 0xa7, 0xba, 0xc3, 0x76, 0x59, 0x27,
-    0x89, 0x10, 0x76, 0x4c, 0xd7, 0xc2, 0x0a, 0x7d, 0x65, 0xa5, 0x9a, 0x04, 0xb0, 0xac, 0x2a, 0xde,
-];
-const BASE_POINT2_Y: [u8; 32] = [
-    0x30, 0xe2, 0xfe, 0xb3, 0x8d, 0x82, 0x4e, 0x0e, 0xa2, 0x95, 0x2f, 0x2a, 0x48, 0x5b, 0xbc, 0xdd,
-    0x4c, 0x72, 0x8a, 0x74, 0xf4, 0xfa, 0xc7, 0xdc, 0x0d, 0xc9, 0x90, 0x8d, 0x9a, 0x8d, 0xc1, 0xa4,
-];
+lazy_static::lazy_static! {
+    static ref GENERATOR: Secp256r1Point = Secp256r1Point {
+        purpose: "base_fe",
+        ge: Some(
+            VerifyKey::from_encoded_point(&AffinePoint::generator().to_encoded_point(true))
+                .unwrap(),
+        ),
+    };
+
+    /* base_point2 is the first NUMS generator seeded with the encoded generator, i.e. the hash
+    of G || I2OSP(0, 4) with DST "CURV-V01-CS01-NUMS-with-P256_XMD:SHA-256_SSWU_RO_".
+    See test_base_point2 */
+    static ref BASE_POINT2: Secp256r1Point = {
+        let g = Secp256r1Point::generator();
+        let h = Secp256r1Point::nums_generators(&g.pk_to_key_slice(), 1)[0];
+        Secp256r1Point {
+            purpose: "random",
+            ge: h.ge,
+        }
+    };
+
+    static ref GENERATOR_TABLE: FixedBaseTable<ProjectivePoint> =
+        FixedBaseTable::new(ProjectivePoint::generator(), ProjectivePoint::identity(), 256);
+
+    static ref BASE_POINT2_TABLE: FixedBaseTable<ProjectivePoint> =
+        FixedBaseTable::new(BASE_POINT2.to_projective(), ProjectivePoint::identity(), 256);
+}
 
 impl Zeroize for Secp256r1Scalar {
     fn zeroize(&mut self) {
@@ -59,9 +80,9 @@ impl Zeroize for Secp256r1Scalar {
 impl ECScalar for Secp256r1Scalar {
     type SecretKey = SK;
 
-    fn new_random() -> Secp256r1Scalar {
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Secp256r1Scalar {
         let mut arr = [0u8; 32];
-        thread_rng().fill(&mut arr[..]);
+        rng.fill_bytes(&mut arr[..]);
         let gen_arr: GenericArray<u8, U32> = *GenericArray::from_slice(&arr);
         Secp256r1Scalar {
             purpose: "random",
@@ -228,6 +249,12 @@ impl PartialEq for Secp256r1Scalar {
     }
 }
 
+impl PartialEq for Secp256r1Point {
+    fn eq(&self, other: &Secp256r1Point) -> bool {
+        self.ge == other.ge
+    }
+}
+
 impl Zeroize for Secp256r1Point {
     fn zeroize(&mut self) {
         unsafe { ptr::write_volatile(self, GE::generator()) };
@@ -242,96 +269,88 @@ impl ECPoint for Secp256r1Point {
     type Scalar = Secp256r1Scalar;
 
     fn base_point2() -> Secp256r1Point {
-        let mut v = vec![4 as u8];
-        v.extend(BASE_POINT2_X.as_ref());
-        v.extend(BASE_POINT2_Y.as_ref());
-        Secp256r1Point::from_bytes(&v).unwrap()
+        *BASE_POINT2
     }
 
     fn generator() -> Secp256r1Point {
+        *GENERATOR
+    }
+
+    fn zero() -> Secp256r1Point {
         Secp256r1Point {
-            purpose: "base_fe",
-            ge: VerifyKey::from_encoded_point(&AffinePoint::generator().to_encoded_point(true))
-                .unwrap(),
+            purpose: "zero",
+            ge: None,
         }
     }
 
+    fn is_zero(&self) -> bool {
+        self.ge.is_none()
+    }
+
+    /// panics on the point at infinity, it has no VerifyKey representation
     fn get_element(&self) -> PK {
-        self.ge.clone()
+        self.ge
+            .expect("the point at infinity has no public key representation")
     }
 
+    /// the point at infinity is mapped to zero
     fn bytes_compressed_to_big_int(&self) -> BigInt {
-        BigInt::from(self.get_element().to_encoded_point(true).as_bytes())
+        match self.ge {
+            Some(ge) => BigInt::from(ge.to_encoded_point(true).as_bytes()),
+            None => BigInt::zero(),
+        }
     }
 
     fn x_coor(&self) -> Option<BigInt> {
-        Some(BigInt::from(EncodedPoint::from(&self.ge).x().as_slice()))
+        self.ge
+            .map(|ge| BigInt::from(EncodedPoint::from(&ge).x().as_slice()))
     }
 
     fn y_coor(&self) -> Option<BigInt> {
         // need this back and forth conversion to get an uncompressed point
-        let tmp = AffinePoint::from_encoded_point(&EncodedPoint::from(&self.ge)).unwrap();
-        Some(BigInt::from(
-            tmp.to_encoded_point(false).y().unwrap().as_slice(),
-        ))
+        self.ge.map(|ge| {
+            let tmp = AffinePoint::from_encoded_point(&EncodedPoint::from(&ge)).unwrap();
+            BigInt::from(tmp.to_encoded_point(false).y().unwrap().as_slice())
+        })
     }
 
+    /// the SEC1 encoding [0x00] is the point at infinity, any other all zero input is rejected
     fn from_bytes(bytes: &[u8]) -> Result<Secp256r1Point, ErrorKey> {
+        if bytes == [0u8] {
+            return Ok(Secp256r1Point::zero());
+        }
         let result = PK::new(&bytes);
         let test = result.map(|pk| Secp256r1Point {
             purpose: "random",
-            ge: pk,
+            ge: Some(pk),
         });
         test.map_err(|_err| ErrorKey::InvalidPublicKey)
     }
 
+    /// the point at infinity is encoded as in SEC1 by the single byte 0x00
     fn pk_to_key_slice(&self) -> Vec<u8> {
-        let tmp = AffinePoint::from_encoded_point(&EncodedPoint::from(&self.ge)).unwrap();
-        tmp.to_encoded_point(false).as_ref().to_vec()
+        match self.ge {
+            Some(ge) => {
+                let tmp = AffinePoint::from_encoded_point(&EncodedPoint::from(&ge)).unwrap();
+                tmp.to_encoded_point(false).as_ref().to_vec()
+            }
+            None => vec![0],
+        }
     }
 
     fn scalar_mul(&self, fe: &SK) -> Secp256r1Point {
-        let point = ProjectivePoint::from(
-            AffinePoint::from_encoded_point(&EncodedPoint::from(&self.ge)).unwrap(),
-        );
         let scalar = Scalar::from_bytes_reduced(&fe.to_bytes());
-        Secp256r1Point {
-            purpose: "mul",
-            ge: VerifyKey::from_encoded_point(&(point * scalar).to_affine().to_encoded_point(true))
-                .unwrap(),
-        }
+        Secp256r1Point::from_projective(&(self.to_projective() * scalar))
     }
 
     fn add_point(&self, other: &PK) -> Secp256r1Point {
-        let point1 = ProjectivePoint::from(
-            AffinePoint::from_encoded_point(&EncodedPoint::from(&self.ge)).unwrap(),
-        );
-        let point2 = ProjectivePoint::from(
-            AffinePoint::from_encoded_point(&EncodedPoint::from(other)).unwrap(),
-        );
-        Secp256r1Point {
-            purpose: "mul",
-            ge: VerifyKey::from_encoded_point(
-                &(point1 + point2).to_affine().to_encoded_point(true),
-            )
-            .unwrap(),
-        }
+        let other = Secp256r1Point::pk_to_projective(other);
+        Secp256r1Point::from_projective(&(self.to_projective() + other))
     }
 
     fn sub_point(&self, other: &PK) -> Secp256r1Point {
-        let point1 = ProjectivePoint::from(
-            AffinePoint::from_encoded_point(&EncodedPoint::from(&self.ge)).unwrap(),
-        );
-        let point2 = ProjectivePoint::from(
-            AffinePoint::from_encoded_point(&EncodedPoint::from(other)).unwrap(),
-        );
-        Secp256r1Point {
-            purpose: "sub",
-            ge: VerifyKey::from_encoded_point(
-                &(point1 - point2).to_affine().to_encoded_point(true),
-            )
-            .unwrap(),
-        }
+        let other = Secp256r1Point::pk_to_projective(other);
+        Secp256r1Point::from_projective(&(self.to_projective() - other))
     }
 
     fn from_coor(x: &BigInt, y: &BigInt) -> Secp256r1Point {
@@ -357,17 +376,77 @@ impl ECPoint for Secp256r1Point {
         let y_arr: GenericArray<u8, U32> = *GenericArray::from_slice(&vec_y);
         Secp256r1Point {
             purpose: "base_fe",
-            ge: VerifyKey::from_encoded_point(&EncodedPoint::from_affine_coordinates(
-                &x_arr, &y_arr, false,
-            ))
-            .unwrap(),
+            ge: Some(
+                VerifyKey::from_encoded_point(&EncodedPoint::from_affine_coordinates(
+                    &x_arr, &y_arr, false,
+                ))
+                .unwrap(),
+            ),
         }
     }
+
+    // runs Straus/Pippenger on projective points to avoid an inversion per addition
+    fn multi_scalar_mul(points: &[Secp256r1Point], scalars: &[Secp256r1Scalar]) -> Secp256r1Point {
+        let points = points
+            .iter()
+            .map(|p| p.to_projective())
+            .collect::<Vec<ProjectivePoint>>();
+        let scalars = scalars
+            .iter()
+            .map(|k| k.to_big_int())
+            .collect::<Vec<BigInt>>();
+        let res = multi_scalar_mul(&points, &scalars).unwrap_or_else(ProjectivePoint::identity);
+        Secp256r1Point::from_projective(&res)
+    }
+
+    fn is_valid(&self) -> bool {
+        match self.ge {
+            Some(ge) => AffinePoint::from_encoded_point(&EncodedPoint::from(&ge))
+                .is_some()
+                .into(),
+            None => true,
+        }
+    }
+
+    fn mul_base(scalar: &Secp256r1Scalar) -> Secp256r1Point {
+        let res = GENERATOR_TABLE.mul(&scalar.fe.to_bytes());
+        Secp256r1Point::from_projective(&res)
+    }
+
+    fn mul_base_point2(scalar: &Secp256r1Scalar) -> Secp256r1Point {
+        let res = BASE_POINT2_TABLE.mul(&scalar.fe.to_bytes());
+        Secp256r1Point::from_projective(&res)
+    }
 }
 
 impl Secp256r1Point {
+    fn to_projective(&self) -> ProjectivePoint {
+        match self.ge {
+            Some(ge) => Secp256r1Point::pk_to_projective(&ge),
+            None => ProjectivePoint::identity(),
+        }
+    }
+
+    fn pk_to_projective(pk: &PK) -> ProjectivePoint {
+        ProjectivePoint::from(AffinePoint::from_encoded_point(&EncodedPoint::from(pk)).unwrap())
+    }
+
+    fn from_projective(point: &ProjectivePoint) -> Secp256r1Point {
+        let point = point.to_affine();
+        let ge = if bool::from(point.is_identity()) {
+            None
+        } else {
+            Some(VerifyKey::from_encoded_point(&point.to_encoded_point(true)).unwrap())
+        };
+        Secp256r1Point { purpose: "mul", ge }
+    }
+
     // derive point from BigInt
     fn from_bigint(i: &BigInt) -> Result<Secp256r1Point, ()> {
+        // bytes_compressed_to_big_int maps the point at infinity to zero
+        if i.is_zero() {
+            return Ok(Secp256r1Point::zero());
+        }
         let vec = BigInt::to_vec(i);
         let point = match Secp256r1Point::from_bytes(&vec) {
             Ok(v) => v,
@@ -377,6 +456,39 @@ impl Secp256r1Point {
     }
 }
 
+lazy_static::lazy_static! {
+    // simplified SWU directly to P-256, constants from RFC 9380 section 8.2
+    static ref SSWU: SimpleSwu<PrimeField> = {
+        let field =
+            PrimeField::from_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+        let a = field.neg(&BigInt::from(3));
+        let b = BigInt::from_hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b");
+        let z = field.neg(&BigInt::from(10));
+        SimpleSwu::new(field, a, b, z)
+    };
+}
+
+impl Secp256r1Point {
+    fn map_to_curve(u: &BigInt) -> Secp256r1Point {
+        let (x, y) = SSWU.map_to_curve(u).unwrap();
+        Secp256r1Point::from_coor(&x, &y)
+    }
+}
+
+impl HashToCurve for Secp256r1Point {
+    const SUITE_ID: &'static str = "P256_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Secp256r1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, 2, SSWU.field.p(), 48);
+        Secp256r1Point::map_to_curve(&u[0]) + Secp256r1Point::map_to_curve(&u[1])
+    }
+
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> Secp256r1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, 1, SSWU.field.p(), 48);
+        Secp256r1Point::map_to_curve(&u[0])
+    }
+}
+
 impl Mul<Secp256r1Scalar> for Secp256r1Point {
     type Output = Secp256r1Point;
     fn mul(self, other: Secp256r1Scalar) -> Self::Output {
@@ -401,42 +513,42 @@ impl<'o> Mul<&'o Secp256r1Scalar> for &'o Secp256r1Point {
 impl Add<Secp256r1Point> for Secp256r1Point {
     type Output = Secp256r1Point;
     fn add(self, other: Secp256r1Point) -> Self::Output {
-        self.add_point(&other.get_element())
+        Secp256r1Point::from_projective(&(self.to_projective() + other.to_projective()))
     }
 }
 
 impl<'o> Add<&'o Secp256r1Point> for Secp256r1Point {
     type Output = Secp256r1Point;
     fn add(self, other: &'o Secp256r1Point) -> Self::Output {
-        self.add_point(&other.get_element())
+        Secp256r1Point::from_projective(&(self.to_projective() + other.to_projective()))
     }
 }
 
 impl<'o> Add<&'o Secp256r1Point> for &'o Secp256r1Point {
     type Output = Secp256r1Point;
     fn add(self, other: &'o Secp256r1Point) -> Self::Output {
-        self.add_point(&other.get_element())
+        Secp256r1Point::from_projective(&(self.to_projective() + other.to_projective()))
     }
 }
 
 impl Sub<Secp256r1Point> for Secp256r1Point {
     type Output = Secp256r1Point;
     fn sub(self, other: Secp256r1Point) -> Self::Output {
-        self.sub_point(&other.get_element())
+        Secp256r1Point::from_projective(&(self.to_projective() - other.to_projective()))
     }
 }
 
 impl<'o> Sub<&'o Secp256r1Point> for Secp256r1Point {
     type Output = Secp256r1Point;
     fn sub(self, other: &'o Secp256r1Point) -> Self::Output {
-        self.sub_point(&other.get_element())
+        Secp256r1Point::from_projective(&(self.to_projective() - other.to_projective()))
     }
 }
 
 impl<'o> Sub<&'o Secp256r1Point> for &'o Secp256r1Point {
     type Output = Secp256r1Point;
     fn sub(self, other: &'o Secp256r1Point) -> Self::Output {
-        self.sub_point(&other.get_element())
+        Secp256r1Point::from_projective(&(self.to_projective() - other.to_projective()))
     }
 }
 
@@ -489,9 +601,7 @@ mod tests {
     use super::{BigInt, ErrorKey};
     use super::{Secp256r1Point, Secp256r1Scalar};
     use crate::arithmetic::traits::{Converter, Modulo, Samplable};
-    use crate::cryptographic_primitives::hashing::hash_sha256::HSha256;
-    use crate::cryptographic_primitives::hashing::traits::Hash;
-    use crate::elliptic::curves::traits::{ECPoint, ECScalar};
+    use crate::elliptic::curves::traits::{ECPoint, ECScalar, HashToCurve};
 
     fn random_point() -> Secp256r1Point {
         let random_scalar: Secp256r1Scalar = Secp256r1Scalar::new_random();
@@ -499,7 +609,7 @@ mod tests {
         let pk = base_point.scalar_mul(&random_scalar.get_element());
         Secp256r1Point {
             purpose: "random_point",
-            ge: pk.get_element(),
+            ge: pk.ge,
         }
     }
 
@@ -632,6 +742,18 @@ mod tests {
         assert!(result.is_ok() | result.is_err())
     }
 
+    #[test]
+    fn test_from_bytes_zero() {
+        // only the SEC1 encoding [0x00] is the point at infinity
+        assert!(Secp256r1Point::from_bytes(&[0u8]).unwrap().is_zero());
+        for len in [0usize, 32, 33, 64, 65].iter() {
+            assert_eq!(
+                Secp256r1Point::from_bytes(&vec![0u8; *len]).unwrap_err(),
+                ErrorKey::InvalidPublicKey
+            );
+        }
+    }
+
     #[test]
     fn test_add_sub() {
         let q = Secp256r1Scalar::q();
@@ -762,26 +884,20 @@ mod tests {
     #[test]
     fn test_base_point2() {
         /* Show that base_point2() is returning a point of unknown discrete logarithm.
-        It is done by using SHA256 repeatedly as a pseudo-random function, with the generator
-        as the initial input, until receiving a valid Secp256r1 point. */
+        It is hashed to the curve with the generator as input. */
 
         let base_point2 = Secp256r1Point::base_point2();
 
         let g = Secp256r1Point::generator();
-        let mut hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        hash = HSha256::create_hash(&[&hash]);
-
-        assert_eq!(hash, base_point2.x_coor().unwrap(),);
-
-        // check that base_point2 is indeed on the curve (from_coor() will fail otherwise)
+        let seed = [&g.pk_to_key_slice()[..], &[0u8; 4]].concat();
+        let dst = b"CURV-V01-CS01-NUMS-with-P256_XMD:SHA-256_SSWU_RO_";
+        assert_eq!(Secp256r1Point::hash_to_curve(&seed, dst), base_point2);
         assert_eq!(
-            Secp256r1Point::from_coor(
-                &base_point2.x_coor().unwrap(),
-                &base_point2.y_coor().unwrap()
-            )
-            .get_element(),
-            base_point2.get_element()
+            base_point2.x_coor().unwrap().to_hex(),
+            "e5334a3749bb2967d3317122289a569ba880e56e7fdf7fd4a179ddeb871c4e34"
         );
+        assert!(!base_point2.is_zero());
+        assert_ne!(base_point2, g);
     }
 
     #[test]
@@ -888,4 +1004,73 @@ mod tests {
         let j: Secp256r1Scalar = ECScalar::from(&BigInt::from(2));
         assert_eq!((j.clone() * i.clone()).to_big_int(), j.to_big_int());
     }
+
+    // RFC 9380 appendix J.1.1
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+        let vectors: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+                "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+            ),
+            (
+                b"abc",
+                "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+                "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+            ),
+            (
+                b"abcdef0123456789",
+                "65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+                "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let p = Secp256r1Point::hash_to_curve(msg, dst);
+            assert_eq!(p.x_coor().unwrap(), BigInt::from_hex(x));
+            assert_eq!(p.y_coor().unwrap(), BigInt::from_hex(y));
+        }
+
+        // the map of u0 for the empty message
+        let u0 =
+            BigInt::from_hex("ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009");
+        let q0 = Secp256r1Point::map_to_curve(&u0);
+        assert_eq!(
+            q0.x_coor().unwrap(),
+            BigInt::from_hex("ab640a12220d3ff283510ff3f4b1953d09fad35795140b1c5d64f313967934d5")
+        );
+        assert_eq!(
+            q0.y_coor().unwrap(),
+            BigInt::from_hex("dccb558863804a881d4fff3455716c836cef230e5209594ddd33d85c565b19b1")
+        );
+    }
+
+    // RFC 9380 appendix J.1.2
+    #[test]
+    fn test_encode_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_NU_";
+        let vectors: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "f871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1",
+                "87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b",
+            ),
+            (
+                b"abc",
+                "fc3f5d734e8dce41ddac49f47dd2b8a57257522a865c124ed02b92b5237befa4",
+                "fe4d197ecf5a62645b9690599e1d80e82c500b22ac705a0b421fac7b47157866",
+            ),
+            (
+                b"abcdef0123456789",
+                "f164c6674a02207e414c257ce759d35eddc7f55be6d7f415e2cc177e5d8faa84",
+                "3aa274881d30db70485368c0467e97da0e73c18c1d00f34775d012b6fcee7f97",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let p = Secp256r1Point::encode_to_curve(msg, dst);
+            assert_eq!(p.x_coor().unwrap(), BigInt::from_hex(x));
+            assert_eq!(p.y_coor().unwrap(), BigInt::from_hex(y));
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::arithmetic::montgomery::ModulusN;
+use crate::arithmetic::number_theory::sqrt_mod_pq;
 use crate::arithmetic::traits::{Converter, Modulo, Samplable};
 use crate::{BigInt};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, Div, DivAssign};
@@ -33,6 +35,10 @@ pub static ref p:BigInt= {
    pub static ref M:BigInt ={
       p.clone()*q.clone()
    };
+   // the Montgomery parameters of M, for the constant time operations modulo M
+   pub static ref M_MODULUS:ModulusN ={
+      ModulusN::new(&M)
+   };
    pub static ref Phi:BigInt ={
       (p.clone()-1)*(q.clone()-1)
    };
@@ -117,9 +123,10 @@ impl Zqf {
         Phi.clone()
     }
 
+    // e is usually secret, ModulusN::pow is constant time like Modulo::mod_pow_sec
     pub fn pow_mod_m(&self, e: &BigInt) -> Self {
         Zqf {
-            f: BigInt::mod_pow(&self.f, e, &M),
+            f: M_MODULUS.pow(&self.f, e),
         }
     }
 
@@ -129,10 +136,9 @@ impl Zqf {
         }
     }
 
-    pub fn sqrt(&self) -> Self {
-        Zqf {
-            f: BigInt::root(&self.f, 2).mod_floor(&M),
-        }
+    // a square root modulo M, computed with the factors p and q
+    pub fn sqrt(&self) -> Option<Self> {
+        sqrt_mod_pq(&self.f, &p, &q).map(|f| Zqf { f })
     }
 
     pub fn square(&self) -> Self {
@@ -141,7 +147,7 @@ impl Zqf {
     }
     fn invert(&self) -> Self {
         Zqf {
-            f: self.f.invert(&M).unwrap(),
+            f: BigInt::mod_inv_sec(&self.f, &M),
         }
     }
 }
@@ -149,7 +155,7 @@ impl Zqf {
 impl Mul<Zqf> for Zqf {
     type Output = Zqf;
     fn mul(mut self, other: Zqf) -> Zqf {
-        self.f = BigInt::mod_mul(&self.f, &other.f, &M);
+        self.f = M_MODULUS.mul(&self.f, &other.f);
         self
     }
 }
@@ -157,7 +163,7 @@ impl Mul<Zqf> for Zqf {
 impl<'o> Mul<&'o Zqf> for Zqf {
     type Output = Zqf;
     fn mul(mut self, other: &'o Zqf) -> Zqf {
-        self.f = BigInt::mod_mul(&self.f, &other.f, &M);
+        self.f = M_MODULUS.mul(&self.f, &other.f);
         self
     }
 }
@@ -224,6 +230,17 @@ mod tests {
         assert_eq!(a, b.sqrt())
     }
 
+    #[test]
+    fn test_zqf_sqrt() {
+        let x = Zqf::get_random_from_z_phi();
+        let square = x.square();
+        let root = square.sqrt().unwrap();
+        assert_eq!(root.square().to_big_int(), square.to_big_int());
+        // -1 is not a square mod a Blum integer
+        let minus_one = Zqf::from(M.clone() - BigInt::one());
+        assert!(minus_one.sqrt().is_none());
+    }
+
     #[test]
     fn test_M_length() {
         use crate::elliptic::curves::integer_group;
@@ -1,6 +1,10 @@
-use crate::arithmetic::traits::{Converter, Modulo, Samplable};
+use crate::arithmetic::montgomery::ModulusN;
+use crate::arithmetic::traits::{Converter, Modulo, Samplable, EGCD};
 use crate::elliptic::curves::traits::{ECPoint, ECScalar};
 use crate::{BigInt, ErrorKey};
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use super::hash_to_curve::expand_message_xmd;
 use super::rsa_group;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub};
 use std::ptr;
@@ -27,6 +31,10 @@ lazy_static::lazy_static! {
    pub static ref Q:BigInt = {
       rsa_group::M.clone()
    };
+   // the Montgomery parameters of Q, for the constant time exponentiations
+   pub static ref Q_MODULUS:ModulusN = {
+      ModulusN::new(&Q)
+   };
    // {
    //  let mut lbslice: [u8; 256] = [0xff as u8; 256];
    //  lbslice[0] = 0x7f;
@@ -34,7 +42,18 @@ lazy_static::lazy_static! {
    //  lb = lb.nextprime();
    //  lb
    // };
+   // the modulus hashed with expand_message_xmd of RFC 9380 and reduced, 16 extra bytes make
+   // the bias negligible. Nobody knows its discrete log, see test_zqg_base2
+   static ref BASE_POINT2:BigInt = {
+      let len = BigInt::to_vec(&Q).len() + 16;
+      let bytes = expand_message_xmd::<Sha256>(&BigInt::to_vec(&Q), BASE_POINT2_DST, len);
+      BigInt::modulus(&BigInt::from(&bytes[..]), &Q)
+   };
 }
+
+/* base_point2 is the hash of the modulus under this DST */
+const BASE_POINT2_DST: &[u8] = b"CURV-V01-CS01-RSA-GROUP-BASE-POINT2";
+
 pub type GE = Zqg;
 pub type FE = Zqf;
 impl From<&BigInt> for Zqf {
@@ -45,9 +64,9 @@ impl From<&BigInt> for Zqf {
 impl ECScalar for Zqf {
     type SecretKey = Zqf;
 
-    fn new_random() -> Self {
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
         Zqf {
-            f: BigInt::sample_below(&FE::q()),
+            f: BigInt::sample_below_with_rng(&FE::q(), rng),
         }
     }
 
@@ -91,7 +110,7 @@ impl ECScalar for Zqf {
 
     fn invert(&self) -> Self {
         Zqf {
-            f: self.f.invert(&Self::q()).unwrap(),
+            f: BigInt::mod_inv_sec(&self.f, &Self::q()),
         }
     }
 }
@@ -168,7 +187,7 @@ impl ECPoint for Zqg {
 
     fn base_point2() -> Self {
         Zqg {
-            g: BigInt::from(1378),
+            g: BASE_POINT2.clone(),
         }
     }
 
@@ -176,6 +195,15 @@ impl ECPoint for Zqg {
         Zqg { g: rsa_group::g.clone() }
     }
 
+    // the group is multiplicative, its identity element is 1
+    fn zero() -> Self {
+        Zqg { g: BigInt::one() }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.g == BigInt::one()
+    }
+
     fn get_element(&self) -> Self::PublicKey {
         self.clone()
     }
@@ -193,9 +221,14 @@ impl ECPoint for Zqg {
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self, ErrorKey> {
-        Ok(Zqg {
+        let point = Zqg {
             g: BigInt::from(bytes),
-        })
+        };
+        if point.is_valid() {
+            Ok(point)
+        } else {
+            Err(ErrorKey::InvalidPublicKey)
+        }
     }
 
     fn pk_to_key_slice(&self) -> Vec<u8> {
@@ -209,9 +242,10 @@ impl ECPoint for Zqg {
         //hex_str
     }
 
+    // the scalar is a secret exponent
     fn scalar_mul(&self, fe: &Self::SecretKey) -> Self {
         Zqg {
-            g: BigInt::mod_pow(&self.g, &fe.f, &Self::q()),
+            g: Q_MODULUS.pow(&self.g, &fe.f),
         }
     }
 
@@ -228,12 +262,18 @@ impl ECPoint for Zqg {
     fn from_coor(x: &BigInt, y: &BigInt) -> Self {
         unimplemented!()
     }
+
+    // elements are the units modulo Q. The order of the group is unknown so there is no
+    // sub group check beyond that.
+    fn is_valid(&self) -> bool {
+        self.g > BigInt::zero() && self.g < *Q && BigInt::egcd(&self.g, &Q).0 == BigInt::one()
+    }
 }
 
 impl Mul<Zqf> for Zqg {
     type Output = Zqg;
     fn mul(mut self, other: Zqf) -> Zqg {
-        self.g = BigInt::mod_pow(&self.g, &other.f, &Self::q());
+        self.g = Q_MODULUS.pow(&self.g, &other.f);
         self
     }
 }
@@ -241,7 +281,7 @@ impl Mul<Zqf> for Zqg {
 impl<'o> Mul<&'o Zqf> for Zqg {
     type Output = Zqg;
     fn mul(mut self, other: &'o Zqf) -> Zqg {
-        self.g = BigInt::mod_pow(&self.g, &other.f, &Self::q());
+        self.g = Q_MODULUS.pow(&self.g, &other.f);
         self
     }
 }
@@ -320,10 +360,25 @@ mod tests {
         //zqg2.unwrap().eq(&zqg);
     }
 
+    #[test]
+    fn test_zqg_from_bytes_not_a_unit() {
+        assert!(Zqg::from_bytes(&BigInt::to_vec(&Q)).is_err());
+        assert!(Zqg::from_bytes(&[0u8]).is_err());
+        assert!(Zqg::from_bytes(&BigInt::to_vec(&rsa_group::p)).is_err());
+        assert!(Zqg::from_bytes(&[1u8]).is_ok());
+        assert!(Zqg::generator().is_valid());
+    }
+
     #[test]
     fn test_zqg_base2() {
         //println!("{:?}",Zqg::base_point2().g);
         let point = Zqg::base_point2();
+        // base_point2 is hashed from the modulus, it is a unit different from the generator
+        let len = BigInt::to_vec(&Q).len() + 16;
+        let bytes = expand_message_xmd::<Sha256>(&BigInt::to_vec(&Q), BASE_POINT2_DST, len);
+        assert_eq!(point.g, BigInt::modulus(&BigInt::from(&bytes[..]), &Q));
+        assert!(point.is_valid());
+        assert!(point != Zqg::generator() && !point.is_zero());
         //let result1 = HSha256::create_hash_from_ge(&vec![&point, &Zqg::generator()]);
         //assert!(result1.to_big_int().to_str_radix(2).len() > 240);
         let result2 = HSha256::create_hash_from_ge(&vec![&Zqg::generator(), &point]);
@@ -16,39 +16,53 @@
 // The Public Key codec: Point <> SecretKey
 //
 
-use super::traits::{ECPoint, ECScalar};
+use super::hash_to_curve::{hash_to_field, Field, Isogeny, PrimeField, SimpleSwu};
+use super::traits::{ECPoint, ECScalar, HashToCurve};
+use crate::arithmetic::montgomery::{Limbs, Modulus256};
 use crate::arithmetic::traits::{Converter, Modulo};
 use crate::BigInt;
 use crate::ErrorKey;
 
-use rand::{thread_rng, Rng};
+use rand::{CryptoRng, RngCore};
 use secp256k1::constants::{
     CURVE_ORDER, GENERATOR_X, GENERATOR_Y, SECRET_KEY_SIZE, UNCOMPRESSED_PUBLIC_KEY_SIZE,
 };
-use secp256k1::{PublicKey, Secp256k1, SecretKey, VerifyOnly};
+use secp256k1::{PublicKey, Secp256k1, SecretKey, SignOnly, VerifyOnly};
 use serde::de;
 use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::ser::{Serialize, Serializer};
 use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
 use std::fmt;
 use std::ops::{Add, Mul};
 use std::ptr;
 use std::sync::{atomic, Once};
 use zeroize::Zeroize;
 
-/* X coordinate of a point of unknown discrete logarithm.
-Computed using a deterministic algorithm with the generator as input.
-See test_base_point2 */
-const BASE_POINT2_X: [u8; 32] = [
-    0x08, 0xd1, 0x32, 0x21, 0xe3, 0xa7, 0x32, 0x6a, 0x34, 0xdd, 0x45, 0x21, 0x4b, 0xa8, 0x01, 0x16,
-    0xdd, 0x14, 0x2e, 0x4b, 0x5f, 0xf3, 0xce, 0x66, 0xa8, 0xdc, 0x7b, 0xfa, 0x03, 0x78, 0xb7, 0x95,
+/* Order of the field the curve is defined over */
+pub const FIELD_PRIME: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xff, 0xff, 0xfc, 0x2f,
 ];
 
-const BASE_POINT2_Y: [u8; 32] = [
-    0x5d, 0x41, 0xac, 0x14, 0x77, 0x61, 0x4b, 0x5c, 0x08, 0x48, 0xd5, 0x0d, 0xbd, 0x56, 0x5e, 0xa2,
-    0x80, 0x7b, 0xcb, 0xa1, 0xdf, 0x0d, 0xf0, 0x7a, 0x82, 0x17, 0xe9, 0xf7, 0xf7, 0xc2, 0xbe, 0x88,
-];
+/* The order of the group (CURVE_ORDER) as Montgomery parameters, used for native scalar
+arithmetic. m_inv = -n^-1 mod 2^64, r2 = 2^512 mod n. */
+pub const CURVE_ORDER_MODULUS: Modulus256 = Modulus256 {
+    m: [
+        0xbfd2_5e8c_d036_4141,
+        0xbaae_dce6_af48_a03b,
+        0xffff_ffff_ffff_fffe,
+        0xffff_ffff_ffff_ffff,
+    ],
+    m_inv: 0x4b0d_ff66_5588_b13f,
+    r2: [
+        0x896c_f214_67d7_d140,
+        0x7414_96c2_0e7c_f878,
+        0xe697_f5e4_5bcd_07c6,
+        0x9d67_1cd5_81c6_9bc5,
+    ],
+};
 
 pub type SK = SecretKey;
 pub type PK = PublicKey;
@@ -62,12 +76,37 @@ pub struct Secp256k1Scalar {
 #[derive(Clone, Debug, Copy)]
 pub struct Secp256k1Point {
     purpose: &'static str,
-    ge: PK,
+    // None is the point at infinity, which libsecp256k1 public keys cannot represent
+    ge: Option<PK>,
 }
 
 pub type GE = Secp256k1Point;
 pub type FE = Secp256k1Scalar;
 
+lazy_static::lazy_static! {
+    static ref GENERATOR: Secp256k1Point = {
+        let mut v = vec![4 as u8];
+        v.extend(GENERATOR_X.as_ref());
+        v.extend(GENERATOR_Y.as_ref());
+        Secp256k1Point {
+            purpose: "base_fe",
+            ge: Some(PK::from_slice(&v).unwrap()),
+        }
+    };
+
+    /* base_point2 is the first NUMS generator seeded with the encoded generator, i.e. the hash
+    of G || I2OSP(0, 4) with DST "CURV-V01-CS01-NUMS-with-secp256k1_XMD:SHA-256_SSWU_RO_".
+    See test_base_point2 */
+    static ref BASE_POINT2: Secp256k1Point = {
+        let g = Secp256k1Point::generator();
+        let h = Secp256k1Point::nums_generators(&g.pk_to_key_slice(), 1)[0];
+        Secp256k1Point {
+            purpose: "random",
+            ge: h.ge,
+        }
+    };
+}
+
 impl Secp256k1Point {
     pub fn random_point() -> Secp256k1Point {
         let random_scalar: Secp256k1Scalar = Secp256k1Scalar::new_random();
@@ -75,7 +114,7 @@ impl Secp256k1Point {
         let pk = base_point.scalar_mul(&random_scalar.get_element());
         Secp256k1Point {
             purpose: "random_point",
-            ge: pk.get_element(),
+            ge: pk.ge,
         }
     }
     pub fn one() -> Self {
@@ -101,12 +140,17 @@ impl From<&BigInt> for Secp256k1Scalar {
 impl ECScalar for Secp256k1Scalar {
     type SecretKey = SK;
 
-    fn new_random() -> Secp256k1Scalar {
+    // rejection sampling: zero and values of at least the group order are not secret keys
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Secp256k1Scalar {
         let mut arr = [0u8; 32];
-        thread_rng().fill(&mut arr[..]);
-        Secp256k1Scalar {
-            purpose: "random",
-            fe: SK::from_slice(&arr[0..arr.len()]).unwrap(),
+        loop {
+            rng.fill_bytes(&mut arr[..]);
+            if let Ok(fe) = SK::from_slice(&arr[..]) {
+                return Secp256k1Scalar {
+                    purpose: "random",
+                    fe,
+                };
+            }
         }
     }
 
@@ -153,54 +197,51 @@ impl ECScalar for Secp256k1Scalar {
     }
 
     fn add(&self, other: &SK) -> Secp256k1Scalar {
-        let mut other_scalar: FE = ECScalar::new_random();
-        other_scalar.set_element(other.clone());
-        let res: FE = ECScalar::from(&BigInt::mod_add(
-            &self.to_big_int(),
-            &other_scalar.to_big_int(),
-            &FE::q(),
-        ));
+        let res = CURVE_ORDER_MODULUS.add(&sk_to_limbs(&self.fe), &sk_to_limbs(other));
         Secp256k1Scalar {
             purpose: "add",
-            fe: res.get_element(),
+            fe: sk_from_limbs(&res),
         }
     }
 
     fn mul(&self, other: &SK) -> Secp256k1Scalar {
-        let mut other_scalar: FE = ECScalar::new_random();
-        other_scalar.set_element(other.clone());
-        let res: FE = ECScalar::from(&BigInt::mod_mul(
-            &self.to_big_int(),
-            &other_scalar.to_big_int(),
-            &FE::q(),
-        ));
+        let res = CURVE_ORDER_MODULUS.mul(&sk_to_limbs(&self.fe), &sk_to_limbs(other));
         Secp256k1Scalar {
             purpose: "mul",
-            fe: res.get_element(),
+            fe: sk_from_limbs(&res),
         }
     }
 
     fn sub(&self, other: &SK) -> Secp256k1Scalar {
-        let mut other_scalar: FE = ECScalar::new_random();
-        other_scalar.set_element(other.clone());
-        let res: FE = ECScalar::from(&BigInt::mod_sub(
-            &self.to_big_int(),
-            &other_scalar.to_big_int(),
-            &FE::q(),
-        ));
+        let res = CURVE_ORDER_MODULUS.sub(&sk_to_limbs(&self.fe), &sk_to_limbs(other));
         Secp256k1Scalar {
             purpose: "sub",
-            fe: res.get_element(),
+            fe: sk_from_limbs(&res),
         }
     }
 
     fn invert(&self) -> Secp256k1Scalar {
-        let bignum = self.to_big_int();
-        let bn_inv = bignum.invert(&FE::q()).unwrap();
-        ECScalar::from(&bn_inv)
+        let res = CURVE_ORDER_MODULUS.invert(&sk_to_limbs(&self.fe));
+        Secp256k1Scalar {
+            purpose: "invert",
+            fe: sk_from_limbs(&res),
+        }
     }
 }
 
+// SecretKey is a plain 32 bytes big endian array. The library refuses to build a zero
+// SecretKey from a slice, so (as in ECScalar::zero) we construct it directly.
+fn sk_to_limbs(sk: &SK) -> Limbs {
+    let mut bytes = [0u8; SECRET_KEY_SIZE];
+    bytes.copy_from_slice(&sk[..]);
+    CURVE_ORDER_MODULUS.reduce(&Modulus256::from_bytes_be(&bytes))
+}
+
+fn sk_from_limbs(limbs: &Limbs) -> SK {
+    let bytes = Modulus256::to_bytes_be(limbs);
+    unsafe { std::mem::transmute::<[u8; SECRET_KEY_SIZE], SecretKey>(bytes) }
+}
+
 impl Mul<Secp256k1Scalar> for Secp256k1Scalar {
     type Output = Secp256k1Scalar;
     fn mul(self, other: Secp256k1Scalar) -> Secp256k1Scalar {
@@ -270,7 +311,7 @@ impl PartialEq for Secp256k1Scalar {
 
 impl PartialEq for Secp256k1Point {
     fn eq(&self, other: &Secp256k1Point) -> bool {
-        self.get_element() == other.get_element()
+        self.ge == other.ge
     }
 }
 
@@ -288,53 +329,62 @@ impl ECPoint for Secp256k1Point {
     type Scalar = Secp256k1Scalar;
 
     fn base_point2() -> Secp256k1Point {
-        let mut v = vec![4 as u8];
-        v.extend(BASE_POINT2_X.as_ref());
-        v.extend(BASE_POINT2_Y.as_ref());
-        Secp256k1Point {
-            purpose: "random",
-            ge: PK::from_slice(&v).unwrap(),
-        }
+        *BASE_POINT2
     }
 
     fn generator() -> Secp256k1Point {
-        let mut v = vec![4 as u8];
-        v.extend(GENERATOR_X.as_ref());
-        v.extend(GENERATOR_Y.as_ref());
+        *GENERATOR
+    }
+
+    fn zero() -> Secp256k1Point {
         Secp256k1Point {
-            purpose: "base_fe",
-            ge: PK::from_slice(&v).unwrap(),
+            purpose: "zero",
+            ge: None,
         }
     }
 
+    fn is_zero(&self) -> bool {
+        self.ge.is_none()
+    }
+
+    /// panics on the point at infinity, it has no PK representation
     fn get_element(&self) -> PK {
-        self.ge
+        self.ge.expect("the point at infinity has no public key representation")
     }
 
     /// to return from BigInt to PK use from_bytes:
     /// 1) convert BigInt::to_vec
     /// 2) remove first byte [1..33]
     /// 3) call from_bytes
+    /// the point at infinity is mapped to zero
     fn bytes_compressed_to_big_int(&self) -> BigInt {
-        let serial = self.ge.serialize();
-        BigInt::from(&serial[0..33])
+        match self.ge {
+            Some(ge) => BigInt::from(&ge.serialize()[0..33]),
+            None => BigInt::zero(),
+        }
     }
 
     fn x_coor(&self) -> Option<BigInt> {
-        let serialized_pk = PK::serialize_uncompressed(&self.ge);
-        let x = &serialized_pk[1..serialized_pk.len() / 2 + 1];
-        let x_vec = x.to_vec();
-        Some(BigInt::from(&x_vec[..]))
+        self.ge.map(|ge| {
+            let serialized_pk = PK::serialize_uncompressed(&ge);
+            let x = &serialized_pk[1..serialized_pk.len() / 2 + 1];
+            BigInt::from(x)
+        })
     }
 
     fn y_coor(&self) -> Option<BigInt> {
-        let serialized_pk = PK::serialize_uncompressed(&self.ge);
-        let y = &serialized_pk[(serialized_pk.len() - 1) / 2 + 1..serialized_pk.len()];
-        let y_vec = y.to_vec();
-        Some(BigInt::from(&y_vec[..]))
+        self.ge.map(|ge| {
+            let serialized_pk = PK::serialize_uncompressed(&ge);
+            let y = &serialized_pk[(serialized_pk.len() - 1) / 2 + 1..serialized_pk.len()];
+            BigInt::from(y)
+        })
     }
 
+    /// the SEC1 encoding [0x00] is the point at infinity, any other all zero input is rejected
     fn from_bytes(bytes: &[u8]) -> Result<Secp256k1Point, ErrorKey> {
+        if bytes == [0u8] {
+            return Ok(Secp256k1Point::zero());
+        }
         let bytes_vec = bytes.to_vec();
         let mut bytes_array_65 = [0u8; 65];
         let mut bytes_array_33 = [0u8; 33];
@@ -353,7 +403,7 @@ impl ECPoint for Secp256k1Point {
                 let result = PK::from_slice(&bytes_array_65);
                 let test = result.map(|pk| Secp256k1Point {
                     purpose: "random",
-                    ge: pk,
+                    ge: Some(pk),
                 });
                 test.map_err(|_err| ErrorKey::InvalidPublicKey)
             }
@@ -370,7 +420,7 @@ impl ECPoint for Secp256k1Point {
                 let result = PK::from_slice(&bytes_array_33);
                 let test = result.map(|pk| Secp256k1Point {
                     purpose: "random",
-                    ge: pk,
+                    ge: Some(pk),
                 });
                 test.map_err(|_err| ErrorKey::InvalidPublicKey)
             }
@@ -385,13 +435,17 @@ impl ECPoint for Secp256k1Point {
                 let result = PK::from_slice(&bytes_array_65);
                 let test = result.map(|pk| Secp256k1Point {
                     purpose: "random",
-                    ge: pk,
+                    ge: Some(pk),
                 });
                 test.map_err(|_err| ErrorKey::InvalidPublicKey)
             }
         }
     }
+    /// the point at infinity is encoded as in SEC1 by the single byte 0x00
     fn pk_to_key_slice(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return vec![0];
+        }
         let mut v = vec![4 as u8];
         let x_vec = BigInt::to_vec(&self.x_coor().unwrap());
         let y_vec = BigInt::to_vec(&self.y_coor().unwrap());
@@ -410,25 +464,32 @@ impl ECPoint for Secp256k1Point {
     }
 
     fn scalar_mul(&self, fe: &SK) -> Secp256k1Point {
-        let mut new_point = *self;
-        new_point
-            .ge
-            .mul_assign(get_context(), &fe[..])
-            .expect("Assignment expected");
-        new_point
+        // libsecp256k1 rejects a zero tweak, the product is the point at infinity then
+        let ge = self.ge.and_then(|mut ge| {
+            ge.mul_assign(get_context(), &fe[..]).ok().map(|_| ge)
+        });
+        Secp256k1Point {
+            purpose: "mul",
+            ge,
+        }
     }
 
     fn add_point(&self, other: &PK) -> Secp256k1Point {
+        // combine fails only if the sum is the point at infinity
+        let ge = match self.ge {
+            Some(ge) => ge.combine(other).ok(),
+            None => Some(*other),
+        };
         Secp256k1Point {
             purpose: "combine",
-            ge: self.ge.combine(other).unwrap(),
+            ge,
         }
     }
 
     fn sub_point(&self, other: &PK) -> Secp256k1Point {
         let point = Secp256k1Point {
             purpose: "sub_point",
-            ge: *other,
+            ge: Some(*other),
         };
         let p: Vec<u8> = vec![
             255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
@@ -485,11 +546,97 @@ impl ECPoint for Secp256k1Point {
 
         Secp256k1Point {
             purpose: "base_fe",
-            ge: PK::from_slice(&v).unwrap(),
+            ge: Some(PK::from_slice(&v).unwrap()),
+        }
+    }
+
+    // libsecp256k1 only creates valid public keys, we double check the curve equation y^2 = x^3 + 7
+    fn is_valid(&self) -> bool {
+        let (x, y) = match (self.x_coor(), self.y_coor()) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return true,
+        };
+        let p = BigInt::from(&FIELD_PRIME[..]);
+        let y_square = BigInt::mod_mul(&y, &y, &p);
+        let x_cube = BigInt::mod_pow(&x, &BigInt::from(3), &p);
+        y_square == BigInt::mod_add(&x_cube, &BigInt::from(7), &p)
+    }
+
+    // uses the precomputed generator table of the libsecp256k1 signing context. There is no
+    // override for base_point2: libsecp256k1 has no table for other points and its variable
+    // base multiplication is faster than a table of affine points added with combine.
+    fn mul_base(scalar: &Secp256k1Scalar) -> Secp256k1Point {
+        // libsecp256k1 rejects a zero secret key
+        if Modulus256::is_zero(&sk_to_limbs(&scalar.fe)) {
+            return Self::zero();
+        }
+        Secp256k1Point {
+            purpose: "mul_base",
+            ge: Some(PK::from_secret_key(get_signing_context(), &scalar.fe)),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    // simplified SWU to the curve y^2 = x^3 + A' * x + B' followed by a 3-isogeny to secp256k1,
+    // constants from RFC 9380 section 8.7 and appendix E.1
+    static ref SSWU: SimpleSwu<PrimeField> = {
+        let field = PrimeField::new(BigInt::from(&FIELD_PRIME[..]));
+        let a = BigInt::from_hex("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533");
+        let b = BigInt::from(1771);
+        let z = field.neg(&BigInt::from(11));
+        let isogeny = Isogeny::from_hex(
+            &[
+                "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+                "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+                "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+                "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+            ],
+            &[
+                "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+                "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+                "1",
+            ],
+            &[
+                "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+                "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+                "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+                "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+            ],
+            &[
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+                "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+                "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+                "1",
+            ],
+        );
+        SimpleSwu::new(field, a, b, z).with_isogeny(isogeny)
+    };
+}
+
+impl Secp256k1Point {
+    fn map_to_curve(u: &BigInt) -> Secp256k1Point {
+        match SSWU.map_to_curve(u) {
+            Some((x, y)) => Secp256k1Point::from_coor(&x, &y),
+            None => Secp256k1Point::zero(),
         }
     }
 }
 
+impl HashToCurve for Secp256k1Point {
+    const SUITE_ID: &'static str = "secp256k1_XMD:SHA-256_SSWU_RO_";
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Secp256k1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, 2, SSWU.field.p(), 48);
+        Secp256k1Point::map_to_curve(&u[0]) + Secp256k1Point::map_to_curve(&u[1])
+    }
+
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> Secp256k1Point {
+        let u = hash_to_field::<Sha256>(msg, dst, 1, SSWU.field.p(), 48);
+        Secp256k1Point::map_to_curve(&u[0])
+    }
+}
+
 static mut CONTEXT: Option<Secp256k1<VerifyOnly>> = None;
 
 pub fn get_context() -> &'static Secp256k1<VerifyOnly> {
@@ -500,6 +647,16 @@ pub fn get_context() -> &'static Secp256k1<VerifyOnly> {
     unsafe { CONTEXT.as_ref().unwrap() }
 }
 
+static mut SIGNING_CONTEXT: Option<Secp256k1<SignOnly>> = None;
+
+pub fn get_signing_context() -> &'static Secp256k1<SignOnly> {
+    static INIT_CONTEXT: Once = Once::new();
+    INIT_CONTEXT.call_once(|| unsafe {
+        SIGNING_CONTEXT = Some(Secp256k1::signing_only());
+    });
+    unsafe { SIGNING_CONTEXT.as_ref().unwrap() }
+}
+
 #[cfg(feature = "merkle")]
 impl Hashable for Secp256k1Point {
     fn update_context(&self, context: &mut Sha3) {
@@ -532,21 +689,30 @@ impl<'o> Mul<&'o Secp256k1Scalar> for &'o Secp256k1Point {
 impl Add<Secp256k1Point> for Secp256k1Point {
     type Output = Secp256k1Point;
     fn add(self, other: Secp256k1Point) -> Self::Output {
-        self.add_point(&other.get_element())
+        match other.ge {
+            Some(ge) => self.add_point(&ge),
+            None => self,
+        }
     }
 }
 
 impl<'o> Add<&'o Secp256k1Point> for Secp256k1Point {
     type Output = Secp256k1Point;
     fn add(self, other: &'o Secp256k1Point) -> Self::Output {
-        self.add_point(&other.get_element())
+        match other.ge {
+            Some(ge) => self.add_point(&ge),
+            None => self,
+        }
     }
 }
 
 impl<'o> Add<&'o Secp256k1Point> for &'o Secp256k1Point {
     type Output = Secp256k1Point;
     fn add(self, other: &'o Secp256k1Point) -> Self::Output {
-        self.add_point(&other.get_element())
+        match other.ge {
+            Some(ge) => self.add_point(&ge),
+            None => *self,
+        }
     }
 }
 
@@ -555,9 +721,12 @@ impl Serialize for Secp256k1Point {
         where
             S: Serializer,
     {
+        // the point at infinity is serialized as (0, 0) which is not on the curve
+        let x = self.x_coor().unwrap_or_else(BigInt::zero);
+        let y = self.y_coor().unwrap_or_else(BigInt::zero);
         let mut state = serializer.serialize_struct("Secp256k1Point", 2)?;
-        state.serialize_field("x", &self.x_coor().unwrap().to_hex())?;
-        state.serialize_field("y", &self.y_coor().unwrap().to_hex())?;
+        state.serialize_field("x", &x.to_hex())?;
+        state.serialize_field("y", &y.to_hex())?;
         state.end()
     }
 }
@@ -592,10 +761,7 @@ impl<'de> Visitor<'de> for Secp256k1PointVisitor {
             .next_element()?
             .ok_or_else(|| panic!("deserialization failed"))?;
 
-        let bx = BigInt::from_hex(x);
-        let by = BigInt::from_hex(y);
-
-        Ok(Secp256k1Point::from_coor(&bx, &by))
+        point_from_coor_hex(x, y)
     }
 
     fn visit_map<E: MapAccess<'de>>(self, mut map: E) -> Result<Secp256k1Point, E::Error> {
@@ -613,10 +779,28 @@ impl<'de> Visitor<'de> for Secp256k1PointVisitor {
             }
         }
 
-        let bx = BigInt::from_hex(&x);
-        let by = BigInt::from_hex(&y);
+        point_from_coor_hex(&x, &y)
+    }
+}
 
-        Ok(Secp256k1Point::from_coor(&bx, &by))
+// points come from counterparties: return an error instead of panicking on invalid coordinates
+fn point_from_coor_hex<E: de::Error>(x: &str, y: &str) -> Result<Secp256k1Point, E> {
+    if BigInt::from_hex(x).is_zero() && BigInt::from_hex(y).is_zero() {
+        return Ok(Secp256k1Point::zero());
+    }
+    let coor_size = (UNCOMPRESSED_PUBLIC_KEY_SIZE - 1) / 2;
+    let mut bytes = Vec::with_capacity(2 * coor_size);
+    for coor in [x, y].iter() {
+        let coor_vec = BigInt::to_vec(&BigInt::from_hex(coor));
+        if coor_vec.len() > coor_size {
+            return Err(E::custom("invalid Secp256k1Point coordinate"));
+        }
+        bytes.extend(vec![0u8; coor_size - coor_vec.len()]);
+        bytes.extend(coor_vec);
+    }
+    match Secp256k1Point::from_bytes(&bytes) {
+        Ok(point) if point.is_valid() => Ok(point),
+        _ => Err(E::custom("invalid Secp256k1Point")),
     }
 }
 
@@ -631,6 +815,7 @@ mod tests {
     use crate::cryptographic_primitives::hashing::traits::Hash;
     use crate::elliptic::curves::traits::ECPoint;
     use crate::elliptic::curves::traits::ECScalar;
+    use crate::elliptic::curves::traits::HashToCurve;
     use bincode;
     use serde_json;
 
@@ -775,6 +960,64 @@ mod tests {
         assert!(result.is_ok() | result.is_err())
     }
 
+    #[test]
+    fn test_from_bytes_zero() {
+        // only the SEC1 encoding [0x00] is the point at infinity
+        assert!(Secp256k1Point::from_bytes(&[0u8]).unwrap().is_zero());
+        for len in [0usize, 32, 33, 64, 65].iter() {
+            assert_eq!(
+                Secp256k1Point::from_bytes(&vec![0u8; *len]).unwrap_err(),
+                ErrorKey::InvalidPublicKey
+            );
+        }
+    }
+
+    // returns all zero bytes, then all 0xff bytes, then 1s
+    struct InvalidFirstRng {
+        calls: u8,
+    }
+
+    impl rand::RngCore for InvalidFirstRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0u8; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            let byte = match self.calls {
+                0 => 0,
+                1 => 0xff,
+                _ => 1,
+            };
+            self.calls += 1;
+            for d in dest.iter_mut() {
+                *d = byte;
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl rand::CryptoRng for InvalidFirstRng {}
+
+    #[test]
+    fn test_new_random_rejects_invalid_draws() {
+        let mut rng = InvalidFirstRng { calls: 0 };
+        let s: FE = ECScalar::new_random_with_rng(&mut rng);
+        assert_eq!(rng.calls, 3);
+        assert_eq!(s.to_big_int(), BigInt::from(&[1u8; 32][..]));
+    }
+
     #[test]
     fn test_minus_point() {
         let a: FE = ECScalar::new_random();
@@ -830,25 +1073,88 @@ mod tests {
     #[test]
     fn test_base_point2() {
         /* Show that base_point2() is returning a point of unknown discrete logarithm.
-        It is done by using SHA256 repeatedly as a pseudo-random function, with the generator
-        as the initial input, until receiving a valid Secp256k1 point. */
+        It is hashed to the curve with the generator as input. */
 
         let base_point2 = Secp256k1Point::base_point2();
 
         let g = Secp256k1Point::generator();
-        let mut hash = HSha256::create_hash(&[&g.bytes_compressed_to_big_int()]);
-        hash = HSha256::create_hash(&[&hash]);
-        hash = HSha256::create_hash(&[&hash]);
+        let seed = [&g.pk_to_key_slice()[..], &[0u8; 4]].concat();
+        let dst = b"CURV-V01-CS01-NUMS-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+        assert_eq!(Secp256k1Point::hash_to_curve(&seed, dst), base_point2);
+        assert_eq!(
+            base_point2.x_coor().unwrap().to_hex(),
+            "11dd9227a4193dd555ded0a71fc9ba69e9fe5fd6bb2637b500583fc4bd208909"
+        );
+        assert!(!base_point2.is_zero());
+        assert_ne!(base_point2, g);
+    }
 
-        assert_eq!(hash, base_point2.x_coor().unwrap(), );
+    // RFC 9380 appendix J.8.1
+    #[test]
+    fn test_hash_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+        let vectors: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+            (
+                b"abc",
+                "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+            (
+                b"abcdef0123456789",
+                "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let p = Secp256k1Point::hash_to_curve(msg, dst);
+            assert_eq!(p.x_coor().unwrap(), BigInt::from_hex(x));
+            assert_eq!(p.y_coor().unwrap(), BigInt::from_hex(y));
+        }
 
-        // check that base_point2 is indeed on the curve (from_coor() will fail otherwise)
+        // the map of u0 for the empty message
+        let u0 =
+            BigInt::from_hex("6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3");
+        let q0 = Secp256k1Point::map_to_curve(&u0);
         assert_eq!(
-            Secp256k1Point::from_coor(
-                &base_point2.x_coor().unwrap(),
-                &base_point2.y_coor().unwrap(),
-            ),
-            base_point2
+            q0.x_coor().unwrap(),
+            BigInt::from_hex("74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e")
+        );
+        assert_eq!(
+            q0.y_coor().unwrap(),
+            BigInt::from_hex("c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936")
         );
     }
+
+    // RFC 9380 appendix J.8.2
+    #[test]
+    fn test_encode_to_curve() {
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_NU_";
+        let vectors: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "a4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b",
+                "62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7",
+            ),
+            (
+                b"abc",
+                "3f3b5842033fff837d504bb4ce2a372bfeadbdbd84a1d2b678b6e1d7ee426b9d",
+                "902910d1fef15d8ae2006fc84f2a5a7bda0e0407dc913062c3a493c4f5d876a5",
+            ),
+            (
+                b"abcdef0123456789",
+                "07644fa6281c694709f53bdd21bed94dab995671e4a8cd1904ec4aa50c59bfdf",
+                "c79f8d1dad79b6540426922f7fbc9579c3018dafeffcd4552b1626b506c21e7b",
+            ),
+        ];
+        for (msg, x, y) in vectors.iter() {
+            let p = Secp256k1Point::encode_to_curve(msg, dst);
+            assert_eq!(p.x_coor().unwrap(), BigInt::from_hex(x));
+            assert_eq!(p.y_coor().unwrap(), BigInt::from_hex(y));
+        }
+    }
 }
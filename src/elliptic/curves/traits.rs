@@ -7,13 +7,20 @@
 
 use std::ops::{Add, Mul};
 
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+use super::multi_scalar_mul;
 use crate::BigInt;
 use crate::ErrorKey;
 
 pub trait ECScalar: Mul<Output=Self> + Add<Output=Self>  + Sized{
     type SecretKey;
 
-    fn new_random() -> Self;
+    fn new_random_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Self;
+    fn new_random() -> Self {
+        Self::new_random_with_rng(&mut OsRng::new().unwrap())
+    }
     fn zero() -> Self;
     fn get_element(&self) -> Self::SecretKey;
     fn set_element(&mut self, element: Self::SecretKey);
@@ -32,7 +39,6 @@ pub trait ECScalar: Mul<Output=Self> + Add<Output=Self>  + Sized{
 //     }
 // }
 
-// TODO: add a fn is_point
 pub trait ECPoint:
 Mul<<Self as ECPoint>::Scalar, Output=Self> + Add<Output=Self> + PartialEq
     where
@@ -45,6 +51,10 @@ Mul<<Self as ECPoint>::Scalar, Output=Self> + Add<Output=Self> + PartialEq
 
     fn base_point2() -> Self;
     fn generator() -> Self;
+
+    /// returns the identity element of the group (the point at infinity).
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
     fn get_element(&self) -> Self::PublicKey;
     fn x_coor(&self) -> Option<BigInt>;
     fn y_coor(&self) -> Option<BigInt>;
@@ -55,4 +65,128 @@ Mul<<Self as ECPoint>::Scalar, Output=Self> + Add<Output=Self> + PartialEq
     fn add_point(&self, other: &Self::PublicKey) -> Self;
     fn sub_point(&self, other: &Self::PublicKey) -> Self;
     fn from_coor(x: &BigInt, y: &BigInt) -> Self;
+
+    /// returns true if the point is a valid element of the group (e.g. it lies on the curve).
+    fn is_valid(&self) -> bool;
+
+    /// returns true if the point is in the subgroup of prime order q generated by generator().
+    /// For curves of prime order (cofactor 1) this is the same as is_valid.
+    fn is_in_prime_order_subgroup(&self) -> bool {
+        self.is_valid()
+    }
+
+    /// returns generator() * scalar. Curves override it using a precomputed table of multiples
+    /// of the generator, which is much faster than a generic scalar multiplication.
+    fn mul_base(scalar: &Self::Scalar) -> Self {
+        Self::generator().scalar_mul(&scalar.get_element())
+    }
+
+    /// returns base_point2() * scalar, see mul_base.
+    fn mul_base_point2(scalar: &Self::Scalar) -> Self {
+        Self::base_point2().scalar_mul(&scalar.get_element())
+    }
+
+    /// computes scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]. Uses Straus' method
+    /// for small inputs and Pippenger's for large ones. Curves may override it with a native
+    /// implementation.
+    fn multi_scalar_mul(points: &[Self], scalars: &[Self::Scalar]) -> Self
+    where
+        Self: Clone,
+    {
+        let scalars = scalars.iter().map(|k| k.to_big_int()).collect::<Vec<_>>();
+        multi_scalar_mul::multi_scalar_mul(points, &scalars).unwrap_or_else(Self::zero)
+    }
+}
+
+/// A bilinear map e: G1 x G2 -> Gt. Gt is written additively, like the groups of points:
+/// e(a * P, b * Q) = (a * b) * e(P, Q) and e(P1 + P2, Q) = e(P1, Q) + e(P2, Q).
+pub trait Pairing {
+    type G1: ECPoint;
+    type G2: ECPoint;
+    type Gt: PartialEq;
+
+    fn pairing(p: &Self::G1, q: &Self::G2) -> Self::Gt;
+
+    /// computes e(p_1, q_1) + ... + e(p_n, q_n). Implementations share the final exponentiation
+    /// between the terms (multi Miller loop), which is much faster than n pairings.
+    fn multi_pairing(terms: &[(&Self::G1, &Self::G2)]) -> Self::Gt;
+}
+
+/// Hashing arbitrary messages to group elements as specified in RFC 9380. The output of
+/// hash_to_curve is indistinguishable from a random oracle, encode_to_curve is cheaper but its
+/// output is not uniformly distributed. Both are deterministic and nobody knows the discrete log
+/// of the result.
+pub trait HashToCurve: ECPoint {
+    /// the suite implemented by the curve, e.g. "secp256k1_XMD:SHA-256_SSWU_RO_"
+    const SUITE_ID: &'static str;
+
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self;
+    fn encode_to_curve(msg: &[u8], dst: &[u8]) -> Self;
+
+    /// derives n generators whose discrete logs with respect to each other and to generator()
+    /// are unknown ("nothing up my sleeve"). The i-th generator is the hash of seed || I2OSP(i, 4),
+    /// so anyone can recompute them from the seed.
+    fn nums_generators(seed: &[u8], n: usize) -> Vec<Self> {
+        let dst = format!("CURV-V01-CS01-NUMS-with-{}", Self::SUITE_ID);
+        (0..n as u32)
+            .map(|i| {
+                let mut msg = seed.to_vec();
+                msg.extend_from_slice(&i.to_be_bytes());
+                Self::hash_to_curve(&msg, dst.as_bytes())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ECPoint, ECScalar};
+    use crate::test_for_all_curves;
+    use crate::BigInt;
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+
+    test_for_all_curves!(test_zero);
+    fn test_zero<P>()
+    where
+        P: ECPoint + Clone + std::fmt::Debug,
+    {
+        let zero = P::zero();
+        let g = P::generator();
+        assert!(zero.is_zero());
+        assert!(!g.is_zero());
+        assert!(zero.is_valid());
+        assert!(zero.is_in_prime_order_subgroup());
+
+        assert_eq!(zero.clone() + g.clone(), g);
+        assert_eq!(g.clone() + zero.clone(), g);
+        assert_eq!(zero.add_point(&g.get_element()), g);
+        assert!(g.sub_point(&g.get_element()).is_zero());
+
+        let k: P::Scalar = ECScalar::new_random();
+        assert!((g.clone() * ECScalar::zero()).is_zero());
+        assert!((zero.clone() * k).is_zero());
+        assert!(P::mul_base(&ECScalar::zero()).is_zero());
+        assert!(P::mul_base_point2(&ECScalar::zero()).is_zero());
+        assert!(P::multi_scalar_mul(&[], &[]).is_zero());
+
+        // g * (q - 1) + g
+        let minus_one: P::Scalar = ECScalar::from(&(P::Scalar::q() - BigInt::from(1)));
+        assert!((g.clone() * minus_one + g).is_zero());
+    }
+
+    test_for_all_curves!(test_zero_serialization);
+    fn test_zero_serialization<P>()
+    where
+        P: ECPoint + Serialize + DeserializeOwned + std::fmt::Debug,
+    {
+        let zero = P::zero();
+        let s = serde_json::to_string(&zero).expect("Failed in serialization");
+        let des_zero: P = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_zero, zero);
+
+        let bytes = zero.pk_to_key_slice();
+        let zero_from_bytes = P::from_bytes(&bytes).expect("Failed to decode the identity");
+        assert!(zero_from_bytes.is_zero());
+    }
 }
@@ -12,7 +12,12 @@ pub extern crate lazy_static;
 pub mod elliptic;
 
 pub mod arithmetic;
+#[cfg(feature = "rust-gmp-kzen")]
 pub use crate::arithmetic::big_gmp::BigInt;
+#[cfg(all(feature = "num-bigint", not(feature = "rust-gmp-kzen")))]
+pub use crate::arithmetic::big_native::BigInt;
+#[cfg(not(any(feature = "rust-gmp-kzen", feature = "num-bigint")))]
+compile_error!("enable a BigInt backend: the `rust-gmp-kzen` (default) or `num-bigint` feature");
 //pub use lazy_static;
 pub mod cryptographic_primitives;
 
@@ -21,8 +26,10 @@ pub enum ErrorKey {
     InvalidPublicKey,
 }
 
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
 pub enum ErrorSS {
     VerifyShareError,
+    NotEnoughValidShares,
 }
 
 #[cfg(test)]
@@ -34,6 +41,27 @@ macro_rules! test_for_all_curves {
     ($fn: ident) => {
         crate::test_for_all_curves!([] $fn);
     };
+    ([$($attrs:tt)*] $fn: ident) => {
+        crate::test_for_all_elliptic_curves!([$($attrs)*] $fn);
+        paste::paste!{
+            #[test]
+            $($attrs)*
+            fn [<$fn _integer_group>]() {
+                $fn::<crate::elliptic::curves::integer_group::GE>()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+#[macro_export]
+macro_rules! test_for_all_elliptic_curves {
+    (#[should_panic] $fn: ident) => {
+        crate::test_for_all_elliptic_curves!([#[should_panic]] $fn);
+    };
+    ($fn: ident) => {
+        crate::test_for_all_elliptic_curves!([] $fn);
+    };
     ([$($attrs:tt)*] $fn: ident) => {
         paste::paste!{
             #[test]
@@ -61,11 +89,6 @@ macro_rules! test_for_all_curves {
             fn [<$fn _p256>]() {
                 $fn::<crate::elliptic::curves::p256::GE>()
             }
-            #[test]
-            $($attrs)*
-            fn [<$fn _integer_group>]() {
-                $fn::<crate::elliptic::curves::integer_group::GE>()
-            }
         }
     };
 }